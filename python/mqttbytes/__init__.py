@@ -0,0 +1,7 @@
+# The extension module is built into this package as `mqttbytes.mqttbytes`.
+# It registers its submodules, such as `mqttbytes.v4`, in `sys.modules`, and
+# the modules written in Python are imported next to it.
+from .mqttbytes import *  # noqa: F401,F403
+from .mqttbytes import __version__  # noqa: F401
+from . import aio, client  # noqa: F401
+from .client import AsyncClient, Client  # noqa: F401
@@ -0,0 +1,217 @@
+# Generated by tools/generate_stubs.py, do not edit.
+from typing import Any, ClassVar, Iterator
+from . import aio as aio, capture as capture, client as client, testing as testing
+from . import v4 as v4, v5 as v5
+from .client import AsyncClient as AsyncClient, Client as Client
+
+__version__: str
+
+class MqttBytesError(Exception):
+    """Error during serialization and deserialization."""
+
+class ProtocolError(Exception):
+    """Packet received or sent out of order with the MQTT protocol flow."""
+
+class ValidationError(MqttBytesError):
+    """Packet breaking a rule of the MQTT specification, raised by `validate()` and strict reads and writes."""
+
+class InsufficientBytes(MqttBytesError):
+    """More bytes are required to frame a packet. `required` is the minimum number of additional bytes."""
+    required: int
+
+class NotConnect(MqttBytesError):
+    """First packet isn't a Connect. `packet_type` is the type of the packet received."""
+    packet_type: PacketType
+
+class UnexpectedConnect(MqttBytesError):
+    """Connect packet received on an established connection."""
+
+class InvalidConnectReturnCode(MqttBytesError):
+    """Unknown ConnAck return code, stored in `code`."""
+    code: int
+
+class InvalidReason(MqttBytesError):
+    """Unknown reason code, stored in `code`."""
+    code: int
+
+class InvalidProtocol(MqttBytesError):
+    """Connect packet with a protocol name other than MQTT."""
+
+class InvalidProtocolLevel(MqttBytesError):
+    """Connect packet with an unsupported protocol level, stored in `level`."""
+    level: int
+
+class IncorrectPacketFormat(MqttBytesError):
+    """Packet fields are inconsistent with its type."""
+
+class InvalidPacketType(MqttBytesError):
+    """Unknown packet type, stored in `packet_type`."""
+    packet_type: int
+
+class InvalidPropertyType(MqttBytesError):
+    """Unknown property identifier, stored in `property_type`."""
+    property_type: int
+
+class InvalidRetainForwardRule(MqttBytesError):
+    """Unknown retain handling option, stored in `rule`."""
+    rule: int
+
+class InvalidQoS(MqttBytesError):
+    """QoS other than 0, 1 or 2, stored in `qos`."""
+    qos: int
+
+class InvalidSubscribeReasonCode(MqttBytesError):
+    """Unknown SubAck return code, stored in `code`."""
+    code: int
+
+class PacketIdZero(MqttBytesError):
+    """Packet id 0 used by a packet that requires one."""
+
+class SubscriptionIdZero(MqttBytesError):
+    """Subscription identifier 0 in a v5 packet."""
+
+class PayloadSizeIncorrect(MqttBytesError):
+    """Payload length doesn't match the remaining length."""
+
+class PayloadTooLong(MqttBytesError):
+    """Payload is longer than the protocol allows."""
+
+class PayloadSizeLimitExceeded(MqttBytesError):
+    """Packet is larger than `max_size`. `size` is the size of the packet."""
+    size: int
+
+class PayloadRequired(MqttBytesError):
+    """Packet requires a payload and has none."""
+
+class TopicNotUtf8(MqttBytesError):
+    """Topic or string field isn't valid UTF-8."""
+
+class BoundaryCrossed(MqttBytesError):
+    """Field extends past the end of the packet. `length` is the length read."""
+    length: int
+
+class MalformedPacket(MqttBytesError):
+    """Packet can't be decoded."""
+
+class MalformedRemainingLength(MqttBytesError):
+    """Remaining length is encoded with more than 4 bytes."""
+
+class FixedHeader:
+    """Packet type from a byte.
+
+    ```
+             7                          3                          0
+             +--------------------------+--------------------------+
+    byte 1   | MQTT Control Packet Type | Flags for each type      |
+             +--------------------------+--------------------------+
+             |         Remaining Bytes Len  (1/2/3/4 bytes)        |
+             +-----------------------------------------------------+
+
+    http://docs.oasis-open.org/mqtt/mqtt/v3.1.1/os/mqtt-v3.1.1-os.html#_Figure_2.2_-
+    ```
+    """
+    def __init__(self, byte1: int, remaining_len_len: int, remaining_len: int) -> None: ...
+    def packet_type(self) -> PacketType: ...
+    def frame_length(self) -> int:
+        """Returns the size of full packet (fixed header + variable header + payload).
+        Fixed header is enough to get the size of a frame in the stream.
+        """
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
+class PacketType:
+    """MQTT packet type."""
+    Connect: ClassVar[PacketType]
+    ConnAck: ClassVar[PacketType]
+    Publish: ClassVar[PacketType]
+    PubAck: ClassVar[PacketType]
+    PubRec: ClassVar[PacketType]
+    PubRel: ClassVar[PacketType]
+    PubComp: ClassVar[PacketType]
+    Subscribe: ClassVar[PacketType]
+    SubAck: ClassVar[PacketType]
+    Unsubscribe: ClassVar[PacketType]
+    UnsubAck: ClassVar[PacketType]
+    PingReq: ClassVar[PacketType]
+    PingResp: ClassVar[PacketType]
+    Disconnect: ClassVar[PacketType]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Protocol:
+    V4: ClassVar[Protocol]
+    V5: ClassVar[Protocol]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class QoS:
+    """Quality of service."""
+    AtMostOnce: ClassVar[QoS]
+    AtLeastOnce: ClassVar[QoS]
+    ExactlyOnce: ClassVar[QoS]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class SubscriptionTree:
+    """Topic filters mapped to arbitrary values, matched against topics in one call.
+
+    Filters are split on '/' into a trie so that matching a topic only visits
+    the branches that can match, including the '+' and '#' wildcards. As
+    required by the MQTT specification, filters starting with a wildcard don't
+    match topics starting with '$'.
+    """
+    def __init__(self) -> None: ...
+    def insert(self, filter: str, value: Any) -> Any | None:
+        """Maps `filter` to `value` and returns the value it replaced, if any.
+        Raises `ValueError` if `filter` is not a valid topic filter.
+        """
+    def remove(self, filter: str) -> Any | None:
+        """Removes `filter` and returns its value."""
+    def get(self, filter: str) -> Any | None:
+        """Returns the value mapped to `filter`, if any."""
+    def matches(self, topic: str) -> list[tuple[str, Any]]:
+        """Returns the `(filter, value)` entries whose filter matches `topic`."""
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __contains__(self, filter: str) -> bool: ...
+    def __iter__(self) -> Iterator[tuple[str, Any]]:
+        """Iterates over the `(filter, value)` entries."""
+
+def check(stream: bytes, max_packet_size: int) -> FixedHeader:
+    """Checks if the stream has enough bytes to frame a packet and returns fixed header
+    only if a packet can be framed with existing bytes in the `stream`.
+    The passed stream doesn't modify parent stream's cursor. If this function
+    returned an error, next `check` on the same parent stream is forced start
+    with cursor at 0 again (Iter is owned. Only Iter's cursor is changed internally).
+    """
+
+def has_wildcards(s: str) -> bool:
+    """Checks if a topic or topic filter has wildcards."""
+
+def matches(topic: str, filter: str) -> bool:
+    """Checks if topic matches a filter. topic and filter validation isn't done here.
+
+    **NOTE**: 'topic' is a misnomer in the arg. this can also be used to match 2 wild subscriptions.
+    **NOTE**: make sure a topic is validated during a publish and filter is validated
+    during a subscribe.
+    """
+
+def qos(num: int) -> QoS:
+    """Maps a number to QoS."""
+
+def valid_filter(filter: str) -> bool:
+    """Checks if the filter is valid.
+
+    https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/os/mqtt-v3.1.1-os.html#_Toc398718106
+    """
+
+def valid_topic(topic: str) -> bool:
+    """Checks if a topic is valid."""
@@ -0,0 +1,47 @@
+"""Reading and writing MQTT 3.1.1 packets on asyncio streams.
+
+Framing is done by `mqttbytes.check`: the bytes read so far are checked and
+exactly the number of missing bytes it reports is read next, so no byte past
+the end of a packet is ever consumed from the reader.
+"""
+
+import asyncio
+
+from mqttbytes import InsufficientBytes, check, v4
+
+
+async def read_packet(reader, max_size):
+    """Reads the next packet from an `asyncio.StreamReader`.
+
+    Returns None if the stream ends before a new packet, and raises
+    `asyncio.IncompleteReadError` if it ends in the middle of one.
+    """
+    frame = b""
+    while True:
+        try:
+            check(frame, max_size)
+            break
+        except InsufficientBytes as err:
+            try:
+                frame += await reader.readexactly(err.required)
+            except asyncio.IncompleteReadError as eof:
+                if frame or eof.partial:
+                    raise asyncio.IncompleteReadError(frame + eof.partial, None) from None
+                return None
+    packet, _ = v4.read_from(frame, 0, max_size)
+    return packet
+
+
+async def write_packet(writer, packet):
+    """Writes a packet to an `asyncio.StreamWriter` and waits for it to drain."""
+    writer.write(packet.write())
+    await writer.drain()
+
+
+async def iter_packets(reader, max_size):
+    """Yields packets from an `asyncio.StreamReader` until the stream ends."""
+    while True:
+        packet = await read_packet(reader, max_size)
+        if packet is None:
+            return
+        yield packet
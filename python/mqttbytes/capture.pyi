@@ -0,0 +1,75 @@
+# Generated by tools/generate_stubs.py, do not edit.
+from typing import ClassVar
+from _typeshed import ReadableBuffer
+from mqttbytes.v4 import Packet
+from os import PathLike
+
+class CapturedPacket:
+    """MQTT packet decoded from a capture."""
+    def __repr__(self) -> str: ...
+    @property
+    def timestamp(self) -> float:
+        """Seconds since the epoch at which the segment completing the packet
+        was captured.
+        """
+    @property
+    def direction(self) -> Direction: ...
+    @property
+    def client(self) -> tuple[str, int]:
+        """`(host, port)` of the client."""
+    @property
+    def server(self) -> tuple[str, int]:
+        """`(host, port)` of the server."""
+    @property
+    def packet(self) -> Packet:
+        """Decoded `v4` packet."""
+
+class Direction:
+    """Direction of a captured packet."""
+    ClientToServer: ClassVar[Direction]
+    ServerToClient: ClassVar[Direction]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class PcapReader:
+    """Iterates over the MQTT packets of a pcap or pcapng file, as
+    `CapturedPacket`s in the order they were completed.
+
+    TCP connections to `port` are reassembled, over Ethernet, Linux cooked,
+    raw IP and loopback captures. Connections whose handshake wasn't captured
+    are decoded from their first segment. A direction of a connection whose
+    bytes can't be decoded, e.g. when the capture started in the middle of a
+    packet or missed a segment, is skipped until the connection restarts and
+    the reason is added to `errors`. A connection is forgotten once both
+    directions were closed with FIN, or one was reset with RST.
+    """
+    def __init__(self, path: str | PathLike[str], port: int = 1883, max_size: int = 268435455) -> None: ...
+    def __iter__(self) -> PcapReader: ...
+    def __next__(self) -> CapturedPacket: ...
+    @property
+    def errors(self) -> list[str]:
+        """Why directions of connections were skipped, in the order it happened."""
+
+class PcapWriter:
+    """Writes the bytes exchanged by a client and a server, e.g. packets encoded
+    with `write()`, to a pcap file as a TCP connection over raw IP.
+
+    `client` and `server` are `(host, port)` tuples of IP addresses of the same
+    family, `("10.0.0.1", 50000)` and `("10.0.0.2", 1883)` by default. The
+    handshake is written along with the first bytes and the connection is
+    closed by `close()`. Wireshark decodes the bytes as MQTT when the server
+    port is 1883, and they can be read back with `PcapReader`.
+    """
+    def __init__(self, path: str | PathLike[str], client: tuple[str, int] = ..., server: tuple[str, int] = ...) -> None: ...
+    def write(self, data: ReadableBuffer, direction: Direction, timestamp: float | None = None) -> None:
+        """Writes `data` sent in `direction`, captured `timestamp` seconds after
+        the epoch, or now. `data` is split in segments of 1460 bytes.
+        """
+    def close(self) -> None:
+        """Closes the connection, if any bytes were written, and the file.
+        Closing again does nothing.
+        """
+    def __enter__(self) -> PcapWriter: ...
+    def __exit__(self, _exc_type: object, _exc_value: object, _traceback: object) -> bool: ...
@@ -0,0 +1,546 @@
+"""MQTT 3.1.1 clients over TCP or Unix sockets, built on `v4.ClientSession`.
+
+`Client` runs the network loop in a background thread and `AsyncClient` in an
+asyncio task. Both send `PingReq` when nothing was sent for `keep_alive`
+seconds, resend unacknowledged QoS1 and QoS2 publishes with `dup` set every
+`retry_interval` seconds, and pass incoming publishes to the callbacks of the
+matching subscriptions, or to `on_message` when no subscription has one.
+Exceptions raised by callbacks are logged to the `mqttbytes.client` logger
+and don't stop the network loop.
+"""
+
+import asyncio
+import functools
+import inspect
+import logging
+import socket
+import threading
+import time
+
+from mqttbytes import QoS, SubscriptionTree, qos as qos_from_int, v4, valid_filter
+
+__all__ = ["AsyncClient", "Client"]
+
+_logger = logging.getLogger(__name__)
+
+
+def _qos(qos):
+    return qos if isinstance(qos, QoS) else qos_from_int(qos)
+
+
+def _check_filter(topic):
+    """Raises ValueError if `topic` is not a filter `SubscriptionTree` accepts."""
+    if not valid_filter(topic) or any("+" in level and level != "+" for level in topic.split("/")):
+        raise ValueError("invalid filter: {!r}".format(topic))
+
+
+class _Waiter:
+    """Result of a request, set by the network thread."""
+
+    def __init__(self):
+        self._event = threading.Event()
+        self._value = None
+        self._error = None
+
+    def set(self, value):
+        self._value = value
+        self._event.set()
+
+    def fail(self, error):
+        self._error = error
+        self._event.set()
+
+    def wait(self, timeout):
+        if not self._event.wait(timeout):
+            raise TimeoutError("no answer from the broker")
+        if self._error is not None:
+            raise self._error
+        return self._value
+
+
+class _AsyncWaiter:
+    """Result of a request, set by the network task."""
+
+    def __init__(self):
+        self._future = asyncio.get_running_loop().create_future()
+
+    def set(self, value):
+        if not self._future.done():
+            self._future.set_result(value)
+
+    def fail(self, error):
+        if not self._future.done():
+            self._future.set_exception(error)
+
+    async def wait(self, timeout):
+        try:
+            return await asyncio.wait_for(asyncio.shield(self._future), timeout)
+        except asyncio.TimeoutError:
+            raise TimeoutError("no answer from the broker") from None
+
+
+class _Base:
+    """Protocol state shared by both clients, without any I/O."""
+
+    def __init__(
+        self,
+        client_id,
+        host,
+        port,
+        path,
+        keep_alive,
+        clean_session,
+        login,
+        last_will,
+        max_size,
+        retry_interval,
+        timeout,
+        on_message,
+    ):
+        connect = v4.Connect(client_id)
+        connect.keep_alive = keep_alive
+        connect.clean_session = clean_session
+        connect.login = login
+        connect.last_will = last_will
+        self.session = v4.ClientSession(connect, max_size)
+        self.host = host
+        self.port = port
+        self.path = path
+        self.keep_alive = keep_alive
+        self.retry_interval = retry_interval
+        self.timeout = timeout
+        self.on_message = on_message
+        self._callbacks = SubscriptionTree()
+        self._waiters = {}
+        self._last_sent = time.monotonic()
+        self._ping_sent = None
+        # Time at which each packet id in flight was last sent.
+        self._inflight = {}
+        self._error = None
+
+    @property
+    def connected(self):
+        return self.session.connected and self._error is None
+
+    def _tick_interval(self):
+        intervals = [self.retry_interval]
+        if self.keep_alive:
+            intervals.append(self.keep_alive / 2)
+        return max(min(intervals) / 2, 0.05)
+
+    def _timers(self, now):
+        """Queues a ping or retries when due. Raises TimeoutError when the
+        broker didn't answer a ping within `keep_alive` seconds."""
+        if not self.session.connected:
+            return
+        if self._ping_sent is not None and now - self._ping_sent >= self.keep_alive:
+            raise TimeoutError("no PingResp from the broker")
+        if self.keep_alive and self._ping_sent is None and now - self._last_sent >= self.keep_alive:
+            self.session.ping()
+            self._ping_sent = now
+        inflight = self.session.inflight
+        self._inflight = {pkid: self._inflight.get(pkid, now) for pkid in inflight}
+        if any(now - sent >= self.retry_interval for sent in self._inflight.values()):
+            self.session.resend()
+            self._inflight = dict.fromkeys(inflight, now)
+
+    def _handle(self, events):
+        """Resolves the waiters of `events` and returns the callbacks to call."""
+        calls = []
+        for event in events:
+            if isinstance(event, v4.ConnAck):
+                self._resolve(("connack",), event)
+            elif isinstance(event, (v4.PubAck, v4.PubComp)):
+                self._resolve(("publish", event.pkid), event)
+            elif isinstance(event, v4.SubAck):
+                self._resolve(("subscribe", event.pkid), event)
+            elif isinstance(event, v4.UnsubAck):
+                self._resolve(("unsubscribe", event.pkid), event)
+            elif isinstance(event, v4.PingResp):
+                self._ping_sent = None
+            elif isinstance(event, v4.Publish):
+                callbacks = [callback for _, callback in self._callbacks.matches(event.topic)]
+                if not callbacks and self.on_message is not None:
+                    callbacks = [self.on_message]
+                calls.extend((callback, event) for callback in callbacks)
+        return calls
+
+    def _resolve(self, key, value):
+        waiter = self._waiters.pop(key, None)
+        if waiter is not None:
+            waiter.set(value)
+
+    def _fail(self, error):
+        self._error = error
+        waiters, self._waiters = self._waiters, {}
+        for waiter in waiters.values():
+            waiter.fail(error)
+
+    def _check_connack(self, connack):
+        if connack.code != v4.ConnectReturnCode.Success:
+            raise ConnectionRefusedError("connection refused: {!r}".format(connack.code))
+
+    def _check_error(self):
+        if self._error is not None:
+            raise ConnectionError("connection to the broker was lost") from self._error
+
+
+class Client(_Base):
+    """Blocking client, with the network loop in a background thread.
+
+    Callbacks run in that thread and may call the client, except for
+    requests waiting for an answer.
+    """
+
+    def __init__(
+        self,
+        client_id="",
+        host="localhost",
+        port=1883,
+        *,
+        path=None,
+        keep_alive=60,
+        clean_session=True,
+        login=None,
+        last_will=None,
+        max_size=1024 * 1024,
+        retry_interval=10.0,
+        timeout=10.0,
+        on_message=None,
+    ):
+        super().__init__(
+            client_id,
+            host,
+            port,
+            path,
+            keep_alive,
+            clean_session,
+            login,
+            last_will,
+            max_size,
+            retry_interval,
+            timeout,
+            on_message,
+        )
+        self._lock = threading.RLock()
+        self._sock = None
+        self._thread = None
+
+    def connect(self):
+        """Connects to the broker and waits for its `ConnAck`."""
+        if self.path is not None:
+            sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+            sock.settimeout(self.timeout)
+            sock.connect(self.path)
+        else:
+            sock = socket.create_connection((self.host, self.port), self.timeout)
+        sock.settimeout(self._tick_interval())
+        self._sock = sock
+        self._error = None
+        with self._lock:
+            self.session.connect()
+            waiter = self._waiters[("connack",)] = _Waiter()
+            self._send()
+        self._thread = threading.Thread(target=self._run, name="mqttbytes-client", daemon=True)
+        self._thread.start()
+        try:
+            self._check_connack(waiter.wait(self.timeout))
+        except BaseException:
+            self._close()
+            raise
+
+    def disconnect(self):
+        """Sends `Disconnect` and closes the connection."""
+        if self._sock is None:
+            return
+        with self._lock:
+            if self.session.connected:
+                self.session.disconnect()
+                self._send()
+        self._close()
+
+    def publish(self, topic, payload=b"", qos=QoS.AtMostOnce, retain=False, wait=True):
+        """Publishes a message and returns its packet id. With `wait`, QoS1
+        and QoS2 publishes return once acknowledged by the broker."""
+        publish = v4.Publish(topic, _qos(qos), payload)
+        publish.retain = retain
+        with self._lock:
+            self._check_error()
+            pkid = self.session.publish(publish)
+            waiter = None
+            if pkid and wait:
+                waiter = self._waiters[("publish", pkid)] = _Waiter()
+            self._send()
+        if waiter is not None:
+            waiter.wait(self.timeout)
+        return pkid
+
+    def subscribe(self, topic, qos=QoS.AtMostOnce, callback=None):
+        """Subscribes to `topic` and returns the QoS granted by the broker,
+        or None if it refused the subscription. Publishes matching `topic`
+        are passed to `callback` when given."""
+        _check_filter(topic)
+        with self._lock:
+            self._check_error()
+            pkid = self.session.subscribe(v4.Subscribe(topic, _qos(qos)))
+            waiter = self._waiters[("subscribe", pkid)] = _Waiter()
+            if callback is not None:
+                self._callbacks.insert(topic, callback)
+            self._send()
+        granted = waiter.wait(self.timeout).return_codes[0]
+        if granted is None and callback is not None:
+            with self._lock:
+                self._callbacks.remove(topic)
+        return granted
+
+    def unsubscribe(self, topic):
+        """Unsubscribes from `topic` and waits for the broker's answer."""
+        with self._lock:
+            self._check_error()
+            pkid = self.session.unsubscribe(v4.Unsubscribe(topic))
+            waiter = self._waiters[("unsubscribe", pkid)] = _Waiter()
+            self._send()
+        waiter.wait(self.timeout)
+        with self._lock:
+            if topic in self._callbacks:
+                self._callbacks.remove(topic)
+
+    def __enter__(self):
+        self.connect()
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.disconnect()
+
+    def _send(self):
+        data = self.session.data_to_send()
+        if data:
+            self._sock.sendall(data)
+            self._last_sent = time.monotonic()
+
+    def _run(self):
+        sock = self._sock
+        try:
+            while True:
+                try:
+                    data = sock.recv(65536)
+                    if not data:
+                        raise ConnectionResetError("connection closed by the broker")
+                except socket.timeout:
+                    data = b""
+                with self._lock:
+                    events = self.session.receive_data(data) if data else []
+                    calls = self._handle(events)
+                    self._timers(time.monotonic())
+                    self._send()
+                for callback, publish in calls:
+                    try:
+                        callback(publish)
+                    except Exception:
+                        _logger.exception("callback %r failed on %r", callback, publish)
+                if not self.session.connected and not self._waiters:
+                    return
+        except Exception as error:
+            with self._lock:
+                self._fail(error)
+
+    def _close(self):
+        sock, self._sock = self._sock, None
+        if sock is None:
+            return
+        try:
+            sock.shutdown(socket.SHUT_RDWR)
+        except OSError:
+            pass
+        sock.close()
+        if self._thread is not None and self._thread is not threading.current_thread():
+            self._thread.join()
+        self._thread = None
+
+
+class AsyncClient(_Base):
+    """asyncio client, with the network loop in a task.
+
+    Callbacks are called from that task. Coroutine callbacks run in tasks of
+    their own, so they may await requests such as `publish(..., wait=True)`
+    while the network loop reads the answer.
+    """
+
+    def __init__(
+        self,
+        client_id="",
+        host="localhost",
+        port=1883,
+        *,
+        path=None,
+        keep_alive=60,
+        clean_session=True,
+        login=None,
+        last_will=None,
+        max_size=1024 * 1024,
+        retry_interval=10.0,
+        timeout=10.0,
+        on_message=None,
+    ):
+        super().__init__(
+            client_id,
+            host,
+            port,
+            path,
+            keep_alive,
+            clean_session,
+            login,
+            last_will,
+            max_size,
+            retry_interval,
+            timeout,
+            on_message,
+        )
+        self._reader = None
+        self._writer = None
+        self._task = None
+        self._callback_tasks = set()
+
+    async def connect(self):
+        """Connects to the broker and waits for its `ConnAck`."""
+        if self.path is not None:
+            connection = asyncio.open_unix_connection(self.path)
+        else:
+            connection = asyncio.open_connection(self.host, self.port)
+        self._reader, self._writer = await asyncio.wait_for(connection, self.timeout)
+        self._error = None
+        self.session.connect()
+        waiter = self._waiters[("connack",)] = _AsyncWaiter()
+        self._send()
+        self._task = asyncio.ensure_future(self._run())
+        try:
+            self._check_connack(await waiter.wait(self.timeout))
+        except BaseException:
+            await self._close()
+            raise
+
+    async def disconnect(self):
+        """Sends `Disconnect` and closes the connection."""
+        if self._writer is None:
+            return
+        if self.session.connected:
+            self.session.disconnect()
+            self._send()
+            await self._writer.drain()
+        await self._close()
+
+    async def publish(self, topic, payload=b"", qos=QoS.AtMostOnce, retain=False, wait=True):
+        """Publishes a message and returns its packet id. With `wait`, QoS1
+        and QoS2 publishes return once acknowledged by the broker."""
+        self._check_error()
+        publish = v4.Publish(topic, _qos(qos), payload)
+        publish.retain = retain
+        pkid = self.session.publish(publish)
+        waiter = None
+        if pkid and wait:
+            waiter = self._waiters[("publish", pkid)] = _AsyncWaiter()
+        self._send()
+        await self._writer.drain()
+        if waiter is not None:
+            await waiter.wait(self.timeout)
+        return pkid
+
+    async def subscribe(self, topic, qos=QoS.AtMostOnce, callback=None):
+        """Subscribes to `topic` and returns the QoS granted by the broker,
+        or None if it refused the subscription. Publishes matching `topic`
+        are passed to `callback` when given."""
+        _check_filter(topic)
+        self._check_error()
+        pkid = self.session.subscribe(v4.Subscribe(topic, _qos(qos)))
+        waiter = self._waiters[("subscribe", pkid)] = _AsyncWaiter()
+        if callback is not None:
+            self._callbacks.insert(topic, callback)
+        self._send()
+        await self._writer.drain()
+        granted = (await waiter.wait(self.timeout)).return_codes[0]
+        if granted is None and callback is not None:
+            self._callbacks.remove(topic)
+        return granted
+
+    async def unsubscribe(self, topic):
+        """Unsubscribes from `topic` and waits for the broker's answer."""
+        self._check_error()
+        pkid = self.session.unsubscribe(v4.Unsubscribe(topic))
+        waiter = self._waiters[("unsubscribe", pkid)] = _AsyncWaiter()
+        self._send()
+        await self._writer.drain()
+        await waiter.wait(self.timeout)
+        if topic in self._callbacks:
+            self._callbacks.remove(topic)
+
+    async def __aenter__(self):
+        await self.connect()
+        return self
+
+    async def __aexit__(self, exc_type, exc_value, traceback):
+        await self.disconnect()
+
+    def _send(self):
+        data = self.session.data_to_send()
+        if data:
+            self._writer.write(data)
+            self._last_sent = time.monotonic()
+
+    async def _run(self):
+        try:
+            while True:
+                try:
+                    data = await asyncio.wait_for(self._reader.read(65536), self._tick_interval())
+                    if not data:
+                        raise ConnectionResetError("connection closed by the broker")
+                except asyncio.TimeoutError:
+                    data = b""
+                events = self.session.receive_data(data) if data else []
+                calls = self._handle(events)
+                self._timers(time.monotonic())
+                self._send()
+                for callback, publish in calls:
+                    self._dispatch(callback, publish)
+                if not self.session.connected and not self._waiters:
+                    return
+        except asyncio.CancelledError:
+            raise
+        except Exception as error:
+            self._fail(error)
+
+    def _dispatch(self, callback, publish):
+        """Calls `callback`, and schedules the coroutine it returns in a task."""
+        try:
+            result = callback(publish)
+        except Exception:
+            _logger.exception("callback %r failed on %r", callback, publish)
+            return
+        if inspect.isawaitable(result):
+            task = asyncio.ensure_future(result)
+            self._callback_tasks.add(task)
+            task.add_done_callback(functools.partial(self._callback_done, callback, publish))
+
+    def _callback_done(self, callback, publish, task):
+        self._callback_tasks.discard(task)
+        if not task.cancelled() and task.exception() is not None:
+            _logger.error("callback %r failed on %r", callback, publish, exc_info=task.exception())
+
+    async def _close(self):
+        writer, self._writer = self._writer, None
+        if writer is None:
+            return
+        # Callbacks still running, except the one closing the client.
+        for task in self._callback_tasks - {asyncio.current_task()}:
+            task.cancel()
+        task, self._task = self._task, None
+        if task is not None and task is not asyncio.current_task():
+            task.cancel()
+            try:
+                await task
+            except asyncio.CancelledError:
+                pass
+        writer.close()
+        try:
+            await writer.wait_closed()
+        except OSError:
+            pass
@@ -0,0 +1,30 @@
+# Generated by tools/generate_stubs.py, do not edit.
+
+class Broker:
+    """MQTT 3.1.1 broker listening on localhost, for integration tests.
+
+    Clients are served by Rust threads, without the GIL. The broker handles
+    QoS 0, 1 and 2 in both directions, retained messages and last wills.
+    Sessions are not persisted: a client reconnecting with the same client id
+    takes over the previous connection and starts without subscriptions.
+    """
+    def __init__(self, port: int = 0, max_size: int = ...) -> None:
+        """Starts listening on 127.0.0.1 at `port`, or on a free port when 0."""
+    def close(self) -> None:
+        """Stops listening and closes every connection."""
+    def __enter__(self) -> Broker: ...
+    def __exit__(self, _exc_type: object, _exc_value: object, _traceback: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    @property
+    def host(self) -> str: ...
+    @property
+    def port(self) -> int: ...
+    @property
+    def address(self) -> tuple[str, int]:
+        """`(host, port)` tuple, as accepted by `socket.create_connection`."""
+    @property
+    def clients(self) -> list[str]:
+        """Ids of the connected clients, sorted."""
+    @property
+    def retained(self) -> list[str]:
+        """Topics that have a retained message."""
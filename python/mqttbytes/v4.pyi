@@ -0,0 +1,748 @@
+# Generated by tools/generate_stubs.py, do not edit.
+"""MQTT 3.1.1 packets, readers and sans-IO sessions.
+
+Packets are encoded and decoded without checking every rule of the
+specification. `validate()` on each packet checks the rules left out and
+raises `ValidationError` for the first one broken. `write(strict=True)`
+validates a packet before encoding it, and `read`, `read_from`, `read_all`
+and `StreamDecoder` with `strict=True` validate packets once decoded. The
+rules are:
+
+- strings and binary fields are at most 65535 bytes long, and strings
+  have no null character;
+- topics are not empty and have no wildcards, and filters are valid topic
+  filters;
+- packets carrying a packet id have a non-zero one, except QoS0 publishes
+  which have none;
+- other rules specific to a packet, documented by its `validate()`.
+"""
+from typing import Any, ClassVar, Iterable, Iterator, Sequence
+from _typeshed import ReadableBuffer, WriteableBuffer
+from mqttbytes import FixedHeader, Protocol, QoS
+from typing_extensions import TypeAlias
+
+Packet: TypeAlias = Connect | ConnAck | Publish | PubAck | PubRec | PubRel | PubComp | Subscribe | SubAck | Unsubscribe | UnsubAck | PingReq | PingResp | Disconnect
+
+class ConnAck:
+    """Acknowledgement to connect packet."""
+    def __init__(self, code: ConnectReturnCode, session_present: bool = False) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> ConnAck: ...
+    def validate(self) -> None:
+        """Checks that `session_present` is only set with `Success`."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> ConnAck: ...
+    def __deepcopy__(self, _memo: Any) -> ConnAck: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]:
+        """Returns the packet as a JSON-safe dict, with the code as its name."""
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> ConnAck: ...
+    @property
+    def session_present(self) -> bool: ...
+    @session_present.setter
+    def session_present(self, session_present: bool) -> None: ...
+    @property
+    def code(self) -> ConnectReturnCode: ...
+    @code.setter
+    def code(self, code: ConnectReturnCode) -> None: ...
+
+class Connect:
+    """Connection packet initiated by the client."""
+    def __init__(self, client_id: str | None = None, keep_alive: int = 10, clean_session: bool = True, last_will: LastWill | None = None, login: Login | None = None, protocol: Protocol = ..., *, id: str | None = None) -> None:
+        """`id` is a deprecated alias of `client_id`."""
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> Connect: ...
+    def validate(self) -> None:
+        """Checks the client id, which can only be empty with `clean_session`, the
+        topic and message of the last will, and the login, whose password needs
+        a username.
+        """
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> Connect: ...
+    def __deepcopy__(self, _memo: Any) -> Connect: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self, encoding: str = "base64") -> dict[str, Any]:
+        """Returns the packet as a JSON-safe dict, with the last will message
+        encoded as `encoding` ('base64' or 'hex').
+        """
+    @staticmethod
+    def from_dict(data: dict[str, Any], encoding: str = "base64") -> Connect:
+        """Builds the packet from a dict returned by `to_dict`. Only `client_id`
+        is required.
+        """
+    @property
+    def protocol(self) -> Protocol: ...
+    @protocol.setter
+    def protocol(self, protocol: Protocol) -> None: ...
+    @property
+    def keep_alive(self) -> int: ...
+    @keep_alive.setter
+    def keep_alive(self, keep_alive: int) -> None: ...
+    @property
+    def client_id(self) -> str: ...
+    @client_id.setter
+    def client_id(self, client_id: str) -> None: ...
+    @property
+    def clean_session(self) -> bool: ...
+    @clean_session.setter
+    def clean_session(self, clean_session: bool) -> None: ...
+    @property
+    def last_will(self) -> LastWill | None: ...
+    @last_will.setter
+    def last_will(self, last_will: LastWill | None) -> None: ...
+    @property
+    def login(self) -> Login | None: ...
+    @login.setter
+    def login(self, login: Login | None) -> None: ...
+
+class Disconnect:
+    def __init__(self) -> None: ...
+    def validate(self) -> None: ...
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> Disconnect: ...
+    def __deepcopy__(self, _memo: Any) -> Disconnect: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> Disconnect: ...
+
+class LastWill:
+    """LastWill that broker forwards on behalf of the client."""
+    def __init__(self, topic: str, message: ReadableBuffer, qos: QoS | int, retain: bool = False) -> None: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self, encoding: str = "base64") -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any], encoding: str = "base64") -> LastWill: ...
+    @property
+    def topic(self) -> str: ...
+    @topic.setter
+    def topic(self, topic: str) -> None: ...
+    @property
+    def message(self) -> memoryview:
+        """Read-only `memoryview` sharing the memory of the message."""
+    @message.setter
+    def message(self, message: ReadableBuffer) -> None:
+        """Accepts any object supporting the buffer protocol."""
+    @property
+    def qos(self) -> QoS: ...
+    @qos.setter
+    def qos(self, qos: QoS | int) -> None: ...
+    @property
+    def retain(self) -> bool: ...
+    @retain.setter
+    def retain(self, retain: bool) -> None: ...
+
+class Login:
+    def __init__(self, username: str, password: str = "") -> None:
+        """The password is only sent when it isn't empty."""
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str:
+        """The password is masked so that reprs can be logged safely."""
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> Login: ...
+    def validate(self, username: str, password: str) -> bool: ...
+    @property
+    def username(self) -> str: ...
+    @username.setter
+    def username(self, username: str) -> None: ...
+    @property
+    def password(self) -> str: ...
+    @password.setter
+    def password(self, password: str) -> None: ...
+
+class PingReq:
+    def __init__(self) -> None: ...
+    def validate(self) -> None: ...
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> PingReq: ...
+    def __deepcopy__(self, _memo: Any) -> PingReq: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> PingReq: ...
+
+class PingResp:
+    def __init__(self) -> None: ...
+    def validate(self) -> None: ...
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> PingResp: ...
+    def __deepcopy__(self, _memo: Any) -> PingResp: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> PingResp: ...
+
+class PubAck:
+    """Acknowledgement to QoS1 publish."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> PubAck: ...
+    def validate(self) -> None:
+        """Checks that the packet id is not 0."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> PubAck: ...
+    def __deepcopy__(self, _memo: Any) -> PubAck: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> PubAck: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+
+class PubComp:
+    """Acknowledgement to QoS1 publish."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> PubComp: ...
+    def validate(self) -> None:
+        """Checks that the packet id is not 0."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> PubComp: ...
+    def __deepcopy__(self, _memo: Any) -> PubComp: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> PubComp: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+
+class PubRec:
+    """Acknowledgement to QoS1 publish."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> PubRec: ...
+    def validate(self) -> None:
+        """Checks that the packet id is not 0."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> PubRec: ...
+    def __deepcopy__(self, _memo: Any) -> PubRec: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> PubRec: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+
+class PubRel:
+    """Acknowledgement to QoS1 publish."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> PubRel: ...
+    def validate(self) -> None:
+        """Checks that the packet id is not 0."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> PubRel: ...
+    def __deepcopy__(self, _memo: Any) -> PubRel: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> PubRel: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+
+class Publish:
+    """Publish packet."""
+    def __init__(self, topic: str, qos: QoS | int, payload: ReadableBuffer, retain: bool = False, dup: bool = False, pkid: int = 0) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> Publish: ...
+    def validate(self) -> None:
+        """Checks the topic, and that the packet id is 0 and `dup` unset for QoS0,
+        and the packet id non-zero for QoS1 and QoS2.
+        """
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> Publish: ...
+    def __deepcopy__(self, _memo: Any) -> Publish: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self, encoding: str = "base64") -> dict[str, Any]:
+        """Returns the packet as a JSON-safe dict, with the payload encoded as
+        `encoding` ('base64' or 'hex') and the QoS as its number.
+        """
+    @staticmethod
+    def from_dict(data: dict[str, Any], encoding: str = "base64") -> Publish:
+        """Builds the packet from a dict returned by `to_dict`. Only `topic`,
+        `qos` and `payload` are required.
+        """
+    @property
+    def dup(self) -> bool: ...
+    @dup.setter
+    def dup(self, dup: bool) -> None: ...
+    @property
+    def qos(self) -> QoS: ...
+    @qos.setter
+    def qos(self, qos: QoS | int) -> None: ...
+    @property
+    def retain(self) -> bool: ...
+    @retain.setter
+    def retain(self, retain: bool) -> None: ...
+    @property
+    def topic(self) -> str: ...
+    @topic.setter
+    def topic(self, topic: str) -> None: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def payload(self) -> memoryview:
+        """Read-only `memoryview` sharing the memory of the payload."""
+    @payload.setter
+    def payload(self, payload: ReadableBuffer) -> None:
+        """Accepts any object supporting the buffer protocol."""
+
+class SubAck:
+    """Acknowledgement to subscribe."""
+    def __init__(self, pkid: int, return_codes: Sequence[QoS | int | None]) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> SubAck: ...
+    def validate(self) -> None:
+        """Checks the packet id, and that there is at least one return code."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> SubAck: ...
+    def __deepcopy__(self, _memo: Any) -> SubAck: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]:
+        """Returns the packet as a JSON-safe dict, with each granted QoS as its
+        number and failures as `None`.
+        """
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> SubAck: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def return_codes(self) -> list[QoS | None]: ...
+    @return_codes.setter
+    def return_codes(self, return_codes: Sequence[QoS | int | None]) -> None: ...
+
+class Subscribe:
+    """Subscription packet.
+
+    Behaves as a list of its filters: it can be iterated, reversed, indexed
+    and tested with `in`, and filters are added with `append` and removed with
+    `remove`. Filters are returned as copies. `len()` is the encoded length
+    like for other packets, `len(subscribe.filters)` is the number of filters.
+    """
+    def __init__(self, path: str | SubscribeFilter | tuple[str, QoS | int] | Iterable[SubscribeFilter | tuple[str, QoS | int] | str] | None = None, qos: QoS | int | None = None, pkid: int = 0) -> None:
+        """`path` is a single filter path subscribed at `qos`, a single `(path,
+        qos)` tuple, or an iterable of `SubscribeFilter`s, `(path, qos)` tuples
+        and paths subscribed at `qos`.
+        """
+    def add(self, path: str, qos: QoS | int) -> None: ...
+    def append(self, filter: SubscribeFilter | tuple[str, QoS | int]) -> None:
+        """Adds a `SubscribeFilter` or a `(path, qos)` tuple."""
+    def remove(self, filter: str | SubscribeFilter | tuple[str, QoS | int]) -> None:
+        """Removes the first filter equal to `filter`, or with path `filter` when
+        given a string. Raises `ValueError` if there is none.
+        """
+    def __contains__(self, filter: object) -> bool:
+        """Tells whether a filter equals `filter`, or has path `filter` when given
+        a string. False for objects that are not filters.
+        """
+    def __getitem__(self, index: int) -> SubscribeFilter: ...
+    def __iter__(self) -> Iterator[SubscribeFilter]: ...
+    def __reversed__(self) -> Iterator[SubscribeFilter]: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> Subscribe: ...
+    def validate(self) -> None:
+        """Checks the packet id, and that there is at least one filter and every
+        filter is valid.
+        """
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> Subscribe: ...
+    def __deepcopy__(self, _memo: Any) -> Subscribe: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> Subscribe: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def filters(self) -> list[SubscribeFilter]: ...
+    @filters.setter
+    def filters(self, filters: Sequence[SubscribeFilter]) -> None: ...
+
+class UnsubAck:
+    """Acknowledgement to unsubscribe."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> UnsubAck: ...
+    def validate(self) -> None:
+        """Checks that the packet id is not 0."""
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> UnsubAck: ...
+    def __deepcopy__(self, _memo: Any) -> UnsubAck: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> UnsubAck: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+
+class Unsubscribe:
+    """Unsubscribe packet.
+
+    Behaves as a list of its topic filters: it can be iterated, reversed,
+    indexed and tested with `in`, and filters are added with `append` and
+    removed with `remove`. `len(unsubscribe.topics)` is the number of filters.
+    """
+    def __init__(self, topic: str | Iterable[str] | None = None, pkid: int = 0) -> None:
+        """`topic` is a single topic filter or an iterable of them."""
+    def append(self, topic: str) -> None: ...
+    def remove(self, topic: str) -> None:
+        """Removes the first occurrence of `topic`. Raises `ValueError` if there
+        is none.
+        """
+    def __contains__(self, topic: object) -> bool:
+        """False for objects that are not strings."""
+    def __getitem__(self, index: int) -> str: ...
+    def __iter__(self) -> Iterator[str]: ...
+    def __reversed__(self) -> Iterator[str]: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes, strict: bool = False) -> Unsubscribe: ...
+    def validate(self) -> None:
+        """Checks the packet id, and that there is at least one filter and every
+        filter is valid.
+        """
+    def write(self, strict: bool = False) -> bytes: ...
+    def write_into(self, buffer: WriteableBuffer, offset: int = 0) -> int: ...
+    def __reduce__(self) -> tuple[Any, tuple[dict[str, Any]]]: ...
+    def __copy__(self) -> Unsubscribe: ...
+    def __deepcopy__(self, _memo: Any) -> Unsubscribe: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> Unsubscribe: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def topics(self) -> list[str]: ...
+    @topics.setter
+    def topics(self, topics: Sequence[str]) -> None: ...
+
+class ConnectReturnCode:
+    """Return code in connack."""
+    Success: ClassVar[ConnectReturnCode]
+    RefusedProtocolVersion: ClassVar[ConnectReturnCode]
+    BadClientId: ClassVar[ConnectReturnCode]
+    ServiceUnavailable: ClassVar[ConnectReturnCode]
+    BadUserNamePassword: ClassVar[ConnectReturnCode]
+    NotAuthorized: ClassVar[ConnectReturnCode]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class RetainForwardRule:
+    OnEverySubscribe: ClassVar[RetainForwardRule]
+    OnNewSubscribe: ClassVar[RetainForwardRule]
+    Never: ClassVar[RetainForwardRule]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class SubscribeFilter:
+    """Subscription filter."""
+    def __init__(self, path: str, qos: QoS | int) -> None: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    def __repr__(self) -> str: ...
+    def to_dict(self) -> dict[str, Any]: ...
+    @staticmethod
+    def from_dict(data: dict[str, Any]) -> SubscribeFilter: ...
+    def __len__(self) -> int: ...
+    @property
+    def path(self) -> str: ...
+    @path.setter
+    def path(self, path: str) -> None: ...
+    @property
+    def qos(self) -> QoS: ...
+    @qos.setter
+    def qos(self, qos: QoS | int) -> None: ...
+
+class StreamDecoder:
+    """Incremental decoder for a stream of MQTT packets.
+
+    Bytes are appended with `feed` and complete packets are pulled out by
+    iterating over the decoder. Bytes of a partially received packet are kept
+    until the next `feed` completes it.
+
+    With `strict` set, packets are also validated as with their `validate()`.
+    An invalid packet is dropped and the error raised, and iterating again
+    goes on with the next packet.
+    """
+    def __init__(self, max_size: int, strict: bool = False) -> None: ...
+    def feed(self, data: ReadableBuffer) -> None:
+        """Appends received bytes to the internal buffer. `data` can be any object
+        supporting the buffer protocol, such as a `bytearray` or a `memoryview`.
+        """
+    def __len__(self) -> int:
+        """Returns the number of buffered bytes not yet decoded."""
+    def __iter__(self) -> StreamDecoder: ...
+    def __next__(self) -> Packet: ...
+    def __repr__(self) -> str: ...
+    @property
+    def max_size(self) -> int: ...
+    @property
+    def strict(self) -> bool: ...
+
+class ClientSession:
+    """Client side of an MQTT 3.1.1 connection, without any I/O.
+
+    Bytes received from the broker are passed to `receive_data`, which returns
+    the events the application cares about: `ConnAck`, incoming `Publish`
+    messages (delivered once, even for QoS2), `PubAck` and `PubComp` when an
+    outgoing publish completes, `SubAck`, `UnsubAck` and `PingResp`.
+    Bytes to write to the broker are collected with `data_to_send`.
+
+    When a packet can't be decoded or breaks the protocol, the events of the
+    packets before it are returned and the error is raised by the next call.
+    """
+    def __init__(self, connect: Connect, max_size: int) -> None: ...
+    def connect(self) -> None:
+        """Queues the `Connect` packet, starting a new connection.
+
+        With `clean_session` unset, unacknowledged publishes are queued again
+        with `dup` set, as are pending `PubRel` packets.
+        """
+    def disconnect(self) -> None:
+        """Queues a `Disconnect` packet and closes the session."""
+    def publish(self, publish: Publish) -> int:
+        """Queues a publish and returns the packet id allocated to it, or 0 for QoS0."""
+    def subscribe(self, subscribe: Subscribe) -> int:
+        """Queues a subscribe and returns the packet id allocated to it."""
+    def unsubscribe(self, unsubscribe: Unsubscribe) -> int:
+        """Queues an unsubscribe and returns the packet id allocated to it."""
+    def ping(self) -> None:
+        """Queues a `PingReq` packet."""
+    def resend(self) -> None:
+        """Queues unacknowledged publishes again with `dup` set, and pending
+        `PubRel` packets, when their acknowledgements are overdue.
+        """
+    def receive_data(self, data: ReadableBuffer) -> list[Packet]:
+        """Feeds bytes received from the broker and returns the resulting events.
+        `data` can be any object supporting the buffer protocol.
+        """
+    def data_to_send(self) -> bytes:
+        """Returns the bytes queued for the broker and empties the queue."""
+    def __repr__(self) -> str: ...
+    @property
+    def connected(self) -> bool:
+        """Whether the broker accepted the connection."""
+    @property
+    def inflight(self) -> list[int]:
+        """Packet ids of outgoing publishes not yet fully acknowledged."""
+
+class ServerSession:
+    """Broker side of an MQTT 3.1.1 connection, without any I/O.
+
+    Bytes received from the client are passed to `receive_data`, which answers
+    the packets the protocol requires (`ConnAck`, `SubAck`, `UnsubAck`,
+    `PingResp` and the QoS acknowledgements) and returns the events the broker
+    acts on: the accepted `Connect`, incoming `Publish` messages (delivered
+    once, even for QoS2), `Subscribe`, `Unsubscribe`, `PubAck` and `PubComp`
+    when a publish sent to the client completes, and `Disconnect`.
+    Bytes to write to the client are collected with `data_to_send`. A publish
+    to a topic that isn't valid closes the session.
+
+    When a packet can't be decoded or breaks the protocol, the events of the
+    packets before it are returned and the error is raised by the next call.
+    """
+    def __init__(self, max_size: int, login: Login | None = None, max_qos: QoS | int = ...) -> None:
+        """When `login` is set, clients must present matching credentials.
+        Subscriptions are granted at most `max_qos`.
+        """
+    def receive_data(self, data: ReadableBuffer) -> list[Packet]:
+        """Feeds bytes received from the client and returns the resulting events.
+        `data` can be any object supporting the buffer protocol.
+        """
+    def publish(self, publish: Publish) -> int:
+        """Queues a publish to the client and returns the packet id allocated to
+        it, or 0 for QoS0.
+        """
+    def data_to_send(self) -> bytes:
+        """Returns the bytes queued for the client and empties the queue."""
+    def __repr__(self) -> str: ...
+    @property
+    def connected(self) -> bool:
+        """Whether a `Connect` was received and accepted."""
+    @property
+    def closed(self) -> bool:
+        """Whether the connection should be closed, after sending any queued bytes."""
+    @property
+    def inflight(self) -> list[int]:
+        """Packet ids of publishes to the client not yet fully acknowledged."""
+
+class RetainedStore:
+    """Last retained publish of every topic, as kept by a broker."""
+    def __init__(self) -> None: ...
+    def store(self, publish: Publish) -> None:
+        """Updates the store from an incoming publish. Publishes without the retain
+        flag are ignored, and a retained publish with an empty payload deletes
+        the message retained for its topic.
+        """
+    def retained(self, filter: SubscribeFilter, rule: RetainForwardRule = ..., new_subscription: bool = True) -> list[Publish]:
+        """Returns the retained publishes to forward for a subscription to `filter`.
+
+        `new_subscription` tells whether the filter was not already subscribed
+        by the client, which `RetainForwardRule.OnNewSubscribe` depends on.
+        Returned publishes keep the retain flag, have no packet id and are
+        downgraded to the QoS granted by `filter`.
+        """
+    def remove(self, topic: str) -> None:
+        """Deletes the message retained for `topic`, if any."""
+    def get(self, topic: str) -> Publish | None:
+        """Returns the message retained for `topic`, if any."""
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __contains__(self, topic: str) -> bool: ...
+    @property
+    def topics(self) -> list[str]:
+        """Topics that have a retained message."""
+
+class PacketIdAllocator:
+    """Hands out packet ids for outgoing packets and tracks the ones in flight.
+
+    Ids are non-zero, increase and wrap after 65535, skipping ids still in
+    flight. An id is released by `acknowledge` with the packet completing its
+    flow: `PubAck` for QoS1 publishes, `PubComp` for QoS2 publishes (`PubRec`
+    keeps the id in flight), `SubAck` and `UnsubAck`.
+    """
+    def __init__(self) -> None: ...
+    def allocate(self) -> int:
+        """Returns the next free packet id and marks it in flight. Raises
+        `ProtocolError` when all 65535 ids are in flight.
+        """
+    def release(self, pkid: int) -> bool:
+        """Releases `pkid` and returns whether it was in flight."""
+    def acknowledge(self, packet: PubAck | PubComp | SubAck | UnsubAck) -> bool:
+        """Releases the id of an acknowledgement and returns whether it was in
+        flight. Accepts `PubAck`, `PubComp`, `SubAck` and `UnsubAck`.
+        """
+    def clear(self) -> None:
+        """Releases every id, e.g. when a clean session starts."""
+    def __contains__(self, pkid: int) -> bool: ...
+    def __len__(self) -> int:
+        """Returns the number of ids in flight."""
+    def __repr__(self) -> str: ...
+    @property
+    def in_flight(self) -> list[int]:
+        """Ids in flight, in increasing order."""
+
+def read(bytes: bytes, max_size: int, strict: bool = False) -> Packet:
+    """Reads a stream of bytes and extracts next MQTT packet out of it.
+
+    With `strict` set, the packet is also validated as with its `validate()`.
+    """
+
+def read_from(buffer: ReadableBuffer, offset: int, max_size: int, strict: bool = False) -> tuple[Packet, int]:
+    """Reads the next MQTT packet from `buffer` starting at `offset`.
+
+    Accepts any object supporting the buffer protocol and only copies the bytes
+    of the decoded packet. Returns the packet and the number of bytes consumed.
+    With `strict` set, the packet is also validated as with its `validate()`.
+    """
+
+def read_all(buffer: ReadableBuffer, max_size: int, strict: bool = False) -> tuple[list[Packet], int]:
+    """Reads every complete MQTT packet from `buffer`.
+
+    Returns the packets and the offset of the first byte not consumed, where
+    the next packet starts once more bytes are received. The complete packets
+    are copied once and then decoded without further copies. An error is only
+    raised when the first packet is invalid; packets before an invalid one are
+    returned, and reading again from the returned offset raises the error.
+    With `strict` set, packets are also validated as with their
+    `validate()`, and an invalid packet is handled like one that can't be
+    decoded.
+    """
+
+def packet_from_dict(data: dict[str, Any], encoding: str = "base64") -> Packet:
+    """Builds a packet from a dict returned by its `to_dict`, dispatching on the
+    `type` key. `encoding` applies to the `Publish` and `Connect` payloads.
+    """
+
+def write_many(packets: Iterable[Packet]) -> bytes:
+    """Encodes packets back to back and returns the bytes. `packets` can be any
+    iterable of v4 packets.
+    """
@@ -0,0 +1,784 @@
+# Generated by tools/generate_stubs.py, do not edit.
+from typing import ClassVar, Sequence
+from _typeshed import ReadableBuffer
+from mqttbytes import FixedHeader, Protocol, QoS
+from typing_extensions import TypeAlias
+
+Packet: TypeAlias = Connect | ConnAck | Publish | PubAck | PubRec | PubRel | PubComp | Subscribe | SubAck | Unsubscribe | UnsubAck | PingReq | PingResp | Disconnect
+
+class ConnAck:
+    """Acknowledgement to connect packet."""
+    def __init__(self, code: ConnectReturnCode, session_present: bool) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> ConnAck: ...
+    def write(self) -> bytes: ...
+    @property
+    def session_present(self) -> bool: ...
+    @session_present.setter
+    def session_present(self, session_present: bool) -> None: ...
+    @property
+    def code(self) -> ConnectReturnCode: ...
+    @code.setter
+    def code(self, code: ConnectReturnCode) -> None: ...
+    @property
+    def properties(self) -> ConnAckProperties | None: ...
+    @properties.setter
+    def properties(self, properties: ConnAckProperties | None) -> None: ...
+
+class Connect:
+    """Connection packet initiated by the client."""
+    def __init__(self, id: str) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> Connect: ...
+    def write(self) -> bytes: ...
+    @property
+    def protocol(self) -> Protocol: ...
+    @protocol.setter
+    def protocol(self, protocol: Protocol) -> None: ...
+    @property
+    def keep_alive(self) -> int: ...
+    @keep_alive.setter
+    def keep_alive(self, keep_alive: int) -> None: ...
+    @property
+    def client_id(self) -> str: ...
+    @client_id.setter
+    def client_id(self, client_id: str) -> None: ...
+    @property
+    def clean_session(self) -> bool: ...
+    @clean_session.setter
+    def clean_session(self, clean_session: bool) -> None: ...
+    @property
+    def last_will(self) -> LastWill | None: ...
+    @last_will.setter
+    def last_will(self, last_will: LastWill | None) -> None: ...
+    @property
+    def login(self) -> Login | None: ...
+    @login.setter
+    def login(self, login: Login | None) -> None: ...
+    @property
+    def properties(self) -> ConnectProperties | None: ...
+    @properties.setter
+    def properties(self, properties: ConnectProperties | None) -> None: ...
+
+class Disconnect:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> Disconnect: ...
+    def write(self) -> bytes: ...
+    @property
+    def reason_code(self) -> DisconnectReasonCode: ...
+    @reason_code.setter
+    def reason_code(self, reason_code: DisconnectReasonCode) -> None: ...
+    @property
+    def properties(self) -> DisconnectProperties | None: ...
+    @properties.setter
+    def properties(self, properties: DisconnectProperties | None) -> None: ...
+
+class LastWill:
+    """LastWill that broker forwards on behalf of the client."""
+    def __init__(self, topic: str, payload: ReadableBuffer, qos: QoS, retain: bool) -> None: ...
+    @property
+    def topic(self) -> str: ...
+    @topic.setter
+    def topic(self, topic: str) -> None: ...
+    @property
+    def message(self) -> memoryview:
+        """Read-only `memoryview` sharing the memory of the message."""
+    @message.setter
+    def message(self, message: ReadableBuffer) -> None:
+        """Accepts any object supporting the buffer protocol."""
+    @property
+    def qos(self) -> QoS: ...
+    @qos.setter
+    def qos(self, qos: QoS) -> None: ...
+    @property
+    def retain(self) -> bool: ...
+    @retain.setter
+    def retain(self, retain: bool) -> None: ...
+    @property
+    def properties(self) -> WillProperties | None: ...
+    @properties.setter
+    def properties(self, properties: WillProperties | None) -> None: ...
+
+class Login:
+    def __init__(self, u: str, p: str) -> None: ...
+    @property
+    def username(self) -> str: ...
+    @username.setter
+    def username(self, username: str) -> None: ...
+    @property
+    def password(self) -> str: ...
+    @password.setter
+    def password(self, password: str) -> None: ...
+
+class PingReq:
+    def __init__(self) -> None: ...
+    def write(self) -> bytes: ...
+
+class PingResp:
+    def __init__(self) -> None: ...
+    def write(self) -> bytes: ...
+
+class PubAck:
+    """Acknowledgement to QoS1 publish."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> PubAck: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def reason(self) -> PubAckReason: ...
+    @reason.setter
+    def reason(self, reason: PubAckReason) -> None: ...
+    @property
+    def properties(self) -> PubAckProperties | None: ...
+    @properties.setter
+    def properties(self, properties: PubAckProperties | None) -> None: ...
+
+class PubComp:
+    """QoS2 publish complete, in response to PUBREL packet."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> PubComp: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def reason(self) -> PubCompReason: ...
+    @reason.setter
+    def reason(self, reason: PubCompReason) -> None: ...
+    @property
+    def properties(self) -> PubCompProperties | None: ...
+    @properties.setter
+    def properties(self, properties: PubCompProperties | None) -> None: ...
+
+class PubRec:
+    """Acknowledgement to QoS2 publish."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> PubRec: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def reason(self) -> PubRecReason: ...
+    @reason.setter
+    def reason(self, reason: PubRecReason) -> None: ...
+    @property
+    def properties(self) -> PubRecProperties | None: ...
+    @properties.setter
+    def properties(self, properties: PubRecProperties | None) -> None: ...
+
+class PubRel:
+    """QoS2 publish release, in response to PUBREC packet."""
+    def __init__(self, pkid: int) -> None: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> PubRel: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def reason(self) -> PubRelReason: ...
+    @reason.setter
+    def reason(self, reason: PubRelReason) -> None: ...
+    @property
+    def properties(self) -> PubRelProperties | None: ...
+    @properties.setter
+    def properties(self, properties: PubRelProperties | None) -> None: ...
+
+class Publish:
+    """Publish packet."""
+    def __init__(self, topic: str, qos: QoS, payload: ReadableBuffer) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> Publish: ...
+    def write(self) -> bytes: ...
+    @property
+    def dup(self) -> bool: ...
+    @dup.setter
+    def dup(self, dup: bool) -> None: ...
+    @property
+    def qos(self) -> QoS: ...
+    @qos.setter
+    def qos(self, qos: QoS) -> None: ...
+    @property
+    def retain(self) -> bool: ...
+    @retain.setter
+    def retain(self, retain: bool) -> None: ...
+    @property
+    def topic(self) -> str: ...
+    @topic.setter
+    def topic(self, topic: str) -> None: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def payload(self) -> memoryview:
+        """Read-only `memoryview` sharing the memory of the payload."""
+    @payload.setter
+    def payload(self, payload: ReadableBuffer) -> None:
+        """Accepts any object supporting the buffer protocol."""
+    @property
+    def properties(self) -> PublishProperties | None: ...
+    @properties.setter
+    def properties(self, properties: PublishProperties | None) -> None: ...
+
+class SubAck:
+    """Acknowledgement to subscribe."""
+    def __init__(self, pkid: int, return_codes: Sequence[SubscribeReasonCode]) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> SubAck: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def return_codes(self) -> list[SubscribeReasonCode]: ...
+    @return_codes.setter
+    def return_codes(self, return_codes: Sequence[SubscribeReasonCode]) -> None: ...
+    @property
+    def properties(self) -> SubAckProperties | None: ...
+    @properties.setter
+    def properties(self, properties: SubAckProperties | None) -> None: ...
+
+class Subscribe:
+    """Subscription packet."""
+    def __init__(self, path: str | Sequence[SubscribeFilter] | None = None, qos: QoS | None = None) -> None: ...
+    def add(self, path: str, qos: QoS) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> Subscribe: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def filters(self) -> list[SubscribeFilter]: ...
+    @filters.setter
+    def filters(self, filters: Sequence[SubscribeFilter]) -> None: ...
+    @property
+    def properties(self) -> SubscribeProperties | None: ...
+    @properties.setter
+    def properties(self, properties: SubscribeProperties | None) -> None: ...
+
+class UnsubAck:
+    """Acknowledgement to unsubscribe."""
+    def __init__(self, pkid: int) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> UnsubAck: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def reasons(self) -> list[UnsubAckReason]: ...
+    @reasons.setter
+    def reasons(self, reasons: Sequence[UnsubAckReason]) -> None: ...
+    @property
+    def properties(self) -> UnsubAckProperties | None: ...
+    @properties.setter
+    def properties(self, properties: UnsubAckProperties | None) -> None: ...
+
+class Unsubscribe:
+    """Unsubscribe packet."""
+    def __init__(self, topic: str) -> None: ...
+    def __len__(self) -> int: ...
+    @staticmethod
+    def read(fixed_header: FixedHeader, bytes: bytes) -> Unsubscribe: ...
+    def write(self) -> bytes: ...
+    @property
+    def pkid(self) -> int: ...
+    @pkid.setter
+    def pkid(self, pkid: int) -> None: ...
+    @property
+    def filters(self) -> list[str]: ...
+    @filters.setter
+    def filters(self, filters: Sequence[str]) -> None: ...
+    @property
+    def properties(self) -> UnsubscribeProperties | None: ...
+    @properties.setter
+    def properties(self, properties: UnsubscribeProperties | None) -> None: ...
+
+class ConnAckProperties:
+    """Properties of the connack packet."""
+    def __init__(self) -> None: ...
+    @property
+    def session_expiry_interval(self) -> int | None: ...
+    @session_expiry_interval.setter
+    def session_expiry_interval(self, session_expiry_interval: int | None) -> None: ...
+    @property
+    def receive_max(self) -> int | None: ...
+    @receive_max.setter
+    def receive_max(self, receive_max: int | None) -> None: ...
+    @property
+    def max_qos(self) -> int | None: ...
+    @max_qos.setter
+    def max_qos(self, max_qos: int | None) -> None: ...
+    @property
+    def retain_available(self) -> int | None: ...
+    @retain_available.setter
+    def retain_available(self, retain_available: int | None) -> None: ...
+    @property
+    def max_packet_size(self) -> int | None: ...
+    @max_packet_size.setter
+    def max_packet_size(self, max_packet_size: int | None) -> None: ...
+    @property
+    def assigned_client_identifier(self) -> str | None: ...
+    @assigned_client_identifier.setter
+    def assigned_client_identifier(self, assigned_client_identifier: str | None) -> None: ...
+    @property
+    def topic_alias_max(self) -> int | None: ...
+    @topic_alias_max.setter
+    def topic_alias_max(self, topic_alias_max: int | None) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+    @property
+    def wildcard_subscription_available(self) -> int | None: ...
+    @wildcard_subscription_available.setter
+    def wildcard_subscription_available(self, wildcard_subscription_available: int | None) -> None: ...
+    @property
+    def subscription_identifiers_available(self) -> int | None: ...
+    @subscription_identifiers_available.setter
+    def subscription_identifiers_available(self, subscription_identifiers_available: int | None) -> None: ...
+    @property
+    def shared_subscription_available(self) -> int | None: ...
+    @shared_subscription_available.setter
+    def shared_subscription_available(self, shared_subscription_available: int | None) -> None: ...
+    @property
+    def server_keep_alive(self) -> int | None: ...
+    @server_keep_alive.setter
+    def server_keep_alive(self, server_keep_alive: int | None) -> None: ...
+    @property
+    def response_information(self) -> str | None: ...
+    @response_information.setter
+    def response_information(self, response_information: str | None) -> None: ...
+    @property
+    def server_reference(self) -> str | None: ...
+    @server_reference.setter
+    def server_reference(self, server_reference: str | None) -> None: ...
+    @property
+    def authentication_method(self) -> str | None: ...
+    @authentication_method.setter
+    def authentication_method(self, authentication_method: str | None) -> None: ...
+    @property
+    def authentication_data(self) -> list[int] | None: ...
+    @authentication_data.setter
+    def authentication_data(self, authentication_data: bytes | None) -> None: ...
+
+class ConnectProperties:
+    """Properties of the connect packet."""
+    def __init__(self) -> None: ...
+    @property
+    def session_expiry_interval(self) -> int | None: ...
+    @session_expiry_interval.setter
+    def session_expiry_interval(self, session_expiry_interval: int | None) -> None: ...
+    @property
+    def receive_maximum(self) -> int | None: ...
+    @receive_maximum.setter
+    def receive_maximum(self, receive_maximum: int | None) -> None: ...
+    @property
+    def max_packet_size(self) -> int | None: ...
+    @max_packet_size.setter
+    def max_packet_size(self, max_packet_size: int | None) -> None: ...
+    @property
+    def topic_alias_max(self) -> int | None: ...
+    @topic_alias_max.setter
+    def topic_alias_max(self, topic_alias_max: int | None) -> None: ...
+    @property
+    def request_response_info(self) -> int | None: ...
+    @request_response_info.setter
+    def request_response_info(self, request_response_info: int | None) -> None: ...
+    @property
+    def request_problem_info(self) -> int | None: ...
+    @request_problem_info.setter
+    def request_problem_info(self, request_problem_info: int | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+    @property
+    def authentication_method(self) -> str | None: ...
+    @authentication_method.setter
+    def authentication_method(self, authentication_method: str | None) -> None: ...
+    @property
+    def authentication_data(self) -> list[int] | None: ...
+    @authentication_data.setter
+    def authentication_data(self, authentication_data: bytes | None) -> None: ...
+
+class DisconnectProperties:
+    """Properties of the disconnect packet."""
+    def __init__(self) -> None: ...
+    @property
+    def session_expiry_interval(self) -> int | None: ...
+    @session_expiry_interval.setter
+    def session_expiry_interval(self, session_expiry_interval: int | None) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+    @property
+    def server_reference(self) -> str | None: ...
+    @server_reference.setter
+    def server_reference(self, server_reference: str | None) -> None: ...
+
+class PubAckProperties:
+    """Properties of the puback packet."""
+    def __init__(self) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class PubCompProperties:
+    """Properties of the pubcomp packet."""
+    def __init__(self) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class PubRecProperties:
+    """Properties of the pubrec packet."""
+    def __init__(self) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class PubRelProperties:
+    """Properties of the pubrel packet."""
+    def __init__(self) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class PublishProperties:
+    """Properties of the publish packet."""
+    def __init__(self) -> None: ...
+    @property
+    def payload_format_indicator(self) -> int | None: ...
+    @payload_format_indicator.setter
+    def payload_format_indicator(self, payload_format_indicator: int | None) -> None: ...
+    @property
+    def message_expiry_interval(self) -> int | None: ...
+    @message_expiry_interval.setter
+    def message_expiry_interval(self, message_expiry_interval: int | None) -> None: ...
+    @property
+    def topic_alias(self) -> int | None: ...
+    @topic_alias.setter
+    def topic_alias(self, topic_alias: int | None) -> None: ...
+    @property
+    def response_topic(self) -> str | None: ...
+    @response_topic.setter
+    def response_topic(self, response_topic: str | None) -> None: ...
+    @property
+    def correlation_data(self) -> list[int] | None: ...
+    @correlation_data.setter
+    def correlation_data(self, correlation_data: bytes | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+    @property
+    def subscription_identifiers(self) -> list[int]: ...
+    @subscription_identifiers.setter
+    def subscription_identifiers(self, subscription_identifiers: Sequence[int]) -> None: ...
+    @property
+    def content_type(self) -> str | None: ...
+    @content_type.setter
+    def content_type(self, content_type: str | None) -> None: ...
+
+class SubAckProperties:
+    """Properties of the suback packet."""
+    def __init__(self) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class SubscribeProperties:
+    """Properties of the subscribe packet."""
+    def __init__(self) -> None: ...
+    @property
+    def id(self) -> int | None: ...
+    @id.setter
+    def id(self, id: int | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class UnsubAckProperties:
+    """Properties of the unsuback packet."""
+    def __init__(self) -> None: ...
+    @property
+    def reason_string(self) -> str | None: ...
+    @reason_string.setter
+    def reason_string(self, reason_string: str | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class UnsubscribeProperties:
+    """Properties of the unsubscribe packet."""
+    def __init__(self) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class WillProperties:
+    """Properties of the LastWill."""
+    def __init__(self) -> None: ...
+    @property
+    def delay_interval(self) -> int | None: ...
+    @delay_interval.setter
+    def delay_interval(self, delay_interval: int | None) -> None: ...
+    @property
+    def payload_format_indicator(self) -> int | None: ...
+    @payload_format_indicator.setter
+    def payload_format_indicator(self, payload_format_indicator: int | None) -> None: ...
+    @property
+    def message_expiry_interval(self) -> int | None: ...
+    @message_expiry_interval.setter
+    def message_expiry_interval(self, message_expiry_interval: int | None) -> None: ...
+    @property
+    def content_type(self) -> str | None: ...
+    @content_type.setter
+    def content_type(self, content_type: str | None) -> None: ...
+    @property
+    def response_topic(self) -> str | None: ...
+    @response_topic.setter
+    def response_topic(self, response_topic: str | None) -> None: ...
+    @property
+    def correlation_data(self) -> list[int] | None: ...
+    @correlation_data.setter
+    def correlation_data(self, correlation_data: bytes | None) -> None: ...
+    @property
+    def user_properties(self) -> list[tuple[str, str]]: ...
+    @user_properties.setter
+    def user_properties(self, user_properties: Sequence[tuple[str, str]]) -> None: ...
+
+class ConnectReturnCode:
+    """Return code in connack."""
+    Success: ClassVar[ConnectReturnCode]
+    UnspecifiedError: ClassVar[ConnectReturnCode]
+    MalformedPacket: ClassVar[ConnectReturnCode]
+    ProtocolError: ClassVar[ConnectReturnCode]
+    ImplementationSpecificError: ClassVar[ConnectReturnCode]
+    UnsupportedProtocolVersion: ClassVar[ConnectReturnCode]
+    ClientIdentifierNotValid: ClassVar[ConnectReturnCode]
+    BadUserNamePassword: ClassVar[ConnectReturnCode]
+    NotAuthorized: ClassVar[ConnectReturnCode]
+    ServerUnavailable: ClassVar[ConnectReturnCode]
+    ServerBusy: ClassVar[ConnectReturnCode]
+    Banned: ClassVar[ConnectReturnCode]
+    BadAuthenticationMethod: ClassVar[ConnectReturnCode]
+    TopicNameInvalid: ClassVar[ConnectReturnCode]
+    PacketTooLarge: ClassVar[ConnectReturnCode]
+    QuotaExceeded: ClassVar[ConnectReturnCode]
+    PayloadFormatInvalid: ClassVar[ConnectReturnCode]
+    RetainNotSupported: ClassVar[ConnectReturnCode]
+    QoSNotSupported: ClassVar[ConnectReturnCode]
+    UseAnotherServer: ClassVar[ConnectReturnCode]
+    ServerMoved: ClassVar[ConnectReturnCode]
+    ConnectionRateExceeded: ClassVar[ConnectReturnCode]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class DisconnectReasonCode:
+    """Reason code in disconnect."""
+    NormalDisconnection: ClassVar[DisconnectReasonCode]
+    DisconnectWithWillMessage: ClassVar[DisconnectReasonCode]
+    UnspecifiedError: ClassVar[DisconnectReasonCode]
+    MalformedPacket: ClassVar[DisconnectReasonCode]
+    ProtocolError: ClassVar[DisconnectReasonCode]
+    ImplementationSpecificError: ClassVar[DisconnectReasonCode]
+    NotAuthorized: ClassVar[DisconnectReasonCode]
+    ServerBusy: ClassVar[DisconnectReasonCode]
+    ServerShuttingDown: ClassVar[DisconnectReasonCode]
+    KeepAliveTimeout: ClassVar[DisconnectReasonCode]
+    SessionTakenOver: ClassVar[DisconnectReasonCode]
+    TopicFilterInvalid: ClassVar[DisconnectReasonCode]
+    TopicNameInvalid: ClassVar[DisconnectReasonCode]
+    ReceiveMaximumExceeded: ClassVar[DisconnectReasonCode]
+    TopicAliasInvalid: ClassVar[DisconnectReasonCode]
+    PacketTooLarge: ClassVar[DisconnectReasonCode]
+    MessageRateTooHigh: ClassVar[DisconnectReasonCode]
+    QuotaExceeded: ClassVar[DisconnectReasonCode]
+    AdministrativeAction: ClassVar[DisconnectReasonCode]
+    PayloadFormatInvalid: ClassVar[DisconnectReasonCode]
+    RetainNotSupported: ClassVar[DisconnectReasonCode]
+    QoSNotSupported: ClassVar[DisconnectReasonCode]
+    UseAnotherServer: ClassVar[DisconnectReasonCode]
+    ServerMoved: ClassVar[DisconnectReasonCode]
+    SharedSubscriptionNotSupported: ClassVar[DisconnectReasonCode]
+    ConnectionRateExceeded: ClassVar[DisconnectReasonCode]
+    MaximumConnectTime: ClassVar[DisconnectReasonCode]
+    SubscriptionIdentifiersNotSupported: ClassVar[DisconnectReasonCode]
+    WildcardSubscriptionsNotSupported: ClassVar[DisconnectReasonCode]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class PubAckReason:
+    """Reason code in puback."""
+    Success: ClassVar[PubAckReason]
+    NoMatchingSubscribers: ClassVar[PubAckReason]
+    UnspecifiedError: ClassVar[PubAckReason]
+    ImplementationSpecificError: ClassVar[PubAckReason]
+    NotAuthorized: ClassVar[PubAckReason]
+    TopicNameInvalid: ClassVar[PubAckReason]
+    PacketIdentifierInUse: ClassVar[PubAckReason]
+    QuotaExceeded: ClassVar[PubAckReason]
+    PayloadFormatInvalid: ClassVar[PubAckReason]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class PubCompReason:
+    """Reason code in pubcomp."""
+    Success: ClassVar[PubCompReason]
+    PacketIdentifierNotFound: ClassVar[PubCompReason]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class PubRecReason:
+    """Reason code in pubrec."""
+    Success: ClassVar[PubRecReason]
+    NoMatchingSubscribers: ClassVar[PubRecReason]
+    UnspecifiedError: ClassVar[PubRecReason]
+    ImplementationSpecificError: ClassVar[PubRecReason]
+    NotAuthorized: ClassVar[PubRecReason]
+    TopicNameInvalid: ClassVar[PubRecReason]
+    PacketIdentifierInUse: ClassVar[PubRecReason]
+    QuotaExceeded: ClassVar[PubRecReason]
+    PayloadFormatInvalid: ClassVar[PubRecReason]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class PubRelReason:
+    """Reason code in pubrel."""
+    Success: ClassVar[PubRelReason]
+    PacketIdentifierNotFound: ClassVar[PubRelReason]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class RetainForwardRule:
+    OnEverySubscribe: ClassVar[RetainForwardRule]
+    OnNewSubscribe: ClassVar[RetainForwardRule]
+    Never: ClassVar[RetainForwardRule]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class SubscribeFilter:
+    """Subscription filter."""
+    def __init__(self, path: str, qos: QoS) -> None: ...
+    def __len__(self) -> int: ...
+    @property
+    def path(self) -> str: ...
+    @path.setter
+    def path(self, path: str) -> None: ...
+    @property
+    def qos(self) -> QoS: ...
+    @qos.setter
+    def qos(self, qos: QoS) -> None: ...
+    @property
+    def nolocal(self) -> bool: ...
+    @nolocal.setter
+    def nolocal(self, nolocal: bool) -> None: ...
+    @property
+    def preserve_retain(self) -> bool: ...
+    @preserve_retain.setter
+    def preserve_retain(self, preserve_retain: bool) -> None: ...
+    @property
+    def retain_forward_rule(self) -> RetainForwardRule: ...
+    @retain_forward_rule.setter
+    def retain_forward_rule(self, retain_forward_rule: RetainForwardRule) -> None: ...
+
+class SubscribeReasonCode:
+    """Reason code for each filter in suback."""
+    QoS0: ClassVar[SubscribeReasonCode]
+    QoS1: ClassVar[SubscribeReasonCode]
+    QoS2: ClassVar[SubscribeReasonCode]
+    Unspecified: ClassVar[SubscribeReasonCode]
+    ImplementationSpecific: ClassVar[SubscribeReasonCode]
+    NotAuthorized: ClassVar[SubscribeReasonCode]
+    TopicFilterInvalid: ClassVar[SubscribeReasonCode]
+    PkidInUse: ClassVar[SubscribeReasonCode]
+    QuotaExceeded: ClassVar[SubscribeReasonCode]
+    SharedSubscriptionsNotSupported: ClassVar[SubscribeReasonCode]
+    SubscriptionIdNotSupported: ClassVar[SubscribeReasonCode]
+    WildcardSubscriptionsNotSupported: ClassVar[SubscribeReasonCode]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+class UnsubAckReason:
+    """Reason code for each filter in unsuback."""
+    Success: ClassVar[UnsubAckReason]
+    NoSubscriptionExisted: ClassVar[UnsubAckReason]
+    UnspecifiedError: ClassVar[UnsubAckReason]
+    ImplementationSpecificError: ClassVar[UnsubAckReason]
+    NotAuthorized: ClassVar[UnsubAckReason]
+    TopicFilterInvalid: ClassVar[UnsubAckReason]
+    PacketIdentifierInUse: ClassVar[UnsubAckReason]
+    def __int__(self) -> int: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+
+def read(bytes: bytes, max_size: int) -> Packet:
+    """Reads a stream of bytes and extracts next MQTT packet out of it."""
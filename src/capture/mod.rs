@@ -0,0 +1,428 @@
+//! MQTT sessions in packet captures.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyFloat;
+
+use crate::buffer_as_slice;
+use crate::repr::repr_str;
+use crate::v4::packet_into_py;
+
+mod pcap;
+mod tcp;
+
+/// Direction of a captured packet.
+#[pyclass(module = "mqttbytes.capture")]
+#[derive(Clone, Copy)]
+pub enum Direction {
+    ClientToServer,
+    ServerToClient,
+}
+
+#[pymethods]
+impl Direction {
+    fn __hash__(&self) -> u64 {
+        *self as u64
+    }
+}
+
+/// MQTT packet decoded from a capture.
+#[pyclass(module = "mqttbytes.capture")]
+pub struct CapturedPacket {
+    timestamp: f64,
+    direction: Direction,
+    client: SocketAddr,
+    server: SocketAddr,
+    packet: ::mqttbytes::v4::Packet,
+}
+
+#[pymethods]
+impl CapturedPacket {
+    fn __repr__(&self, _py: Python) -> PyResult<String> {
+        Ok(format!(
+            "CapturedPacket(timestamp={}, direction=Direction.{}, client={}, server={}, packet={})",
+            PyFloat::new(_py, self.timestamp).repr()?,
+            match self.direction {
+                Direction::ClientToServer => "ClientToServer",
+                Direction::ServerToClient => "ServerToClient",
+            },
+            repr_address(&self.client),
+            repr_address(&self.server),
+            self.get_packet(_py).as_ref(_py).repr()?
+        ))
+    }
+
+    /// Seconds since the epoch at which the segment completing the packet
+    /// was captured.
+    #[getter]
+    fn get_timestamp(&self) -> f64 {
+        self.timestamp
+    }
+
+    #[getter]
+    fn get_direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// `(host, port)` of the client.
+    #[getter]
+    fn get_client(&self) -> (String, u16) {
+        (self.client.ip().to_string(), self.client.port())
+    }
+
+    /// `(host, port)` of the server.
+    #[getter]
+    fn get_server(&self) -> (String, u16) {
+        (self.server.ip().to_string(), self.server.port())
+    }
+
+    /// Decoded `v4` packet.
+    #[getter]
+    fn get_packet(&self, _py: Python) -> PyObject {
+        packet_into_py(_py, self.packet.clone())
+    }
+}
+
+fn repr_address(address: &SocketAddr) -> String {
+    format!(
+        "({}, {})",
+        repr_str(&address.ip().to_string()),
+        address.port()
+    )
+}
+
+/// Tells whether the first byte of a fixed header has the flags MQTT 3.1.1
+/// requires for its packet type. Bytes from the middle of a packet rarely do.
+fn valid_flags(byte: u8) -> bool {
+    match (byte >> 4, byte & 0x0f) {
+        // QoS 3 is invalid.
+        (3, flags) => flags & 0b0110 != 0b0110,
+        // PubRel, Subscribe and Unsubscribe.
+        (6 | 8 | 10, flags) => flags == 0b0010,
+        (1..=14, flags) => flags == 0,
+        _ => false,
+    }
+}
+
+/// One direction of a captured connection.
+#[derive(Default)]
+struct Flow {
+    stream: tcp::Stream,
+    /// Set once bytes couldn't be decoded, until the connection restarts.
+    broken: bool,
+    /// Set once the segment with the FIN flag was received.
+    finished: bool,
+}
+
+/// Iterates over the MQTT packets of a pcap or pcapng file, as
+/// `CapturedPacket`s in the order they were completed.
+///
+/// TCP connections to `port` are reassembled, over Ethernet, Linux cooked,
+/// raw IP and loopback captures. Connections whose handshake wasn't captured
+/// are decoded from their first segment. A direction of a connection whose
+/// bytes can't be decoded, e.g. when the capture started in the middle of a
+/// packet or missed a segment, is skipped until the connection restarts and
+/// the reason is added to `errors`. A connection is forgotten once both
+/// directions were closed with FIN, or one was reset with RST.
+#[pyclass(module = "mqttbytes.capture")]
+pub struct PcapReader {
+    records: pcap::Records<BufReader<File>>,
+    port: u16,
+    max_size: usize,
+    flows: HashMap<(SocketAddr, SocketAddr), [Flow; 2]>,
+    packets: VecDeque<CapturedPacket>,
+    errors: Vec<String>,
+}
+
+#[pymethods]
+impl PcapReader {
+    #[new]
+    #[args(port = "1883", max_size = "268435455")]
+    fn new(path: PathBuf, port: u16, max_size: usize) -> PyResult<Self> {
+        Ok(Self {
+            records: pcap::Records::new(BufReader::new(File::open(path)?))?,
+            port,
+            max_size,
+            flows: HashMap::new(),
+            packets: VecDeque::new(),
+            errors: Vec::new(),
+        })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> PyResult<Option<CapturedPacket>> {
+        while self.packets.is_empty() {
+            let Some(record) = self.records.next()? else {
+                return Ok(None);
+            };
+            if let Some(segment) = tcp::segment(record.link_type, &record.data) {
+                self.push(record.timestamp, &segment);
+            }
+        }
+        Ok(self.packets.pop_front())
+    }
+
+    /// Why directions of connections were skipped, in the order it happened.
+    #[getter]
+    fn get_errors(&self) -> Vec<String> {
+        self.errors.clone()
+    }
+}
+
+impl PcapReader {
+    /// Adds a segment to its connection and decodes the packets it completes.
+    fn push(&mut self, timestamp: f64, segment: &tcp::Segment) {
+        let (direction, client, server) = if segment.destination.port() == self.port {
+            (
+                Direction::ClientToServer,
+                segment.source,
+                segment.destination,
+            )
+        } else if segment.source.port() == self.port {
+            (
+                Direction::ServerToClient,
+                segment.destination,
+                segment.source,
+            )
+        } else {
+            return;
+        };
+        if segment.rst {
+            self.flows.remove(&(client, server));
+            return;
+        }
+        // Such as the last ACK of a connection whose flows were removed.
+        if segment.payload.is_empty()
+            && !segment.syn
+            && !segment.fin
+            && !self.flows.contains_key(&(client, server))
+        {
+            return;
+        }
+        let flows = self.flows.entry((client, server)).or_default();
+        let flow = &mut flows[direction as usize];
+        if segment.syn {
+            *flow = Flow::default();
+        }
+        // Broken flows are only followed to tell when the connection ends.
+        if !flow.broken {
+            let error = if segment.truncated && !segment.payload.is_empty() {
+                Some("segment cut short by the snapshot length of the capture".to_owned())
+            } else if !flow.stream.push(segment) {
+                Some("bytes missing from the capture".to_owned())
+            } else {
+                loop {
+                    match flow.stream.buffer.first() {
+                        Some(&byte) if !valid_flags(byte) => {
+                            break Some(format!("invalid fixed header byte {byte:#04x}"))
+                        }
+                        _ => {}
+                    }
+                    match ::mqttbytes::v4::read(&mut flow.stream.buffer, self.max_size) {
+                        Ok(packet) => self.packets.push_back(CapturedPacket {
+                            timestamp,
+                            direction,
+                            client,
+                            server,
+                            packet,
+                        }),
+                        Err(::mqttbytes::Error::InsufficientBytes(_)) => break None,
+                        Err(error) => break Some(error.to_string()),
+                    }
+                }
+            };
+            if let Some(error) = error {
+                flow.broken = true;
+                flow.stream = tcp::Stream::default();
+                self.errors.push(format!(
+                    "{} -> {} at {timestamp}: {error}",
+                    segment.source, segment.destination
+                ));
+            }
+        }
+        flow.finished |= segment.fin;
+        // Both directions ended, so no more bytes are coming.
+        if flows
+            .iter()
+            .all(|flow| flow.finished && !flow.stream.waiting())
+        {
+            self.flows.remove(&(client, server));
+        }
+    }
+}
+
+/// Sequence numbers of the handshake of the client and of the server.
+const INITIAL_SEQ: [u32; 2] = [0x0001_0000, 0x0002_0000];
+
+/// Writes the bytes exchanged by a client and a server, e.g. packets encoded
+/// with `write()`, to a pcap file as a TCP connection over raw IP.
+///
+/// `client` and `server` are `(host, port)` tuples of IP addresses of the same
+/// family, `("10.0.0.1", 50000)` and `("10.0.0.2", 1883)` by default. The
+/// handshake is written along with the first bytes and the connection is
+/// closed by `close()`. Wireshark decodes the bytes as MQTT when the server
+/// port is 1883, and they can be read back with `PcapReader`.
+#[pyclass(module = "mqttbytes.capture")]
+pub struct PcapWriter {
+    file: Option<BufWriter<File>>,
+    client: SocketAddr,
+    server: SocketAddr,
+    /// Sequence number of the next byte sent by the client and by the server,
+    /// `None` until the handshake is written.
+    seq: Option<[u32; 2]>,
+    /// Timestamp of the last bytes written.
+    timestamp: f64,
+}
+
+#[pymethods]
+impl PcapWriter {
+    #[new]
+    #[args(
+        client = "(\"10.0.0.1\".to_owned(), 50000)",
+        server = "(\"10.0.0.2\".to_owned(), 1883)"
+    )]
+    fn new(path: PathBuf, client: (String, u16), server: (String, u16)) -> PyResult<Self> {
+        let client = socket_address(client)?;
+        let server = socket_address(server)?;
+        if client.is_ipv4() != server.is_ipv4() {
+            return Err(PyValueError::new_err(
+                "client and server addresses are of different families",
+            ));
+        }
+        let mut file = BufWriter::new(File::create(path)?);
+        pcap::write_header(&mut file, tcp::LINKTYPE_RAW)?;
+        Ok(Self {
+            file: Some(file),
+            client,
+            server,
+            seq: None,
+            timestamp: 0.0,
+        })
+    }
+
+    /// Writes `data` sent in `direction`, captured `timestamp` seconds after
+    /// the epoch, or now. `data` is split in segments of 1460 bytes.
+    #[args(timestamp = "None")]
+    fn write(
+        &mut self,
+        _py: Python,
+        data: PyBuffer<u8>,
+        direction: Direction,
+        timestamp: Option<f64>,
+    ) -> PyResult<()> {
+        let timestamp = match timestamp {
+            Some(timestamp) => timestamp,
+            None => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0.0, |now| now.as_secs_f64()),
+        };
+        // Seconds are written as 32 bit unsigned integers.
+        if !(0.0..4_294_967_296.0).contains(&timestamp) {
+            return Err(PyValueError::new_err(format!(
+                "timestamp {timestamp} is out of the range of pcap files"
+            )));
+        }
+        self.timestamp = timestamp;
+        if self.seq.is_none() {
+            self.send(Direction::ClientToServer, tcp::SYN, &[])?;
+            self.send(Direction::ServerToClient, tcp::SYN | tcp::ACK, &[])?;
+            self.send(Direction::ClientToServer, tcp::ACK, &[])?;
+        }
+        let data = buffer_as_slice(_py, &data)?;
+        let chunks = data.chunks(tcp::MSS);
+        let last = chunks.len().saturating_sub(1);
+        for (i, chunk) in chunks.enumerate() {
+            let flags = if i == last {
+                tcp::PSH | tcp::ACK
+            } else {
+                tcp::ACK
+            };
+            self.send(direction, flags, chunk)?;
+        }
+        Ok(())
+    }
+
+    /// Closes the connection, if any bytes were written, and the file.
+    /// Closing again does nothing.
+    fn close(&mut self) -> PyResult<()> {
+        if self.file.is_none() {
+            return Ok(());
+        }
+        if self.seq.is_some() {
+            self.send(Direction::ClientToServer, tcp::FIN | tcp::ACK, &[])?;
+            self.send(Direction::ServerToClient, tcp::FIN | tcp::ACK, &[])?;
+            self.send(Direction::ClientToServer, tcp::ACK, &[])?;
+        }
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+        Ok(())
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __exit__(
+        &mut self,
+        _exc_type: &PyAny,
+        _exc_value: &PyAny,
+        _traceback: &PyAny,
+    ) -> PyResult<bool> {
+        self.close()?;
+        Ok(false)
+    }
+}
+
+impl PcapWriter {
+    /// Writes a segment sent in `direction`, acknowledging every byte received
+    /// when `flags` has ACK.
+    fn send(&mut self, direction: Direction, flags: u8, payload: &[u8]) -> PyResult<()> {
+        let file = self
+            .file
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("PcapWriter is closed"))?;
+        let seq = self.seq.get_or_insert(INITIAL_SEQ);
+        let (sent, received, source, destination) = match direction {
+            Direction::ClientToServer => (0, 1, self.client, self.server),
+            Direction::ServerToClient => (1, 0, self.server, self.client),
+        };
+        let ack = if flags & tcp::ACK != 0 {
+            seq[received]
+        } else {
+            0
+        };
+        let packet = tcp::packet(source, destination, seq[sent], ack, flags, payload);
+        pcap::write_record(file, self.timestamp, &packet)?;
+        // SYN and FIN flags take a sequence number.
+        let len = payload.len() as u32 + (flags & (tcp::SYN | tcp::FIN) != 0) as u32;
+        seq[sent] = seq[sent].wrapping_add(len);
+        Ok(())
+    }
+}
+
+fn socket_address((host, port): (String, u16)) -> PyResult<SocketAddr> {
+    let ip: IpAddr = host
+        .parse()
+        .map_err(|_| PyValueError::new_err(format!("invalid IP address {}", repr_str(&host))))?;
+    Ok(SocketAddr::new(ip, port))
+}
+
+pub fn capture(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<CapturedPacket>()?;
+    m.add_class::<Direction>()?;
+    m.add_class::<PcapReader>()?;
+    m.add_class::<PcapWriter>()?;
+    Ok(())
+}
@@ -0,0 +1,339 @@
+//! Records of pcap and pcapng files.
+//!
+//! https://www.tcpdump.org/manpages/pcap-savefile.5.html
+//! https://www.ietf.org/archive/id/draft-ietf-opsawg-pcapng-01.html
+
+use std::io::{self, Read, Write};
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+const PCAP_MICROS: u32 = 0xa1b2_c3d4;
+const PCAP_NANOS: u32 = 0xa1b2_3c4d;
+const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const PCAPNG_BYTE_ORDER: u32 = 0x1a2b_3c4d;
+const PCAPNG_INTERFACE: u32 = 1;
+const PCAPNG_PACKET: u32 = 2;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+const IF_TSRESOL: u16 = 9;
+const IF_TSOFFSET: u16 = 14;
+
+/// Snapshot length of the files written, above the frames written.
+const SNAPSHOT_LEN: u32 = 65535;
+
+/// Largest record accepted, far above any snapshot length in use.
+const MAX_RECORD_LEN: usize = 256 * 1024 * 1024;
+
+/// Frame captured on a link.
+pub(crate) struct Record {
+    /// Seconds since the epoch.
+    pub(crate) timestamp: f64,
+    /// `LINKTYPE_*` value telling how to parse `data`.
+    pub(crate) link_type: u32,
+    pub(crate) data: Vec<u8>,
+}
+
+/// Capture interface of a pcapng section.
+struct Interface {
+    link_type: u32,
+    units_per_second: f64,
+    offset: f64,
+}
+
+/// Reads the records of a pcap or pcapng file, stopping at the end of the
+/// file or at a record cut short by it.
+pub(crate) struct Records<R> {
+    reader: R,
+    big_endian: bool,
+    /// Timestamp units per second and link type of a pcap file, `None` for a
+    /// pcapng file.
+    pcap: Option<(f64, u32)>,
+    /// Interfaces described in the current pcapng section.
+    interfaces: Vec<Interface>,
+}
+
+impl<R: Read> Records<R> {
+    pub(crate) fn new(mut reader: R) -> PyResult<Self> {
+        let mut magic = [0; 4];
+        if !read_exact(&mut reader, &mut magic)? {
+            return Err(invalid("empty file"));
+        }
+        let mut records = Self {
+            reader,
+            big_endian: false,
+            pcap: None,
+            interfaces: Vec::new(),
+        };
+        if u32::from_le_bytes(magic) == PCAPNG_SECTION_HEADER {
+            records.section_header()?;
+            return Ok(records);
+        }
+        let units_per_second;
+        (records.big_endian, units_per_second) =
+            match (u32::from_le_bytes(magic), u32::from_be_bytes(magic)) {
+                (PCAP_MICROS, _) => (false, 1e6),
+                (_, PCAP_MICROS) => (true, 1e6),
+                (PCAP_NANOS, _) => (false, 1e9),
+                (_, PCAP_NANOS) => (true, 1e9),
+                _ => return Err(invalid("not a pcap or pcapng file")),
+            };
+        let mut header = [0; 20];
+        if !read_exact(&mut records.reader, &mut header)? {
+            return Err(invalid("truncated pcap header"));
+        }
+        // The upper bits hold the FCS length, which doesn't change the frame.
+        let link_type = records.u32(&header[16..]) & 0xffff;
+        records.pcap = Some((units_per_second, link_type));
+        Ok(records)
+    }
+
+    /// Returns the next frame, or `None` at the end of the file.
+    pub(crate) fn next(&mut self) -> PyResult<Option<Record>> {
+        match self.pcap {
+            Some((units_per_second, link_type)) => self.pcap_record(units_per_second, link_type),
+            None => self.pcapng_record(),
+        }
+    }
+
+    fn pcap_record(&mut self, units_per_second: f64, link_type: u32) -> PyResult<Option<Record>> {
+        let mut header = [0; 16];
+        if !read_exact(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+        let seconds = self.u32(&header[0..]) as f64;
+        let units = self.u32(&header[4..]) as f64;
+        let len = self.u32(&header[8..]) as usize;
+        if len > MAX_RECORD_LEN {
+            return Err(invalid(format!("pcap record of {len} bytes")));
+        }
+        let mut data = vec![0; len];
+        if !read_exact(&mut self.reader, &mut data)? {
+            return Ok(None);
+        }
+        Ok(Some(Record {
+            timestamp: seconds + units / units_per_second,
+            link_type,
+            data,
+        }))
+    }
+
+    fn pcapng_record(&mut self) -> PyResult<Option<Record>> {
+        loop {
+            let mut header = [0; 8];
+            if !read_exact(&mut self.reader, &mut header)? {
+                return Ok(None);
+            }
+            let block_type = self.u32(&header[0..]);
+            if block_type == PCAPNG_SECTION_HEADER {
+                self.section_header_len(&header[4..])?;
+                continue;
+            }
+            let len = self.u32(&header[4..]) as usize;
+            if !(12..=MAX_RECORD_LEN).contains(&len) || !len.is_multiple_of(4) {
+                return Err(invalid(format!("pcapng block of {len} bytes")));
+            }
+            // The body is followed by the block length again.
+            let mut body = vec![0; len - 8];
+            if !read_exact(&mut self.reader, &mut body)? {
+                return Ok(None);
+            }
+            body.truncate(len - 12);
+            let record = match block_type {
+                PCAPNG_INTERFACE => {
+                    self.interface(&body)?;
+                    continue;
+                }
+                PCAPNG_ENHANCED_PACKET => {
+                    let id = self.u32(&body) as usize;
+                    self.packet(&body, id)?
+                }
+                PCAPNG_PACKET => {
+                    let id = self.u16(&body) as usize;
+                    self.packet(&body, id)?
+                }
+                PCAPNG_SIMPLE_PACKET => self.simple_packet(&body)?,
+                _ => continue,
+            };
+            return Ok(Some(record));
+        }
+    }
+
+    /// Reads a section header block after its type, which starts a section
+    /// with its own byte order and interfaces.
+    fn section_header(&mut self) -> PyResult<()> {
+        let mut len = [0; 4];
+        if !read_exact(&mut self.reader, &mut len)? {
+            return Err(invalid("truncated pcapng section header"));
+        }
+        self.section_header_len(&len)
+    }
+
+    /// Reads a section header block after its length, whose byte order is
+    /// told by the magic number that follows it.
+    fn section_header_len(&mut self, len: &[u8]) -> PyResult<()> {
+        let mut magic = [0; 4];
+        if !read_exact(&mut self.reader, &mut magic)? {
+            return Err(invalid("truncated pcapng section header"));
+        }
+        self.big_endian = match u32::from_le_bytes(magic) {
+            PCAPNG_BYTE_ORDER => false,
+            magic if magic.swap_bytes() == PCAPNG_BYTE_ORDER => true,
+            _ => return Err(invalid("invalid pcapng byte order magic")),
+        };
+        let len = self.u32(len) as usize;
+        if !(28..=MAX_RECORD_LEN).contains(&len) || !len.is_multiple_of(4) {
+            return Err(invalid(format!("pcapng section header of {len} bytes")));
+        }
+        let mut rest = vec![0; len - 12];
+        if !read_exact(&mut self.reader, &mut rest)? {
+            return Err(invalid("truncated pcapng section header"));
+        }
+        self.interfaces.clear();
+        Ok(())
+    }
+
+    fn interface(&mut self, body: &[u8]) -> PyResult<()> {
+        if body.len() < 8 {
+            return Err(invalid("truncated pcapng interface description"));
+        }
+        let mut interface = Interface {
+            link_type: self.u16(body) as u32,
+            units_per_second: 1e6,
+            offset: 0.0,
+        };
+        let mut options = &body[8..];
+        while options.len() >= 4 {
+            let code = self.u16(&options[0..]);
+            let len = self.u16(&options[2..]) as usize;
+            let Some(value) = options.get(4..4 + len) else {
+                break;
+            };
+            match (code, value) {
+                (IF_TSRESOL, [resolution]) if resolution & 0x80 == 0 => {
+                    interface.units_per_second = 10f64.powi((resolution & 0x7f) as i32);
+                }
+                (IF_TSRESOL, [resolution]) => {
+                    interface.units_per_second = 2f64.powi((resolution & 0x7f) as i32);
+                }
+                (IF_TSOFFSET, value) if value.len() == 8 => {
+                    interface.offset = self.u64(value) as i64 as f64;
+                }
+                _ => {}
+            }
+            // Values are padded to 32 bits.
+            options = options
+                .get((4 + len).next_multiple_of(4)..)
+                .unwrap_or_default();
+        }
+        self.interfaces.push(interface);
+        Ok(())
+    }
+
+    /// Reads an enhanced or obsolete packet block, which both have the
+    /// timestamp and lengths at the same offsets.
+    fn packet(&self, body: &[u8], id: usize) -> PyResult<Record> {
+        let Some(header) = body.get(..20) else {
+            return Err(invalid("truncated pcapng packet block"));
+        };
+        let high = self.u32(&header[4..]) as u64;
+        let low = self.u32(&header[8..]) as u64;
+        let len = self.u32(&header[12..]) as usize;
+        let Some(data) = body.get(20..20 + len) else {
+            return Err(invalid("truncated pcapng packet block"));
+        };
+        let interface = self.interface_at(id)?;
+        Ok(Record {
+            timestamp: interface.offset + ((high << 32) | low) as f64 / interface.units_per_second,
+            link_type: interface.link_type,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Reads a simple packet block, which has no timestamp.
+    fn simple_packet(&self, body: &[u8]) -> PyResult<Record> {
+        if body.len() < 4 {
+            return Err(invalid("truncated pcapng simple packet block"));
+        }
+        let len = (self.u32(body) as usize).min(body.len() - 4);
+        Ok(Record {
+            timestamp: 0.0,
+            link_type: self.interface_at(0)?.link_type,
+            data: body[4..4 + len].to_vec(),
+        })
+    }
+
+    fn interface_at(&self, id: usize) -> PyResult<&Interface> {
+        self.interfaces
+            .get(id)
+            .ok_or_else(|| invalid(format!("pcapng packet on undescribed interface {id}")))
+    }
+
+    fn u16(&self, bytes: &[u8]) -> u16 {
+        let bytes = [bytes[0], bytes[1]];
+        if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        }
+    }
+
+    fn u32(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        }
+    }
+
+    fn u64(&self, bytes: &[u8]) -> u64 {
+        let first = self.u32(&bytes[0..]) as u64;
+        let second = self.u32(&bytes[4..]) as u64;
+        if self.big_endian {
+            (first << 32) | second
+        } else {
+            (second << 32) | first
+        }
+    }
+}
+
+/// Writes the header of a pcap file with microsecond timestamps, in little
+/// endian like the records that follow.
+pub(crate) fn write_header(writer: &mut impl Write, link_type: u32) -> io::Result<()> {
+    let mut header = Vec::with_capacity(24);
+    header.extend_from_slice(&PCAP_MICROS.to_le_bytes());
+    // Version 2.4.
+    header.extend_from_slice(&[2, 0, 4, 0]);
+    // Time zone offset and timestamp accuracy, always zero.
+    header.extend_from_slice(&[0; 8]);
+    header.extend_from_slice(&SNAPSHOT_LEN.to_le_bytes());
+    header.extend_from_slice(&link_type.to_le_bytes());
+    writer.write_all(&header)
+}
+
+/// Writes a pcap record of a whole frame, captured `timestamp` seconds after
+/// the epoch.
+pub(crate) fn write_record(writer: &mut impl Write, timestamp: f64, data: &[u8]) -> io::Result<()> {
+    let micros = (timestamp * 1e6).round() as u64;
+    let mut header = Vec::with_capacity(16);
+    header.extend_from_slice(&((micros / 1_000_000) as u32).to_le_bytes());
+    header.extend_from_slice(&((micros % 1_000_000) as u32).to_le_bytes());
+    header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    writer.write_all(&header)?;
+    writer.write_all(data)
+}
+
+fn invalid(message: impl Into<String>) -> PyErr {
+    PyValueError::new_err(message.into())
+}
+
+/// Fills `buffer` and returns true, or returns false if the file ends first.
+fn read_exact(reader: &mut impl Read, buffer: &mut [u8]) -> io::Result<bool> {
+    match reader.read_exact(buffer) {
+        Ok(()) => Ok(true),
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(error) => Err(error),
+    }
+}
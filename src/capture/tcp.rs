@@ -0,0 +1,298 @@
+//! TCP segments in captured frames, and their reassembly into streams.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use bytes::BytesMut;
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+pub(crate) const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LOOP: u32 = 108;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: [u16; 3] = [0x8100, 0x88a8, 0x9100];
+
+const PROTOCOL_TCP: u8 = 6;
+
+pub(crate) const FIN: u8 = 0x01;
+pub(crate) const SYN: u8 = 0x02;
+const RST: u8 = 0x04;
+pub(crate) const PSH: u8 = 0x08;
+pub(crate) const ACK: u8 = 0x10;
+
+/// Payload of the segments written, that of a TCP segment on Ethernet.
+pub(crate) const MSS: usize = 1460;
+
+/// Segments kept while waiting for a missing one, after which the stream is
+/// considered to have a gap.
+const MAX_AHEAD: usize = 1024;
+
+/// TCP segment of a captured frame.
+pub(crate) struct Segment<'a> {
+    pub(crate) source: SocketAddr,
+    pub(crate) destination: SocketAddr,
+    pub(crate) seq: u32,
+    pub(crate) syn: bool,
+    pub(crate) fin: bool,
+    pub(crate) rst: bool,
+    pub(crate) payload: &'a [u8],
+    /// Whether the capture kept only the start of the segment.
+    pub(crate) truncated: bool,
+}
+
+/// Returns the TCP segment carried by a frame, if any. Fragmented IP packets
+/// are ignored.
+pub(crate) fn segment(link_type: u32, frame: &[u8]) -> Option<Segment<'_>> {
+    let (ethertype, packet) = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut ethertype = u16::from_be_bytes([*frame.get(12)?, *frame.get(13)?]);
+            let mut packet = frame.get(14..)?;
+            while ETHERTYPE_VLAN.contains(&ethertype) {
+                ethertype = u16::from_be_bytes([*packet.get(2)?, *packet.get(3)?]);
+                packet = packet.get(4..)?;
+            }
+            (Some(ethertype), packet)
+        }
+        LINKTYPE_LINUX_SLL => (
+            Some(u16::from_be_bytes([*frame.get(14)?, *frame.get(15)?])),
+            frame.get(16..)?,
+        ),
+        LINKTYPE_LINUX_SLL2 => (
+            Some(u16::from_be_bytes([*frame.first()?, *frame.get(1)?])),
+            frame.get(20..)?,
+        ),
+        // The address family is in host byte order, the IP version tells it.
+        LINKTYPE_NULL | LINKTYPE_LOOP => (None, frame.get(4..)?),
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => (None, frame),
+        _ => return None,
+    };
+    match (ethertype, packet.first()? >> 4) {
+        (Some(ETHERTYPE_IPV4), 4) | (None, 4) => ipv4(packet),
+        (Some(ETHERTYPE_IPV6), 6) | (None, 6) => ipv6(packet),
+        _ => None,
+    }
+}
+
+fn ipv4(packet: &[u8]) -> Option<Segment<'_>> {
+    let header_len = ((packet.first()? & 0x0f) as usize) * 4;
+    let total_len = u16::from_be_bytes([*packet.get(2)?, *packet.get(3)?]) as usize;
+    let fragment = u16::from_be_bytes([*packet.get(6)?, *packet.get(7)?]);
+    // More fragments flag or fragment offset.
+    if fragment & 0x3fff != 0 || *packet.get(9)? != PROTOCOL_TCP || header_len < 20 {
+        return None;
+    }
+    let source: [u8; 4] = packet.get(12..16)?.try_into().ok()?;
+    let destination: [u8; 4] = packet.get(16..20)?.try_into().ok()?;
+    // A zero length is written when the segment was offloaded to the NIC.
+    let total_len = if total_len == 0 {
+        packet.len()
+    } else {
+        total_len
+    };
+    tcp(
+        Ipv4Addr::from(source).into(),
+        Ipv4Addr::from(destination).into(),
+        packet.get(header_len..)?,
+        total_len.checked_sub(header_len)?,
+    )
+}
+
+fn ipv6(packet: &[u8]) -> Option<Segment<'_>> {
+    let payload_len = u16::from_be_bytes([*packet.get(4)?, *packet.get(5)?]) as usize;
+    let mut next_header = *packet.get(6)?;
+    let source: [u8; 16] = packet.get(8..24)?.try_into().ok()?;
+    let destination: [u8; 16] = packet.get(24..40)?.try_into().ok()?;
+    let mut payload = packet.get(40..)?;
+    let mut len = payload_len;
+    loop {
+        let header_len = match next_header {
+            PROTOCOL_TCP => break,
+            // Hop-by-hop, routing and destination options.
+            0 | 43 | 60 => (*payload.get(1)? as usize + 1) * 8,
+            // Authentication header.
+            51 => (*payload.get(1)? as usize + 2) * 4,
+            // Fragments, and protocols other than TCP.
+            _ => return None,
+        };
+        next_header = *payload.first()?;
+        payload = payload.get(header_len..)?;
+        len = len.checked_sub(header_len)?;
+    }
+    tcp(
+        Ipv6Addr::from(source).into(),
+        Ipv6Addr::from(destination).into(),
+        payload,
+        len,
+    )
+}
+
+/// Parses a TCP segment of `len` bytes, of which `segment` were captured.
+fn tcp(source: IpAddr, destination: IpAddr, segment: &[u8], len: usize) -> Option<Segment<'_>> {
+    let header_len = ((segment.get(12)? >> 4) as usize) * 4;
+    let flags = *segment.get(13)?;
+    // Bytes after `len` are padding of the link layer.
+    let captured = segment.get(header_len..len.min(segment.len()))?;
+    Some(Segment {
+        source: SocketAddr::new(source, u16::from_be_bytes([segment[0], segment[1]])),
+        destination: SocketAddr::new(destination, u16::from_be_bytes([segment[2], segment[3]])),
+        seq: u32::from_be_bytes([segment[4], segment[5], segment[6], segment[7]]),
+        syn: flags & SYN != 0,
+        fin: flags & FIN != 0,
+        rst: flags & RST != 0,
+        payload: captured,
+        truncated: len > segment.len(),
+    })
+}
+
+/// Builds a raw IP packet carrying a TCP segment, with valid checksums.
+/// `source` and `destination` must be of the same address family.
+pub(crate) fn packet(
+    source: SocketAddr,
+    destination: SocketAddr,
+    seq: u32,
+    ack: u32,
+    flags: u8,
+    payload: &[u8],
+) -> Vec<u8> {
+    let tcp_len = 20 + payload.len();
+    let mut segment = Vec::with_capacity(tcp_len);
+    segment.extend_from_slice(&source.port().to_be_bytes());
+    segment.extend_from_slice(&destination.port().to_be_bytes());
+    segment.extend_from_slice(&seq.to_be_bytes());
+    segment.extend_from_slice(&ack.to_be_bytes());
+    // Header length in 32 bit words, without options.
+    segment.extend_from_slice(&[5 << 4, flags]);
+    // Window, checksum and urgent pointer.
+    segment.extend_from_slice(&[0xff, 0xff, 0, 0, 0, 0]);
+    segment.extend_from_slice(payload);
+    let mut packet = Vec::with_capacity(40 + tcp_len);
+    let pseudo_header = match (source.ip(), destination.ip()) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            packet.extend_from_slice(&[0x45, 0]);
+            packet.extend_from_slice(&((20 + tcp_len) as u16).to_be_bytes());
+            // Identification, don't fragment flag, TTL and protocol.
+            packet.extend_from_slice(&[0, 0, 0x40, 0, 64, PROTOCOL_TCP, 0, 0]);
+            packet.extend_from_slice(&source.octets());
+            packet.extend_from_slice(&destination.octets());
+            let checksum = checksum(0, &packet);
+            packet[10..12].copy_from_slice(&checksum.to_be_bytes());
+            sum(
+                sum(sum(0, &source.octets()), &destination.octets()),
+                &[0, PROTOCOL_TCP],
+            ) + tcp_len as u32
+        }
+        (IpAddr::V6(source), IpAddr::V6(destination)) => {
+            packet.extend_from_slice(&[6 << 4, 0, 0, 0]);
+            packet.extend_from_slice(&(tcp_len as u16).to_be_bytes());
+            // Next header and hop limit.
+            packet.extend_from_slice(&[PROTOCOL_TCP, 64]);
+            packet.extend_from_slice(&source.octets());
+            packet.extend_from_slice(&destination.octets());
+            sum(
+                sum(sum(0, &source.octets()), &destination.octets()),
+                &[0, PROTOCOL_TCP],
+            ) + tcp_len as u32
+        }
+        _ => unreachable!("addresses of different families"),
+    };
+    let checksum = checksum(pseudo_header, &segment);
+    segment[16..18].copy_from_slice(&checksum.to_be_bytes());
+    packet.extend_from_slice(&segment);
+    packet
+}
+
+/// Adds `bytes` as big endian 16 bit words to a ones' complement sum.
+fn sum(mut sum: u32, bytes: &[u8]) -> u32 {
+    for word in bytes.chunks(2) {
+        sum += u16::from_be_bytes([word[0], *word.get(1).unwrap_or(&0)]) as u32;
+    }
+    sum
+}
+
+/// Returns the internet checksum of `bytes`, starting from a partial sum.
+fn checksum(start: u32, bytes: &[u8]) -> u16 {
+    let mut sum = sum(start, bytes);
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// One direction of a TCP connection, putting segments back in order.
+#[derive(Default)]
+pub(crate) struct Stream {
+    /// Sequence number of the next byte expected, unknown until the first
+    /// segment when the capture started after the handshake.
+    next_seq: Option<u32>,
+    /// Segments received ahead of `next_seq`.
+    ahead: Vec<(u32, Vec<u8>)>,
+    /// Bytes received in order and not consumed yet.
+    pub(crate) buffer: BytesMut,
+}
+
+impl Stream {
+    /// Adds the payload of a segment, returning false when segments kept
+    /// arriving after a gap that was never filled.
+    pub(crate) fn push(&mut self, segment: &Segment) -> bool {
+        let mut seq = segment.seq;
+        if segment.syn {
+            *self = Self::default();
+            // The SYN flag takes a sequence number.
+            seq = seq.wrapping_add(1);
+        }
+        self.next_seq.get_or_insert(seq);
+        if segment.payload.is_empty() {
+            return true;
+        }
+        if !self.insert(seq, segment.payload) {
+            if self.ahead.len() == MAX_AHEAD {
+                return false;
+            }
+            self.ahead.push((seq, segment.payload.to_vec()));
+            return true;
+        }
+        while let Some(index) = self
+            .ahead
+            .iter()
+            .position(|(seq, _)| self.offset(*seq) <= 0)
+        {
+            let (seq, payload) = self.ahead.swap_remove(index);
+            self.insert(seq, &payload);
+        }
+        true
+    }
+
+    /// Appends the bytes of `payload` not received yet, returning false if
+    /// it starts after the next byte expected.
+    fn insert(&mut self, seq: u32, payload: &[u8]) -> bool {
+        let offset = self.offset(seq);
+        if offset > 0 {
+            return false;
+        }
+        // Retransmitted bytes are skipped.
+        let skip = offset.unsigned_abs() as usize;
+        if let Some(new) = payload.get(skip..) {
+            self.buffer.extend_from_slice(new);
+            self.next_seq = self
+                .next_seq
+                .map(|next| next.wrapping_add(new.len() as u32));
+        }
+        true
+    }
+
+    /// Whether segments are kept waiting for a missing one.
+    pub(crate) fn waiting(&self) -> bool {
+        !self.ahead.is_empty()
+    }
+
+    /// Returns the distance from the next byte expected to `seq`, which
+    /// wraps around.
+    fn offset(&self, seq: u32) -> i32 {
+        seq.wrapping_sub(self.next_seq.unwrap_or(seq)) as i32
+    }
+}
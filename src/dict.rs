@@ -0,0 +1,102 @@
+//! Helpers converting packets to and from JSON-safe dicts.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Text encoding of binary fields such as payloads.
+#[derive(Clone, Copy)]
+pub(crate) enum Encoding {
+    Base64,
+    Hex,
+}
+
+impl Encoding {
+    pub(crate) fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "base64" => Ok(Self::Base64),
+            "hex" => Ok(Self::Hex),
+            _ => Err(PyValueError::new_err(format!(
+                "unknown encoding {name:?}, expected 'base64' or 'hex'"
+            ))),
+        }
+    }
+
+    pub(crate) fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Base64 => STANDARD.encode(bytes),
+            Self::Hex => bytes.iter().map(|byte| format!("{byte:02x}")).collect(),
+        }
+    }
+
+    pub(crate) fn decode(self, text: &str) -> PyResult<Vec<u8>> {
+        match self {
+            Self::Base64 => STANDARD
+                .decode(text)
+                .map_err(|err| PyValueError::new_err(format!("invalid base64: {err}"))),
+            Self::Hex => {
+                let invalid = || PyValueError::new_err(format!("invalid hex: {text:?}"));
+                if !text.len().is_multiple_of(2) {
+                    return Err(invalid());
+                }
+                (0..text.len())
+                    .step_by(2)
+                    .map(|i| {
+                        text.get(i..i + 2)
+                            .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                            .ok_or_else(invalid)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Creates a dict holding the `type` key of a packet.
+pub(crate) fn packet_dict<'py>(_py: Python<'py>, packet_type: &str) -> PyResult<&'py PyDict> {
+    let dict = PyDict::new(_py);
+    dict.set_item("type", packet_type)?;
+    Ok(dict)
+}
+
+/// Checks that the `type` key, when present, names the expected packet.
+pub(crate) fn check_type(data: &PyDict, packet_type: &str) -> PyResult<()> {
+    match optional::<&str>(data, "type")? {
+        Some(found) if found != packet_type => Err(PyValueError::new_err(format!(
+            "expected a {packet_type} dict, got type {found:?}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Extracts a key that must be present.
+pub(crate) fn required<'a, T: FromPyObject<'a>>(data: &'a PyDict, key: &str) -> PyResult<T> {
+    match data.get_item(key) {
+        Some(value) => value.extract(),
+        None => Err(PyKeyError::new_err(format!("missing key {key:?}"))),
+    }
+}
+
+/// Extracts a key that may be absent or `None`.
+pub(crate) fn optional<'a, T: FromPyObject<'a>>(
+    data: &'a PyDict,
+    key: &str,
+) -> PyResult<Option<T>> {
+    match data.get_item(key) {
+        Some(value) if !value.is_none() => value.extract().map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Extracts an encoded binary key that must be present.
+pub(crate) fn required_bytes(data: &PyDict, key: &str, encoding: Encoding) -> PyResult<Vec<u8>> {
+    encoding.decode(required(data, key)?)
+}
+
+/// Extracts a QoS stored as its number.
+pub(crate) fn required_qos(data: &PyDict, key: &str) -> PyResult<::mqttbytes::QoS> {
+    let num: u8 = required(data, key)?;
+    ::mqttbytes::qos(num).map_err(|_| PyValueError::new_err(format!("invalid QoS {num}")))
+}
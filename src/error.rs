@@ -0,0 +1,276 @@
+//! Exceptions raised for each `::mqttbytes::Error` variant.
+//!
+//! Every exception derives from `MqttBytesError`. Only `InsufficientBytes`
+//! means that reading can be retried once more data is received; the others
+//! mean that the stream is corrupt or the packet can't be encoded.
+
+use pyo3::create_exception;
+use pyo3::prelude::*;
+
+use crate::{MqttBytesError, PacketType};
+
+create_exception!(
+    mqttbytes,
+    InsufficientBytes,
+    MqttBytesError,
+    "More bytes are required to frame a packet. `required` is the minimum number of additional bytes."
+);
+create_exception!(
+    mqttbytes,
+    NotConnect,
+    MqttBytesError,
+    "First packet isn't a Connect. `packet_type` is the type of the packet received."
+);
+create_exception!(
+    mqttbytes,
+    UnexpectedConnect,
+    MqttBytesError,
+    "Connect packet received on an established connection."
+);
+create_exception!(
+    mqttbytes,
+    InvalidConnectReturnCode,
+    MqttBytesError,
+    "Unknown ConnAck return code, stored in `code`."
+);
+create_exception!(
+    mqttbytes,
+    InvalidReason,
+    MqttBytesError,
+    "Unknown reason code, stored in `code`."
+);
+create_exception!(
+    mqttbytes,
+    InvalidProtocol,
+    MqttBytesError,
+    "Connect packet with a protocol name other than MQTT."
+);
+create_exception!(
+    mqttbytes,
+    InvalidProtocolLevel,
+    MqttBytesError,
+    "Connect packet with an unsupported protocol level, stored in `level`."
+);
+create_exception!(
+    mqttbytes,
+    IncorrectPacketFormat,
+    MqttBytesError,
+    "Packet fields are inconsistent with its type."
+);
+create_exception!(
+    mqttbytes,
+    InvalidPacketType,
+    MqttBytesError,
+    "Unknown packet type, stored in `packet_type`."
+);
+create_exception!(
+    mqttbytes,
+    InvalidPropertyType,
+    MqttBytesError,
+    "Unknown property identifier, stored in `property_type`."
+);
+create_exception!(
+    mqttbytes,
+    InvalidRetainForwardRule,
+    MqttBytesError,
+    "Unknown retain handling option, stored in `rule`."
+);
+create_exception!(
+    mqttbytes,
+    InvalidQoS,
+    MqttBytesError,
+    "QoS other than 0, 1 or 2, stored in `qos`."
+);
+create_exception!(
+    mqttbytes,
+    InvalidSubscribeReasonCode,
+    MqttBytesError,
+    "Unknown SubAck return code, stored in `code`."
+);
+create_exception!(
+    mqttbytes,
+    PacketIdZero,
+    MqttBytesError,
+    "Packet id 0 used by a packet that requires one."
+);
+create_exception!(
+    mqttbytes,
+    SubscriptionIdZero,
+    MqttBytesError,
+    "Subscription identifier 0 in a v5 packet."
+);
+create_exception!(
+    mqttbytes,
+    PayloadSizeIncorrect,
+    MqttBytesError,
+    "Payload length doesn't match the remaining length."
+);
+create_exception!(
+    mqttbytes,
+    PayloadTooLong,
+    MqttBytesError,
+    "Payload is longer than the protocol allows."
+);
+create_exception!(
+    mqttbytes,
+    PayloadSizeLimitExceeded,
+    MqttBytesError,
+    "Packet is larger than `max_size`. `size` is the size of the packet."
+);
+create_exception!(
+    mqttbytes,
+    PayloadRequired,
+    MqttBytesError,
+    "Packet requires a payload and has none."
+);
+create_exception!(
+    mqttbytes,
+    TopicNotUtf8,
+    MqttBytesError,
+    "Topic or string field isn't valid UTF-8."
+);
+create_exception!(
+    mqttbytes,
+    BoundaryCrossed,
+    MqttBytesError,
+    "Field extends past the end of the packet. `length` is the length read."
+);
+create_exception!(
+    mqttbytes,
+    MalformedPacket,
+    MqttBytesError,
+    "Packet can't be decoded."
+);
+create_exception!(
+    mqttbytes,
+    MalformedRemainingLength,
+    MqttBytesError,
+    "Remaining length is encoded with more than 4 bytes."
+);
+
+/// Creates the exception matching `err`, with its argument as an attribute.
+pub(crate) fn to_pyerr(err: ::mqttbytes::Error) -> PyErr {
+    use ::mqttbytes::Error;
+
+    let message = err.to_string();
+    Python::with_gil(|_py| {
+        let (pyerr, attribute) = match err {
+            Error::NotConnect(packet_type) => (
+                NotConnect::new_err(message),
+                Some(("packet_type", PacketType::from(packet_type).into_py(_py))),
+            ),
+            Error::UnexpectedConnect => (UnexpectedConnect::new_err(message), None),
+            Error::InvalidConnectReturnCode(code) => (
+                InvalidConnectReturnCode::new_err(message),
+                Some(("code", code.into_py(_py))),
+            ),
+            Error::InvalidReason(code) => (
+                InvalidReason::new_err(message),
+                Some(("code", code.into_py(_py))),
+            ),
+            Error::InvalidProtocol => (InvalidProtocol::new_err(message), None),
+            Error::InvalidProtocolLevel(level) => (
+                InvalidProtocolLevel::new_err(message),
+                Some(("level", level.into_py(_py))),
+            ),
+            Error::IncorrectPacketFormat => (IncorrectPacketFormat::new_err(message), None),
+            Error::InvalidPacketType(packet_type) => (
+                InvalidPacketType::new_err(message),
+                Some(("packet_type", packet_type.into_py(_py))),
+            ),
+            Error::InvalidPropertyType(property_type) => (
+                InvalidPropertyType::new_err(message),
+                Some(("property_type", property_type.into_py(_py))),
+            ),
+            Error::InvalidRetainForwardRule(rule) => (
+                InvalidRetainForwardRule::new_err(message),
+                Some(("rule", rule.into_py(_py))),
+            ),
+            Error::InvalidQoS(qos) => (
+                InvalidQoS::new_err(message),
+                Some(("qos", qos.into_py(_py))),
+            ),
+            Error::InvalidSubscribeReasonCode(code) => (
+                InvalidSubscribeReasonCode::new_err(message),
+                Some(("code", code.into_py(_py))),
+            ),
+            Error::PacketIdZero => (PacketIdZero::new_err(message), None),
+            Error::SubscriptionIdZero => (SubscriptionIdZero::new_err(message), None),
+            Error::PayloadSizeIncorrect => (PayloadSizeIncorrect::new_err(message), None),
+            Error::PayloadTooLong => (PayloadTooLong::new_err(message), None),
+            Error::PayloadSizeLimitExceeded(size) => (
+                PayloadSizeLimitExceeded::new_err(message),
+                Some(("size", size.into_py(_py))),
+            ),
+            Error::PayloadRequired => (PayloadRequired::new_err(message), None),
+            Error::TopicNotUtf8 => (TopicNotUtf8::new_err(message), None),
+            Error::BoundaryCrossed(length) => (
+                BoundaryCrossed::new_err(message),
+                Some(("length", length.into_py(_py))),
+            ),
+            Error::MalformedPacket => (MalformedPacket::new_err(message), None),
+            Error::MalformedRemainingLength => (MalformedRemainingLength::new_err(message), None),
+            Error::InsufficientBytes(required) => (
+                InsufficientBytes::new_err(message),
+                Some(("required", required.into_py(_py))),
+            ),
+        };
+        if let Some((name, value)) = attribute {
+            if let Err(err) = pyerr.value(_py).setattr(name, value) {
+                return err;
+            }
+        }
+        pyerr
+    })
+}
+
+pub(crate) fn register(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("InsufficientBytes", _py.get_type::<InsufficientBytes>())?;
+    m.add("NotConnect", _py.get_type::<NotConnect>())?;
+    m.add("UnexpectedConnect", _py.get_type::<UnexpectedConnect>())?;
+    m.add(
+        "InvalidConnectReturnCode",
+        _py.get_type::<InvalidConnectReturnCode>(),
+    )?;
+    m.add("InvalidReason", _py.get_type::<InvalidReason>())?;
+    m.add("InvalidProtocol", _py.get_type::<InvalidProtocol>())?;
+    m.add(
+        "InvalidProtocolLevel",
+        _py.get_type::<InvalidProtocolLevel>(),
+    )?;
+    m.add(
+        "IncorrectPacketFormat",
+        _py.get_type::<IncorrectPacketFormat>(),
+    )?;
+    m.add("InvalidPacketType", _py.get_type::<InvalidPacketType>())?;
+    m.add("InvalidPropertyType", _py.get_type::<InvalidPropertyType>())?;
+    m.add(
+        "InvalidRetainForwardRule",
+        _py.get_type::<InvalidRetainForwardRule>(),
+    )?;
+    m.add("InvalidQoS", _py.get_type::<InvalidQoS>())?;
+    m.add(
+        "InvalidSubscribeReasonCode",
+        _py.get_type::<InvalidSubscribeReasonCode>(),
+    )?;
+    m.add("PacketIdZero", _py.get_type::<PacketIdZero>())?;
+    m.add("SubscriptionIdZero", _py.get_type::<SubscriptionIdZero>())?;
+    m.add(
+        "PayloadSizeIncorrect",
+        _py.get_type::<PayloadSizeIncorrect>(),
+    )?;
+    m.add("PayloadTooLong", _py.get_type::<PayloadTooLong>())?;
+    m.add(
+        "PayloadSizeLimitExceeded",
+        _py.get_type::<PayloadSizeLimitExceeded>(),
+    )?;
+    m.add("PayloadRequired", _py.get_type::<PayloadRequired>())?;
+    m.add("TopicNotUtf8", _py.get_type::<TopicNotUtf8>())?;
+    m.add("BoundaryCrossed", _py.get_type::<BoundaryCrossed>())?;
+    m.add("MalformedPacket", _py.get_type::<MalformedPacket>())?;
+    m.add(
+        "MalformedRemainingLength",
+        _py.get_type::<MalformedRemainingLength>(),
+    )?;
+    Ok(())
+}
@@ -1,9 +1,22 @@
-use pyo3::exceptions::PyException;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use pyo3::buffer::PyBuffer;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyBufferError, PyException, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
-use pyo3::{create_exception, wrap_pymodule};
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBool, PyBytes, PyLong};
 
+mod capture;
+mod dict;
+mod error;
+mod payload;
+mod repr;
+mod testing;
+mod tree;
 mod v4;
+mod v5;
 
 create_exception!(
     mqttbytes,
@@ -12,6 +25,20 @@ create_exception!(
     "Error during serialization and deserialization."
 );
 
+create_exception!(
+    mqttbytes,
+    ProtocolError,
+    PyException,
+    "Packet received or sent out of order with the MQTT protocol flow."
+);
+
+create_exception!(
+    mqttbytes,
+    ValidationError,
+    MqttBytesError,
+    "Packet breaking a rule of the MQTT specification, raised by `validate()` and strict reads and writes."
+);
+
 pub struct WrapperMqttBytesError(::mqttbytes::Error);
 
 impl From<::mqttbytes::Error> for WrapperMqttBytesError {
@@ -22,7 +49,42 @@ impl From<::mqttbytes::Error> for WrapperMqttBytesError {
 
 impl From<WrapperMqttBytesError> for PyErr {
     fn from(err: WrapperMqttBytesError) -> PyErr {
-        MqttBytesError::new_err(err.0.to_string())
+        error::to_pyerr(err.0)
+    }
+}
+
+/// Borrows the bytes of a C-contiguous buffer without copying them.
+pub(crate) fn buffer_as_slice<'a>(_py: Python<'a>, buffer: &'a PyBuffer<u8>) -> PyResult<&'a [u8]> {
+    if !buffer.is_c_contiguous() {
+        return Err(PyBufferError::new_err("buffer is not C-contiguous"));
+    }
+    // SAFETY: the buffer is contiguous, holds `len_bytes` bytes and stays
+    // exported (so it cannot be resized) for as long as `buffer` is alive.
+    Ok(unsafe { std::slice::from_raw_parts(buffer.buf_ptr() as *const u8, buffer.len_bytes()) })
+}
+
+/// Borrows the bytes of a writable C-contiguous buffer without copying them.
+#[allow(clippy::mut_from_ref)]
+pub(crate) fn buffer_as_mut_slice<'a>(
+    _py: Python<'a>,
+    buffer: &'a PyBuffer<u8>,
+) -> PyResult<&'a mut [u8]> {
+    if buffer.readonly() {
+        return Err(PyBufferError::new_err("buffer is read-only"));
+    }
+    if !buffer.is_c_contiguous() {
+        return Err(PyBufferError::new_err("buffer is not C-contiguous"));
+    }
+    // SAFETY: as in `buffer_as_slice`, and the exporter allows writes.
+    Ok(unsafe { std::slice::from_raw_parts_mut(buffer.buf_ptr() as *mut u8, buffer.len_bytes()) })
+}
+
+/// Compares two values for `==` and `!=`; other comparisons are not supported.
+pub(crate) fn richcmp<T: PartialEq>(_py: Python, a: &T, b: &T, op: CompareOp) -> PyObject {
+    match op {
+        CompareOp::Eq => (a == b).into_py(_py),
+        CompareOp::Ne => (a != b).into_py(_py),
+        _ => _py.NotImplemented(),
     }
 }
 
@@ -40,13 +102,29 @@ impl From<WrapperMqttBytesError> for PyErr {
 /// ```
 #[pyclass(module = "mqttbytes")]
 #[derive(Clone)]
-pub struct FixedHeader(::mqttbytes::FixedHeader);
+pub struct FixedHeader(::mqttbytes::FixedHeader, FixedHeaderFields);
+
+/// Fields of a `FixedHeader`, which upstream keeps private.
+#[derive(Clone, Hash)]
+struct FixedHeaderFields {
+    byte1: u8,
+    fixed_header_len: usize,
+    remaining_len: usize,
+}
 
 #[pymethods]
 impl FixedHeader {
     #[new]
     fn new(byte1: u8, remaining_len_len: usize, remaining_len: usize) -> Self {
-        ::mqttbytes::FixedHeader::new(byte1, remaining_len_len, remaining_len).into()
+        let fields = FixedHeaderFields {
+            byte1,
+            fixed_header_len: remaining_len_len + 1,
+            remaining_len,
+        };
+        Self(
+            ::mqttbytes::FixedHeader::new(byte1, remaining_len_len, remaining_len),
+            fields,
+        )
     }
 
     fn packet_type(&self) -> Result<PacketType, WrapperMqttBytesError> {
@@ -58,16 +136,33 @@ impl FixedHeader {
     fn frame_length(&self) -> usize {
         self.0.frame_length()
     }
-}
 
-impl From<::mqttbytes::FixedHeader> for FixedHeader {
-    fn from(fixed_header: ::mqttbytes::FixedHeader) -> Self {
-        Self(fixed_header)
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.1.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn __repr__(&self) -> String {
+        let packet_type = match self.0.packet_type() {
+            Ok(packet_type) => format!("PacketType.{packet_type:?}"),
+            Err(_) => "None".to_owned(),
+        };
+        format!(
+            "FixedHeader(packet_type={}, frame_length={})",
+            packet_type,
+            self.0.frame_length()
+        )
     }
 }
 
 /// MQTT packet type.
 #[pyclass(module = "mqttbytes")]
+#[derive(Clone)]
 #[repr(u8)]
 enum PacketType {
     Connect = 1,
@@ -86,6 +181,13 @@ enum PacketType {
     Disconnect,
 }
 
+#[pymethods]
+impl PacketType {
+    fn __hash__(&self) -> u64 {
+        self.clone() as u64
+    }
+}
+
 impl From<::mqttbytes::PacketType> for PacketType {
     fn from(packet_type: ::mqttbytes::PacketType) -> Self {
         match packet_type {
@@ -114,6 +216,13 @@ pub enum Protocol {
     V5,
 }
 
+#[pymethods]
+impl Protocol {
+    fn __hash__(&self) -> u64 {
+        self.clone() as u64
+    }
+}
+
 impl From<::mqttbytes::Protocol> for Protocol {
     fn from(protocol: ::mqttbytes::Protocol) -> Self {
         match protocol {
@@ -143,6 +252,13 @@ pub enum QoS {
     ExactlyOnce = 2,
 }
 
+#[pymethods]
+impl QoS {
+    fn __hash__(&self) -> u64 {
+        self.clone() as u64
+    }
+}
+
 impl From<::mqttbytes::QoS> for QoS {
     fn from(qos: ::mqttbytes::QoS) -> Self {
         match qos {
@@ -163,6 +279,35 @@ impl From<QoS> for ::mqttbytes::QoS {
     }
 }
 
+/// QoS argument accepting a `QoS` or its number, e.g. `qos=1`.
+pub(crate) struct QoSArg(pub(crate) ::mqttbytes::QoS);
+
+impl<'source> FromPyObject<'source> for QoSArg {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if let Ok(qos) = ob.extract::<QoS>() {
+            return Ok(Self(qos.into()));
+        }
+        // `bool` is a subclass of `int`, but `qos=True` is a mistake.
+        if !ob.is_instance_of::<PyLong>()? || ob.is_instance_of::<PyBool>()? {
+            return Err(PyTypeError::new_err(format!(
+                "expected QoS or int, got {}",
+                ob.get_type().name()?
+            )));
+        }
+        let num: u8 = ob
+            .extract()
+            .map_err(|_| PyValueError::new_err(format!("invalid QoS {ob}")))?;
+        let qos = ::mqttbytes::qos(num).map_err(WrapperMqttBytesError::from)?;
+        Ok(Self(qos))
+    }
+}
+
+impl From<QoSArg> for ::mqttbytes::QoS {
+    fn from(qos: QoSArg) -> Self {
+        qos.0
+    }
+}
+
 /// Checks if the stream has enough bytes to frame a packet and returns fixed header
 /// only if a packet can be framed with existing bytes in the `stream`.
 /// The passed stream doesn't modify parent stream's cursor. If this function
@@ -170,9 +315,20 @@ impl From<QoS> for ::mqttbytes::QoS {
 /// with cursor at 0 again (Iter is owned. Only Iter's cursor is changed internally).
 #[pyfunction]
 fn check(stream: &PyBytes, max_packet_size: usize) -> Result<FixedHeader, WrapperMqttBytesError> {
-    ::mqttbytes::check(stream.as_bytes().iter(), max_packet_size)
-        .map(Into::into)
-        .map_err(Into::into)
+    let stream = stream.as_bytes();
+    let frame_length = ::mqttbytes::check(stream.iter(), max_packet_size)?.frame_length();
+    // The header was parsed, so the remaining length ends at the first byte
+    // without the continuation bit.
+    let remaining_len_len = stream[1..]
+        .iter()
+        .take_while(|byte| *byte & 0x80 != 0)
+        .count()
+        + 1;
+    Ok(FixedHeader::new(
+        stream[0],
+        remaining_len_len,
+        frame_length - remaining_len_len - 1,
+    ))
 }
 
 /// Checks if a topic or topic filter has wildcards.
@@ -215,15 +371,41 @@ fn valid_topic(topic: &str) -> bool {
 fn mqttbytes(_py: Python, m: &PyModule) -> PyResult<()> {
     let version = env!("CARGO_PKG_VERSION");
     m.add("__version__", version)?;
-    m.add_wrapped(wrap_pymodule!(v4::v4))?;
+    // Submodules are named after their import path so that their functions
+    // report it as `__module__`, which pickle relies on.
+    let v4 = PyModule::new(_py, "mqttbytes.v4")?;
+    v4::v4(_py, v4)?;
+    m.add("v4", v4)?;
+    _py.import("sys")?
+        .getattr("modules")?
+        .set_item("mqttbytes.v4", v4)?;
+    let v5 = PyModule::new(_py, "mqttbytes.v5")?;
+    v5::v5(_py, v5)?;
+    m.add("v5", v5)?;
+    _py.import("sys")?
+        .getattr("modules")?
+        .set_item("mqttbytes.v5", v5)?;
+    let capture = PyModule::new(_py, "mqttbytes.capture")?;
+    capture::capture(_py, capture)?;
+    m.add("capture", capture)?;
+    _py.import("sys")?
+        .getattr("modules")?
+        .set_item("mqttbytes.capture", capture)?;
+    let testing = PyModule::new(_py, "mqttbytes.testing")?;
+    testing::testing(_py, testing)?;
+    m.add("testing", testing)?;
     _py.import("sys")?
         .getattr("modules")?
-        .set_item("mqttbytes.v4", m.getattr("v4")?)?;
+        .set_item("mqttbytes.testing", testing)?;
     m.add_class::<FixedHeader>()?;
     m.add("MqttBytesError", _py.get_type::<MqttBytesError>())?;
+    m.add("ProtocolError", _py.get_type::<ProtocolError>())?;
+    m.add("ValidationError", _py.get_type::<ValidationError>())?;
+    error::register(_py, m)?;
     m.add_class::<PacketType>()?;
     m.add_class::<Protocol>()?;
     m.add_class::<QoS>()?;
+    m.add_class::<tree::SubscriptionTree>()?;
     m.add_function(wrap_pyfunction!(check, m)?)?;
     m.add_function(wrap_pyfunction!(has_wildcards, m)?)?;
     m.add_function(wrap_pyfunction!(matches, m)?)?;
@@ -0,0 +1,77 @@
+//! Zero-copy access to payloads from Python.
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int, c_void};
+use std::ptr;
+
+use bytes::Bytes;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyBufferError;
+use pyo3::{ffi, prelude::*, AsPyPointer};
+
+use crate::buffer_as_slice;
+
+/// Format of the exported items, unsigned bytes.
+const FORMAT: &CStr = c"B";
+
+/// Read-only buffer exporting the bytes of a payload.
+///
+/// `Bytes` is reference counted and immutable, so the exported memory stays
+/// valid and unchanged for as long as a view holds this object.
+#[pyclass(module = "mqttbytes")]
+pub struct PayloadBuffer(Bytes);
+
+#[pymethods]
+impl PayloadBuffer {
+    unsafe fn __getbuffer__(
+        slf: PyRef<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        if (flags & ffi::PyBUF_WRITABLE) == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("payload is read-only"));
+        }
+
+        (*view).obj = ffi::_Py_NewRef(slf.as_ptr());
+        (*view).buf = slf.0.as_ptr() as *mut c_void;
+        (*view).len = slf.0.len() as isize;
+        (*view).readonly = 1;
+        (*view).itemsize = 1;
+        (*view).format = if (flags & ffi::PyBUF_FORMAT) == ffi::PyBUF_FORMAT {
+            FORMAT.as_ptr() as *mut c_char
+        } else {
+            ptr::null_mut()
+        };
+        (*view).ndim = 1;
+        (*view).shape = if (flags & ffi::PyBUF_ND) == ffi::PyBUF_ND {
+            &mut (*view).len
+        } else {
+            ptr::null_mut()
+        };
+        (*view).strides = if (flags & ffi::PyBUF_STRIDES) == ffi::PyBUF_STRIDES {
+            &mut (*view).itemsize
+        } else {
+            ptr::null_mut()
+        };
+        (*view).suboffsets = ptr::null_mut();
+        (*view).internal = ptr::null_mut();
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {}
+}
+
+/// Returns a read-only `memoryview` sharing the memory of `bytes`.
+pub(crate) fn memoryview(_py: Python, bytes: Bytes) -> PyResult<PyObject> {
+    let buffer = Py::new(_py, PayloadBuffer(bytes))?;
+    // SAFETY: `buffer` is a valid object exporting the buffer protocol.
+    unsafe { PyObject::from_owned_ptr_or_err(_py, ffi::PyMemoryView_FromObject(buffer.as_ptr())) }
+}
+
+/// Copies the contents of any C-contiguous buffer into `Bytes`.
+pub(crate) fn buffer_to_bytes(_py: Python, buffer: &PyBuffer<u8>) -> PyResult<Bytes> {
+    buffer_as_slice(_py, buffer).map(Bytes::copy_from_slice)
+}
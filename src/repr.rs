@@ -0,0 +1,67 @@
+//! Helpers formatting values the way Python's `repr` does.
+
+/// Number of payload bytes shown before a repr is truncated.
+const BYTES_LIMIT: usize = 32;
+
+/// Formats a string as a Python `str` literal.
+pub(crate) fn repr_str(s: &str) -> String {
+    let quote = if s.contains('\'') && !s.contains('"') {
+        '"'
+    } else {
+        '\''
+    };
+    let mut repr = String::with_capacity(s.len() + 2);
+    repr.push(quote);
+    for c in s.chars() {
+        match c {
+            '\\' => repr.push_str("\\\\"),
+            '\n' => repr.push_str("\\n"),
+            '\r' => repr.push_str("\\r"),
+            '\t' => repr.push_str("\\t"),
+            c if c == quote => {
+                repr.push('\\');
+                repr.push(c);
+            }
+            c if c.is_control() => repr.push_str(&c.escape_unicode().to_string()),
+            c => repr.push(c),
+        }
+    }
+    repr.push(quote);
+    repr
+}
+
+/// Formats bytes as a Python `bytes` literal. Long payloads are cut after
+/// `BYTES_LIMIT` bytes and followed by their total length.
+pub(crate) fn repr_bytes(bytes: &[u8]) -> String {
+    let shown = &bytes[..bytes.len().min(BYTES_LIMIT)];
+    let mut repr = String::from("b'");
+    for &byte in shown {
+        match byte {
+            b'\\' => repr.push_str("\\\\"),
+            b'\'' => repr.push_str("\\'"),
+            b'\n' => repr.push_str("\\n"),
+            b'\r' => repr.push_str("\\r"),
+            b'\t' => repr.push_str("\\t"),
+            0x20..=0x7e => repr.push(byte as char),
+            _ => repr.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    repr.push('\'');
+    if shown.len() < bytes.len() {
+        repr.push_str(&format!("...({} bytes)", bytes.len()));
+    }
+    repr
+}
+
+/// Formats an optional value, `None` when absent.
+pub(crate) fn repr_option<T>(value: Option<T>, repr: impl FnOnce(T) -> String) -> String {
+    value.map_or_else(|| "None".to_owned(), repr)
+}
+
+/// Formats a boolean as a Python literal.
+pub(crate) fn repr_bool(value: bool) -> &'static str {
+    match value {
+        true => "True",
+        false => "False",
+    }
+}
@@ -0,0 +1,396 @@
+//! Helpers for testing MQTT applications.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use bytes::BytesMut;
+use pyo3::prelude::*;
+
+use crate::tree::matches;
+use crate::v4::{Publish, RetainForwardRule, RetainedStore, ServerSession, SubscribeFilter};
+use crate::QoSArg;
+
+/// Connection to the broker, from before its `Connect` until it is closed.
+struct Client {
+    /// Set once the `Connect` is accepted.
+    client_id: Option<String>,
+    /// Shut down when a new connection takes over the client id.
+    stream: TcpStream,
+    session: ServerSession,
+    subscriptions: HashMap<String, ::mqttbytes::QoS>,
+    /// Published when the connection is closed without a `Disconnect`.
+    last_will: Option<::mqttbytes::v4::LastWill>,
+    /// Queue of the thread writing to the connection, so that no write blocks
+    /// while the state is locked.
+    writer: Sender<BytesMut>,
+}
+
+impl Client {
+    /// Sends a publish downgraded to `qos`.
+    fn publish(&mut self, mut publish: ::mqttbytes::v4::Publish, qos: ::mqttbytes::QoS) {
+        if qos < publish.qos {
+            publish.qos = qos;
+        }
+        publish.dup = false;
+        // Fails when the session isn't connected or has no packet id left.
+        if self.session.send(publish).is_ok() {
+            self.flush();
+        }
+    }
+
+    /// Queues the bytes of the session for the writer thread. Once a write
+    /// failed the queue is closed, and the connection ends with its next read.
+    fn flush(&mut self) {
+        let data = self.session.take_outgoing();
+        if !data.is_empty() {
+            let _ = self.writer.send(data);
+        }
+    }
+}
+
+struct State {
+    /// Connections by number, which tells a client apart from the one that
+    /// took over its client id.
+    clients: HashMap<u64, Client>,
+    client_ids: HashMap<String, u64>,
+    retained: RetainedStore,
+    connections: u64,
+}
+
+impl State {
+    /// Stores a retained publish and forwards it to the matching subscribers,
+    /// once per client at the highest QoS of their matching subscriptions.
+    fn route(&mut self, publish: ::mqttbytes::v4::Publish) {
+        if publish.retain {
+            self.retained.store(&Publish(publish.clone()));
+        }
+        for client in self.clients.values_mut() {
+            let qos = client
+                .subscriptions
+                .iter()
+                .filter(|(filter, _)| matches(&publish.topic, filter))
+                .map(|(_, qos)| *qos)
+                .fold(None, |max, qos| match max {
+                    Some(max) if qos <= max => Some(max),
+                    _ => Some(qos),
+                });
+            if let Some(qos) = qos {
+                let mut publish = publish.clone();
+                publish.retain = false;
+                client.publish(publish, qos);
+            }
+        }
+    }
+
+    /// Feeds bytes received on a connection to its session and acts on the
+    /// events. Returns false when the connection should be closed.
+    fn receive(&mut self, connection: u64, data: &[u8]) -> bool {
+        let Some(client) = self.clients.get_mut(&connection) else {
+            return false;
+        };
+        let (events, error) = match client.session.receive(data) {
+            Ok(received) => received,
+            Err(err) => (Vec::new(), Some(err)),
+        };
+        client.flush();
+        for event in events {
+            self.handle(connection, event);
+        }
+        let Some(client) = self.clients.get_mut(&connection) else {
+            return false;
+        };
+        client.flush();
+        error.is_none() && !client.session.get_closed()
+    }
+
+    /// Acts on an event of the session of a connection.
+    fn handle(&mut self, connection: u64, event: ::mqttbytes::v4::Packet) {
+        use ::mqttbytes::v4::Packet;
+
+        let State {
+            clients,
+            client_ids,
+            retained,
+            ..
+        } = self;
+        let Some(client) = clients.get_mut(&connection) else {
+            return;
+        };
+        match event {
+            Packet::Connect(connect) => {
+                let client_id = match connect.client_id.is_empty() {
+                    true => format!("auto-{connection}"),
+                    false => connect.client_id,
+                };
+                client.client_id = Some(client_id.clone());
+                client.last_will = connect.last_will;
+                if let Some(previous) = client_ids.insert(client_id, connection) {
+                    if let Some(previous) = clients.get(&previous) {
+                        let _ = previous.stream.shutdown(Shutdown::Both);
+                    }
+                }
+            }
+            Packet::Publish(publish) => self.route(publish),
+            Packet::Subscribe(subscribe) => {
+                for filter in subscribe.filters {
+                    if !::mqttbytes::valid_filter(&filter.path) {
+                        continue;
+                    }
+                    client.subscriptions.insert(filter.path.clone(), filter.qos);
+                    let filter = SubscribeFilter(filter);
+                    for publish in
+                        retained.retained(&filter, RetainForwardRule::OnEverySubscribe, true)
+                    {
+                        let qos = publish.0.qos;
+                        client.publish(publish.0, qos);
+                    }
+                }
+            }
+            Packet::Unsubscribe(unsubscribe) => {
+                for topic in &unsubscribe.topics {
+                    client.subscriptions.remove(topic);
+                }
+            }
+            Packet::Disconnect => client.last_will = None,
+            _ => {}
+        }
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    max_size: usize,
+    closed: AtomicBool,
+    /// Streams and threads of the connections, to shut them down on close.
+    connections: Mutex<Vec<(TcpStream, JoinHandle<()>)>>,
+}
+
+/// MQTT 3.1.1 broker listening on localhost, for integration tests.
+///
+/// Clients are served by Rust threads, without the GIL. The broker handles
+/// QoS 0, 1 and 2 in both directions, retained messages and last wills.
+/// Sessions are not persisted: a client reconnecting with the same client id
+/// takes over the previous connection and starts without subscriptions.
+#[pyclass(module = "mqttbytes.testing")]
+pub struct Broker {
+    shared: Arc<Shared>,
+    address: SocketAddr,
+    acceptor: Option<JoinHandle<()>>,
+}
+
+#[pymethods]
+impl Broker {
+    /// Starts listening on 127.0.0.1 at `port`, or on a free port when 0.
+    #[new]
+    #[args(port = "0", max_size = "1024 * 1024")]
+    fn new(port: u16, max_size: usize) -> PyResult<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let address = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                clients: HashMap::new(),
+                client_ids: HashMap::new(),
+                retained: RetainedStore::new(),
+                connections: 0,
+            }),
+            max_size,
+            closed: AtomicBool::new(false),
+            connections: Mutex::new(Vec::new()),
+        });
+        let acceptor = {
+            let shared = shared.clone();
+            thread::spawn(move || accept(shared, listener))
+        };
+        Ok(Self {
+            shared,
+            address,
+            acceptor: Some(acceptor),
+        })
+    }
+
+    /// Stops listening and closes every connection.
+    fn close(&mut self, _py: Python) {
+        _py.allow_threads(|| self.shutdown());
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __exit__(
+        &mut self,
+        _py: Python,
+        _exc_type: &PyAny,
+        _exc_value: &PyAny,
+        _traceback: &PyAny,
+    ) -> bool {
+        self.close(_py);
+        false
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Broker(port={})", self.address.port())
+    }
+
+    #[getter]
+    fn get_host(&self) -> String {
+        self.address.ip().to_string()
+    }
+
+    #[getter]
+    fn get_port(&self) -> u16 {
+        self.address.port()
+    }
+
+    /// `(host, port)` tuple, as accepted by `socket.create_connection`.
+    #[getter]
+    fn get_address(&self) -> (String, u16) {
+        (self.get_host(), self.get_port())
+    }
+
+    /// Ids of the connected clients, sorted.
+    #[getter]
+    fn get_clients(&self) -> Vec<String> {
+        let state = self.shared.state.lock().unwrap();
+        let mut clients: Vec<String> = state.client_ids.keys().cloned().collect();
+        clients.sort_unstable();
+        clients
+    }
+
+    /// Topics that have a retained message.
+    #[getter]
+    fn get_retained(&self) -> Vec<String> {
+        self.shared.state.lock().unwrap().retained.get_topics()
+    }
+}
+
+impl Broker {
+    fn shutdown(&mut self) {
+        let Some(acceptor) = self.acceptor.take() else {
+            return;
+        };
+        self.shared.closed.store(true, Ordering::SeqCst);
+        // Wakes up the acceptor blocked in `accept`.
+        let _ = TcpStream::connect(self.address);
+        let _ = acceptor.join();
+        let connections = std::mem::take(&mut *self.shared.connections.lock().unwrap());
+        for (stream, connection) in connections {
+            let _ = stream.shutdown(Shutdown::Both);
+            let _ = connection.join();
+        }
+    }
+}
+
+impl Drop for Broker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn accept(shared: Arc<Shared>, listener: TcpListener) {
+    for stream in listener.incoming() {
+        if shared.closed.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(clone) = stream.try_clone() else {
+            continue;
+        };
+        let connection = {
+            let shared = shared.clone();
+            thread::spawn(move || serve(&shared, stream))
+        };
+        let mut connections = shared.connections.lock().unwrap();
+        connections.retain(|(_, connection)| !connection.is_finished());
+        connections.push((clone, connection));
+    }
+}
+
+/// Serves a connection until it is closed.
+fn serve(shared: &Shared, mut stream: TcpStream) {
+    let (Ok(writer_stream), Ok(client_stream)) = (stream.try_clone(), stream.try_clone()) else {
+        return;
+    };
+    let (sender, receiver) = mpsc::channel();
+    let writer = thread::spawn(move || write(writer_stream, receiver));
+    let connection = {
+        let mut state = shared.state.lock().unwrap();
+        state.connections += 1;
+        let connection = state.connections;
+        let client = Client {
+            client_id: None,
+            stream: client_stream,
+            session: ServerSession::new(
+                shared.max_size,
+                None,
+                QoSArg(::mqttbytes::QoS::ExactlyOnce),
+            ),
+            subscriptions: HashMap::new(),
+            last_will: None,
+            writer: sender,
+        };
+        state.clients.insert(connection, client);
+        connection
+    };
+
+    let mut buffer = [0; 4096];
+    loop {
+        let n = match stream.read(&mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        if !shared
+            .state
+            .lock()
+            .unwrap()
+            .receive(connection, &buffer[..n])
+        {
+            break;
+        }
+    }
+
+    let mut state = shared.state.lock().unwrap();
+    if let Some(client) = state.clients.remove(&connection) {
+        if let Some(client_id) = client.client_id {
+            if state.client_ids.get(&client_id) == Some(&connection) {
+                state.client_ids.remove(&client_id);
+            }
+        }
+        if let Some(will) = client.last_will {
+            if !shared.closed.load(Ordering::SeqCst) {
+                let mut publish = ::mqttbytes::v4::Publish::new(will.topic, will.qos, Vec::new());
+                publish.payload = will.message;
+                publish.retain = will.retain;
+                state.route(publish);
+            }
+        }
+    }
+    drop(state);
+    // The writer thread ends once it wrote the bytes queued before the client
+    // and its queue were dropped.
+    let _ = writer.join();
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+/// Writes the bytes queued for a connection. A failed write shuts the stream
+/// down, which ends the reads of the connection.
+fn write(mut stream: TcpStream, receiver: Receiver<BytesMut>) {
+    for data in receiver {
+        if stream.write_all(&data).is_err() {
+            let _ = stream.shutdown(Shutdown::Both);
+            return;
+        }
+    }
+}
+
+pub fn testing(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<Broker>()?;
+    Ok(())
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+use pyo3::{PyTraverseError, PyVisit};
+
+#[derive(Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    entry: Option<(String, PyObject)>,
+}
+
+impl Node {
+    fn is_empty(&self) -> bool {
+        self.children.is_empty() && self.entry.is_none()
+    }
+
+    fn find(&self, filter: &str) -> Option<&Node> {
+        filter
+            .split('/')
+            .try_fold(self, |node, level| node.children.get(level))
+    }
+
+    /// Removes the entry at `levels` and prunes the nodes left empty.
+    fn remove(&mut self, levels: &[&str]) -> Option<(String, PyObject)> {
+        match levels.split_first() {
+            None => self.entry.take(),
+            Some((level, rest)) => {
+                let child = self.children.get_mut(*level)?;
+                let entry = child.remove(rest);
+                if child.is_empty() {
+                    self.children.remove(*level);
+                }
+                entry
+            }
+        }
+    }
+
+    fn collect<'a>(&'a self, levels: &[&str], first: bool, out: &mut Vec<&'a (String, PyObject)>) {
+        // Wildcards at the first level don't match topics starting with '$'.
+        let wildcards = !(first && levels.first().is_some_and(|level| level.starts_with('$')));
+        if wildcards {
+            if let Some(entry) = self.children.get("#").and_then(|node| node.entry.as_ref()) {
+                out.push(entry);
+            }
+        }
+        match levels.split_first() {
+            None => out.extend(self.entry.as_ref()),
+            Some((level, rest)) => {
+                if let Some(child) = self.children.get(*level) {
+                    child.collect(rest, false, out);
+                }
+                if let Some(child) = self.children.get("+").filter(|_| wildcards) {
+                    child.collect(rest, false, out);
+                }
+            }
+        }
+    }
+
+    fn entries<'a>(&'a self, out: &mut Vec<&'a (String, PyObject)>) {
+        out.extend(self.entry.as_ref());
+        for child in self.children.values() {
+            child.entries(out);
+        }
+    }
+}
+
+/// Tells whether `topic` matches `filter`, following the same rules as
+/// `SubscriptionTree`: wildcards at the first level of a filter don't match
+/// topics starting with '$', while literal levels do.
+pub(crate) fn matches(topic: &str, filter: &str) -> bool {
+    let mut levels = topic.split('/');
+    for (i, level) in filter.split('/').enumerate() {
+        let wildcard = level == "+" || level == "#";
+        if i == 0 && wildcard && topic.starts_with('$') {
+            return false;
+        }
+        if level == "#" {
+            return true;
+        }
+        match levels.next() {
+            Some(topic_level) if level == "+" || level == topic_level => {}
+            _ => return false,
+        }
+    }
+    levels.next().is_none()
+}
+
+/// Tells whether `filter` is valid. Upstream only checks the placement of '#',
+/// so '+' is also required to occupy a whole level.
+fn valid_filter(filter: &str) -> bool {
+    ::mqttbytes::valid_filter(filter)
+        && filter
+            .split('/')
+            .all(|level| level == "+" || !level.contains('+'))
+}
+
+/// Topic filters mapped to arbitrary values, matched against topics in one call.
+///
+/// Filters are split on '/' into a trie so that matching a topic only visits
+/// the branches that can match, including the '+' and '#' wildcards. As
+/// required by the MQTT specification, filters starting with a wildcard don't
+/// match topics starting with '$'.
+#[pyclass(module = "mqttbytes")]
+#[derive(Default)]
+pub struct SubscriptionTree {
+    root: Node,
+    len: usize,
+}
+
+#[pymethods]
+impl SubscriptionTree {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `filter` to `value` and returns the value it replaced, if any.
+    /// Raises `ValueError` if `filter` is not a valid topic filter.
+    fn insert(&mut self, filter: &str, value: PyObject) -> PyResult<Option<PyObject>> {
+        if !valid_filter(filter) {
+            return Err(PyValueError::new_err(format!("invalid filter: {filter:?}")));
+        }
+        let node = filter.split('/').fold(&mut self.root, |node, level| {
+            node.children.entry(level.to_owned()).or_default()
+        });
+        let previous = node.entry.replace((filter.to_owned(), value));
+        if previous.is_none() {
+            self.len += 1;
+        }
+        Ok(previous.map(|(_, value)| value))
+    }
+
+    /// Removes `filter` and returns its value.
+    fn remove(&mut self, filter: &str) -> PyResult<PyObject> {
+        let levels: Vec<&str> = filter.split('/').collect();
+        match self.root.remove(&levels) {
+            Some((_, value)) => {
+                self.len -= 1;
+                Ok(value)
+            }
+            None => Err(PyKeyError::new_err(filter.to_owned())),
+        }
+    }
+
+    /// Returns the value mapped to `filter`, if any.
+    fn get(&self, _py: Python, filter: &str) -> Option<PyObject> {
+        self.root
+            .find(filter)
+            .and_then(|node| node.entry.as_ref())
+            .map(|(_, value)| value.clone_ref(_py))
+    }
+
+    /// Returns the `(filter, value)` entries whose filter matches `topic`.
+    fn matches(&self, _py: Python, topic: &str) -> Vec<(String, PyObject)> {
+        let levels: Vec<&str> = topic.split('/').collect();
+        let mut entries = Vec::new();
+        self.root.collect(&levels, true, &mut entries);
+        entries
+            .into_iter()
+            .map(|(filter, value)| (filter.clone(), value.clone_ref(_py)))
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.len
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SubscriptionTree(entries={})", self.len)
+    }
+
+    fn __contains__(&self, filter: &str) -> bool {
+        self.root
+            .find(filter)
+            .is_some_and(|node| node.entry.is_some())
+    }
+
+    /// Iterates over the `(filter, value)` entries.
+    fn __iter__(&self, _py: Python) -> PyResult<PyObject> {
+        let mut entries = Vec::new();
+        self.root.entries(&mut entries);
+        let entries: Vec<(String, PyObject)> = entries
+            .into_iter()
+            .map(|(filter, value)| (filter.clone(), value.clone_ref(_py)))
+            .collect();
+        Ok(PyList::new(_py, entries).call_method0("__iter__")?.into())
+    }
+
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        let mut entries = Vec::new();
+        self.root.entries(&mut entries);
+        for (_, value) in entries {
+            visit.call(value)?;
+        }
+        Ok(())
+    }
+
+    fn __clear__(&mut self) {
+        self.root = Node::default();
+        self.len = 0;
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+
+use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use super::inflight::{unexpected, Inflight};
+use super::{packet_into_py, Connect, Publish, Subscribe, Unsubscribe};
+use crate::repr::repr_str;
+use crate::{buffer_as_slice, ProtocolError, WrapperMqttBytesError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Disconnected,
+    Connecting,
+    Connected,
+}
+
+/// Client side of an MQTT 3.1.1 connection, without any I/O.
+///
+/// Bytes received from the broker are passed to `receive_data`, which returns
+/// the events the application cares about: `ConnAck`, incoming `Publish`
+/// messages (delivered once, even for QoS2), `PubAck` and `PubComp` when an
+/// outgoing publish completes, `SubAck`, `UnsubAck` and `PingResp`.
+/// Bytes to write to the broker are collected with `data_to_send`.
+///
+/// When a packet can't be decoded or breaks the protocol, the events of the
+/// packets before it are returned and the error is raised by the next call.
+#[pyclass(module = "mqttbytes.v4")]
+pub struct ClientSession {
+    connect: ::mqttbytes::v4::Connect,
+    max_size: usize,
+    state: State,
+    incoming: BytesMut,
+    outgoing: BytesMut,
+    inflight: Inflight,
+    pending_subscribe: HashSet<u16>,
+    pending_unsubscribe: HashSet<u16>,
+    /// Error raised by the next `receive_data`, after the events preceding it
+    /// were returned.
+    error: Option<PyErr>,
+}
+
+#[pymethods]
+impl ClientSession {
+    #[new]
+    fn new(connect: &Connect, max_size: usize) -> Self {
+        Self {
+            connect: connect.0.clone(),
+            max_size,
+            state: State::Disconnected,
+            incoming: BytesMut::new(),
+            outgoing: BytesMut::new(),
+            inflight: Inflight::default(),
+            pending_subscribe: HashSet::new(),
+            pending_unsubscribe: HashSet::new(),
+            error: None,
+        }
+    }
+
+    /// Queues the `Connect` packet, starting a new connection.
+    ///
+    /// With `clean_session` unset, unacknowledged publishes are queued again
+    /// with `dup` set, as are pending `PubRel` packets.
+    fn connect(&mut self) -> Result<(), WrapperMqttBytesError> {
+        self.incoming.clear();
+        self.outgoing.clear();
+        // Requests of the previous connection are never answered.
+        for pkid in self
+            .pending_subscribe
+            .drain()
+            .chain(self.pending_unsubscribe.drain())
+        {
+            self.inflight.release(pkid);
+        }
+        self.error = None;
+        self.connect.write(&mut self.outgoing)?;
+        if self.connect.clean_session {
+            self.inflight.clear();
+        } else {
+            self.inflight.resend(&mut self.outgoing)?;
+        }
+        self.state = State::Connecting;
+        Ok(())
+    }
+
+    /// Queues a `Disconnect` packet and closes the session.
+    fn disconnect(&mut self) -> PyResult<()> {
+        self.ensure_open()?;
+        ::mqttbytes::v4::Disconnect
+            .write(&mut self.outgoing)
+            .map_err(WrapperMqttBytesError::from)?;
+        self.state = State::Disconnected;
+        Ok(())
+    }
+
+    /// Queues a publish and returns the packet id allocated to it, or 0 for QoS0.
+    fn publish(&mut self, publish: &Publish) -> PyResult<u16> {
+        self.ensure_open()?;
+        let mut publish = publish.0.clone();
+        publish.pkid = match publish.qos {
+            ::mqttbytes::QoS::AtMostOnce => 0,
+            _ => self.inflight.next_pkid()?,
+        };
+        let pkid = publish.pkid;
+        self.inflight.publish(publish, &mut self.outgoing)?;
+        Ok(pkid)
+    }
+
+    /// Queues a subscribe and returns the packet id allocated to it.
+    fn subscribe(&mut self, subscribe: &Subscribe) -> PyResult<u16> {
+        self.ensure_open()?;
+        let mut subscribe = subscribe.0.clone();
+        subscribe.pkid = self.inflight.next_pkid()?;
+        if let Err(err) = subscribe.write(&mut self.outgoing) {
+            self.inflight.release(subscribe.pkid);
+            return Err(WrapperMqttBytesError::from(err).into());
+        }
+        self.pending_subscribe.insert(subscribe.pkid);
+        Ok(subscribe.pkid)
+    }
+
+    /// Queues an unsubscribe and returns the packet id allocated to it.
+    fn unsubscribe(&mut self, unsubscribe: &Unsubscribe) -> PyResult<u16> {
+        self.ensure_open()?;
+        let mut unsubscribe = unsubscribe.0.clone();
+        unsubscribe.pkid = self.inflight.next_pkid()?;
+        if let Err(err) = unsubscribe.write(&mut self.outgoing) {
+            self.inflight.release(unsubscribe.pkid);
+            return Err(WrapperMqttBytesError::from(err).into());
+        }
+        self.pending_unsubscribe.insert(unsubscribe.pkid);
+        Ok(unsubscribe.pkid)
+    }
+
+    /// Queues a `PingReq` packet.
+    fn ping(&mut self) -> PyResult<()> {
+        self.ensure_open()?;
+        ::mqttbytes::v4::PingReq
+            .write(&mut self.outgoing)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(())
+    }
+
+    /// Queues unacknowledged publishes again with `dup` set, and pending
+    /// `PubRel` packets, when their acknowledgements are overdue.
+    fn resend(&mut self) -> PyResult<()> {
+        if self.state != State::Connected {
+            return Err(ProtocolError::new_err("session is not connected"));
+        }
+        self.inflight.resend(&mut self.outgoing)?;
+        Ok(())
+    }
+
+    /// Feeds bytes received from the broker and returns the resulting events.
+    /// `data` can be any object supporting the buffer protocol.
+    fn receive_data(&mut self, _py: Python, data: PyBuffer<u8>) -> PyResult<Vec<PyObject>> {
+        self.incoming
+            .extend_from_slice(buffer_as_slice(_py, &data)?);
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        let mut events = Vec::new();
+        loop {
+            let event = match ::mqttbytes::v4::read(&mut self.incoming, self.max_size) {
+                Ok(packet) => self.handle(packet),
+                Err(::mqttbytes::Error::InsufficientBytes(_)) => break,
+                Err(err) => Err(WrapperMqttBytesError::from(err).into()),
+            };
+            match event {
+                Ok(Some(event)) => events.push(packet_into_py(_py, event)),
+                Ok(None) => {}
+                Err(err) if events.is_empty() => return Err(err),
+                Err(err) => {
+                    self.error = Some(err);
+                    break;
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Returns the bytes queued for the broker and empties the queue.
+    fn data_to_send(&mut self, _py: Python) -> Py<PyBytes> {
+        let data = self.outgoing.split();
+        PyBytes::new(_py, &data).into()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ClientSession(client_id={}, state={:?}, inflight={})",
+            repr_str(&self.connect.client_id),
+            self.state,
+            self.inflight.outgoing().len()
+        )
+    }
+
+    /// Whether the broker accepted the connection.
+    #[getter]
+    fn get_connected(&self) -> bool {
+        self.state == State::Connected
+    }
+
+    /// Packet ids of outgoing publishes not yet fully acknowledged.
+    #[getter]
+    fn get_inflight(&self) -> Vec<u16> {
+        self.inflight.outgoing()
+    }
+}
+
+impl ClientSession {
+    fn ensure_open(&self) -> PyResult<()> {
+        match self.state {
+            State::Disconnected => Err(ProtocolError::new_err("session is not connected")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Advances the protocol state for an incoming packet and returns the
+    /// packet to surface as an event, if any.
+    fn handle(
+        &mut self,
+        packet: ::mqttbytes::v4::Packet,
+    ) -> PyResult<Option<::mqttbytes::v4::Packet>> {
+        use ::mqttbytes::v4::Packet;
+
+        match (self.state, packet) {
+            (State::Connecting, Packet::ConnAck(connack)) => {
+                self.state = match connack.code {
+                    ::mqttbytes::v4::ConnectReturnCode::Success => State::Connected,
+                    _ => State::Disconnected,
+                };
+                Ok(Some(Packet::ConnAck(connack)))
+            }
+            (State::Connected, Packet::Publish(publish)) => {
+                let deliver = self
+                    .inflight
+                    .incoming_publish(&publish, &mut self.outgoing)?;
+                Ok(deliver.then_some(Packet::Publish(publish)))
+            }
+            (State::Connected, Packet::PubAck(puback)) => {
+                self.inflight.puback(puback.pkid)?;
+                Ok(Some(Packet::PubAck(puback)))
+            }
+            (State::Connected, Packet::PubRec(pubrec)) => {
+                self.inflight.pubrec(pubrec.pkid, &mut self.outgoing)?;
+                Ok(None)
+            }
+            (State::Connected, Packet::PubRel(pubrel)) => {
+                self.inflight.pubrel(pubrel.pkid, &mut self.outgoing)?;
+                Ok(None)
+            }
+            (State::Connected, Packet::PubComp(pubcomp)) => {
+                self.inflight.pubcomp(pubcomp.pkid)?;
+                Ok(Some(Packet::PubComp(pubcomp)))
+            }
+            (State::Connected, Packet::SubAck(suback)) => {
+                if !self.pending_subscribe.remove(&suback.pkid) {
+                    return Err(unexpected("SubAck", suback.pkid));
+                }
+                self.inflight.release(suback.pkid);
+                Ok(Some(Packet::SubAck(suback)))
+            }
+            (State::Connected, Packet::UnsubAck(unsuback)) => {
+                if !self.pending_unsubscribe.remove(&unsuback.pkid) {
+                    return Err(unexpected("UnsubAck", unsuback.pkid));
+                }
+                self.inflight.release(unsuback.pkid);
+                Ok(Some(Packet::UnsubAck(unsuback)))
+            }
+            (State::Connected, Packet::PingResp) => Ok(Some(Packet::PingResp)),
+            (State::Disconnected, _) => Err(ProtocolError::new_err("session is not connected")),
+            (State::Connecting, _) => Err(ProtocolError::new_err(
+                "expected ConnAck as the first packet from the broker",
+            )),
+            (State::Connected, _) => Err(ProtocolError::new_err(
+                "packet is not allowed from the broker",
+            )),
+        }
+    }
+}
@@ -1,28 +1,48 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict};
 
-use crate::{FixedHeader, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{check_type, optional, packet_dict, required};
+use crate::repr::repr_bool;
+use crate::{richcmp, FixedHeader, WrapperMqttBytesError};
 
 /// Acknowledgement to connect packet.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct ConnAck(::mqttbytes::v4::ConnAck);
+pub struct ConnAck(pub ::mqttbytes::v4::ConnAck);
 
 #[pymethods]
 impl ConnAck {
     #[new]
+    #[args(session_present = "false")]
     fn new(code: ConnectReturnCode, session_present: bool) -> Self {
         ::mqttbytes::v4::ConnAck::new(code.into(), session_present).into()
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::ConnAck::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::ConnAck::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::connack(&packet)?;
+        }
+        Ok(packet.into())
+    }
+
+    /// Checks that `session_present` is only set with `Success`.
+    fn validate(&self) -> PyResult<()> {
+        validate::connack(&self.0)
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -30,6 +50,63 @@ impl ConnAck {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ConnAck(code=ConnectReturnCode.{:?}, session_present={})",
+            self.0.code,
+            repr_bool(self.0.session_present),
+        )
+    }
+
+    /// Returns the packet as a JSON-safe dict, with the code as its name.
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = packet_dict(_py, "ConnAck")?;
+        dict.set_item("code", format!("{:?}", self.0.code))?;
+        dict.set_item("session_present", self.0.session_present)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "ConnAck")?;
+        let code = match required::<&str>(data, "code")? {
+            "Success" => ConnectReturnCode::Success,
+            "RefusedProtocolVersion" => ConnectReturnCode::RefusedProtocolVersion,
+            "BadClientId" => ConnectReturnCode::BadClientId,
+            "ServiceUnavailable" => ConnectReturnCode::ServiceUnavailable,
+            "BadUserNamePassword" => ConnectReturnCode::BadUserNamePassword,
+            "NotAuthorized" => ConnectReturnCode::NotAuthorized,
+            code => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown connect return code {code:?}"
+                )))
+            }
+        };
+        let session_present = optional(data, "session_present")?.unwrap_or_default();
+        Ok(::mqttbytes::v4::ConnAck::new(code.into(), session_present).into())
+    }
+
     #[getter]
     fn get_session_present(&self) -> bool {
         self.0.session_present
@@ -70,6 +147,13 @@ pub enum ConnectReturnCode {
     NotAuthorized,
 }
 
+#[pymethods]
+impl ConnectReturnCode {
+    fn __hash__(&self) -> u64 {
+        self.clone() as u64
+    }
+}
+
 impl From<::mqttbytes::v4::ConnectReturnCode> for ConnectReturnCode {
     fn from(code: ::mqttbytes::v4::ConnectReturnCode) -> Self {
         match code {
@@ -1,18 +1,66 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict};
 
-use crate::{FixedHeader, Protocol, QoS, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{
+    check_type, optional, packet_dict, required, required_bytes, required_qos, Encoding,
+};
+use crate::payload::{buffer_to_bytes, memoryview};
+use crate::repr::{repr_bool, repr_bytes, repr_option, repr_str};
+use crate::{richcmp, FixedHeader, Protocol, QoS, QoSArg, WrapperMqttBytesError};
 
 /// Connection packet initiated by the client.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct Connect(::mqttbytes::v4::Connect);
+pub struct Connect(pub ::mqttbytes::v4::Connect);
 
 #[pymethods]
 impl Connect {
     #[new]
-    fn new(id: String) -> Self {
-        ::mqttbytes::v4::Connect::new(id).into()
+    /// `id` is a deprecated alias of `client_id`.
+    #[args(
+        client_id = "None",
+        keep_alive = "10",
+        clean_session = "true",
+        last_will = "None",
+        login = "None",
+        protocol = "Protocol::V4",
+        "*",
+        id = "None"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        _py: Python,
+        client_id: Option<String>,
+        keep_alive: u16,
+        clean_session: bool,
+        last_will: Option<LastWill>,
+        login: Option<Login>,
+        protocol: Protocol,
+        id: Option<String>,
+    ) -> PyResult<Self> {
+        let client_id = match (client_id, id) {
+            (Some(client_id), None) => client_id,
+            (None, Some(id)) => {
+                let category = _py.import("builtins")?.getattr("DeprecationWarning")?;
+                PyErr::warn(_py, category, "`id` is deprecated, use `client_id`", 1)?;
+                id
+            }
+            (Some(_), Some(_)) => {
+                return Err(PyTypeError::new_err("got both `client_id` and `id`"))
+            }
+            (None, None) => return Err(PyTypeError::new_err("missing argument `client_id`")),
+        };
+        let mut connect = ::mqttbytes::v4::Connect::new(client_id);
+        connect.keep_alive = keep_alive;
+        connect.clean_session = clean_session;
+        connect.last_will = last_will.map(|last_will| last_will.0);
+        connect.login = login.map(|login| login.0);
+        connect.protocol = protocol.into();
+        Ok(connect.into())
     }
 
     fn __len__(&self) -> usize {
@@ -20,13 +68,28 @@ impl Connect {
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::Connect::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::Connect::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::connect(&packet)?;
+        }
+        Ok(packet.into())
+    }
+
+    /// Checks the client id, which can only be empty with `clean_session`, the
+    /// topic and message of the last will, and the login, whose password needs
+    /// a username.
+    fn validate(&self) -> PyResult<()> {
+        validate::connect(&self.0)
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -34,6 +97,95 @@ impl Connect {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py, "base64")?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Connect(client_id={}, keep_alive={}, clean_session={}, protocol=Protocol.{:?}, last_will={}, login={})",
+            repr_str(&self.0.client_id),
+            self.0.keep_alive,
+            repr_bool(self.0.clean_session),
+            self.0.protocol,
+            repr_option(self.get_last_will(), |last_will| last_will.__repr__()),
+            repr_option(self.get_login(), |login| login.__repr__()),
+        )
+    }
+
+    /// Returns the packet as a JSON-safe dict, with the last will message
+    /// encoded as `encoding` ('base64' or 'hex').
+    #[args(encoding = "\"base64\"")]
+    fn to_dict<'py>(&self, _py: Python<'py>, encoding: &str) -> PyResult<&'py PyDict> {
+        // Checked even when there is no last will to encode.
+        Encoding::parse(encoding)?;
+        let dict = packet_dict(_py, "Connect")?;
+        dict.set_item("client_id", &self.0.client_id)?;
+        dict.set_item("protocol", format!("{:?}", self.0.protocol))?;
+        dict.set_item("keep_alive", self.0.keep_alive)?;
+        dict.set_item("clean_session", self.0.clean_session)?;
+        let last_will = match self.get_last_will() {
+            Some(last_will) => Some(last_will.to_dict(_py, encoding)?),
+            None => None,
+        };
+        dict.set_item("last_will", last_will)?;
+        let login = match self.get_login() {
+            Some(login) => Some(login.to_dict(_py)?),
+            None => None,
+        };
+        dict.set_item("login", login)?;
+        Ok(dict)
+    }
+
+    /// Builds the packet from a dict returned by `to_dict`. Only `client_id`
+    /// is required.
+    #[staticmethod]
+    #[args(encoding = "\"base64\"")]
+    pub(crate) fn from_dict(data: &PyDict, encoding: &str) -> PyResult<Self> {
+        check_type(data, "Connect")?;
+        Encoding::parse(encoding)?;
+        let mut connect = ::mqttbytes::v4::Connect::new(required::<String>(data, "client_id")?);
+        connect.protocol = match optional::<&str>(data, "protocol")? {
+            None | Some("V4") => ::mqttbytes::Protocol::V4,
+            Some("V5") => ::mqttbytes::Protocol::V5,
+            Some(protocol) => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown protocol {protocol:?}"
+                )))
+            }
+        };
+        if let Some(keep_alive) = optional(data, "keep_alive")? {
+            connect.keep_alive = keep_alive;
+        }
+        if let Some(clean_session) = optional(data, "clean_session")? {
+            connect.clean_session = clean_session;
+        }
+        if let Some(last_will) = optional(data, "last_will")? {
+            connect.last_will = Some(LastWill::from_dict(last_will, encoding)?.0);
+        }
+        if let Some(login) = optional(data, "login")? {
+            connect.login = Some(Login::from_dict(login)?.0);
+        }
+        Ok(connect.into())
+    }
+
     #[getter]
     fn get_protocol(&self) -> Protocol {
         self.0.protocol.into()
@@ -109,8 +261,55 @@ pub struct LastWill(::mqttbytes::v4::LastWill);
 #[pymethods]
 impl LastWill {
     #[new]
-    fn new(topic: String, payload: Vec<u8>, qos: QoS, retain: bool) -> Self {
-        ::mqttbytes::v4::LastWill::new(topic, payload, qos.into(), retain).into()
+    #[args(retain = "false")]
+    fn new(
+        _py: Python,
+        topic: String,
+        message: PyBuffer<u8>,
+        qos: QoSArg,
+        retain: bool,
+    ) -> PyResult<Self> {
+        let mut last_will = ::mqttbytes::v4::LastWill::new(topic, Vec::new(), qos.into(), retain);
+        last_will.message = buffer_to_bytes(_py, &message)?;
+        Ok(last_will.into())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LastWill(topic={}, message={}, qos=QoS.{:?}, retain={})",
+            repr_str(&self.0.topic),
+            repr_bytes(&self.0.message),
+            self.0.qos,
+            repr_bool(self.0.retain),
+        )
+    }
+
+    #[args(encoding = "\"base64\"")]
+    fn to_dict<'py>(&self, _py: Python<'py>, encoding: &str) -> PyResult<&'py PyDict> {
+        let encoding = Encoding::parse(encoding)?;
+        let dict = PyDict::new(_py);
+        dict.set_item("topic", &self.0.topic)?;
+        dict.set_item("message", encoding.encode(&self.0.message))?;
+        dict.set_item("qos", self.0.qos as u8)?;
+        dict.set_item("retain", self.0.retain)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    #[args(encoding = "\"base64\"")]
+    pub(crate) fn from_dict(data: &PyDict, encoding: &str) -> PyResult<Self> {
+        let encoding = Encoding::parse(encoding)?;
+        Ok(::mqttbytes::v4::LastWill::new(
+            required::<String>(data, "topic")?,
+            required_bytes(data, "message", encoding)?,
+            required_qos(data, "qos")?,
+            optional(data, "retain")?.unwrap_or_default(),
+        )
+        .into())
     }
 
     #[getter]
@@ -123,14 +322,17 @@ impl LastWill {
         self.0.topic = topic;
     }
 
+    /// Read-only `memoryview` sharing the memory of the message.
     #[getter]
-    fn get_message(&self) -> Vec<u8> {
-        self.0.message.to_vec()
+    fn get_message(&self, _py: Python) -> PyResult<PyObject> {
+        memoryview(_py, self.0.message.clone())
     }
 
+    /// Accepts any object supporting the buffer protocol.
     #[setter]
-    fn set_message(&mut self, message: Vec<u8>) {
-        self.0.message = message.into();
+    fn set_message(&mut self, _py: Python, message: PyBuffer<u8>) -> PyResult<()> {
+        self.0.message = buffer_to_bytes(_py, &message)?;
+        Ok(())
     }
 
     #[getter]
@@ -139,7 +341,7 @@ impl LastWill {
     }
 
     #[setter]
-    fn set_qos(&mut self, qos: QoS) {
+    fn set_qos(&mut self, qos: QoSArg) {
         self.0.qos = qos.into();
     }
 
@@ -162,13 +364,43 @@ impl From<::mqttbytes::v4::LastWill> for LastWill {
 
 #[pyclass(module = "mqttbytes.v4")]
 #[derive(Clone)]
-pub struct Login(::mqttbytes::v4::Login);
+pub struct Login(pub ::mqttbytes::v4::Login);
 
 #[pymethods]
 impl Login {
+    /// The password is only sent when it isn't empty.
     #[new]
-    fn new(u: String, p: String) -> Self {
-        ::mqttbytes::v4::Login::new(u, p).into()
+    #[args(password = "String::new()")]
+    fn new(username: String, password: String) -> Self {
+        ::mqttbytes::v4::Login::new(username, password).into()
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    /// The password is masked so that reprs can be logged safely.
+    fn __repr__(&self) -> String {
+        format!(
+            "Login(username={}, password='***')",
+            repr_str(&self.0.username)
+        )
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(_py);
+        dict.set_item("username", &self.0.username)?;
+        dict.set_item("password", &self.0.password)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        Ok(::mqttbytes::v4::Login::new(
+            required::<String>(data, "username")?,
+            required::<String>(data, "password")?,
+        )
+        .into())
     }
 
     fn validate(&self, username: String, password: String) -> bool {
@@ -0,0 +1,84 @@
+use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+
+use super::{packet_into_py, validate};
+use crate::repr::repr_bool;
+use crate::{buffer_as_slice, WrapperMqttBytesError};
+
+/// Incremental decoder for a stream of MQTT packets.
+///
+/// Bytes are appended with `feed` and complete packets are pulled out by
+/// iterating over the decoder. Bytes of a partially received packet are kept
+/// until the next `feed` completes it.
+///
+/// With `strict` set, packets are also validated as with their `validate()`.
+/// An invalid packet is dropped and the error raised, and iterating again
+/// goes on with the next packet.
+#[pyclass(module = "mqttbytes.v4")]
+pub struct StreamDecoder {
+    buffer: BytesMut,
+    max_size: usize,
+    strict: bool,
+}
+
+#[pymethods]
+impl StreamDecoder {
+    #[new]
+    #[args(strict = "false")]
+    fn new(max_size: usize, strict: bool) -> Self {
+        Self {
+            buffer: BytesMut::new(),
+            max_size,
+            strict,
+        }
+    }
+
+    /// Appends received bytes to the internal buffer. `data` can be any object
+    /// supporting the buffer protocol, such as a `bytearray` or a `memoryview`.
+    fn feed(&mut self, _py: Python, data: PyBuffer<u8>) -> PyResult<()> {
+        self.buffer.extend_from_slice(buffer_as_slice(_py, &data)?);
+        Ok(())
+    }
+
+    /// Returns the number of buffered bytes not yet decoded.
+    fn __len__(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, _py: Python) -> PyResult<Option<PyObject>> {
+        match ::mqttbytes::v4::read(&mut self.buffer, self.max_size) {
+            Ok(packet) => {
+                if self.strict {
+                    validate::packet(&packet)?;
+                }
+                Ok(Some(packet_into_py(_py, packet)))
+            }
+            Err(::mqttbytes::Error::InsufficientBytes(_)) => Ok(None),
+            Err(err) => Err(WrapperMqttBytesError::from(err).into()),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "StreamDecoder(max_size={}, strict={}, buffered={})",
+            self.max_size,
+            repr_bool(self.strict),
+            self.buffer.len()
+        )
+    }
+
+    #[getter]
+    fn get_max_size(&self) -> usize {
+        self.max_size
+    }
+
+    #[getter]
+    fn get_strict(&self) -> bool {
+        self.strict
+    }
+}
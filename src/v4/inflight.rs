@@ -0,0 +1,151 @@
+use std::collections::{HashMap, HashSet};
+
+use bytes::BytesMut;
+use pyo3::prelude::*;
+
+use super::packet_id::PacketIdAllocator;
+use crate::{ProtocolError, WrapperMqttBytesError};
+
+/// QoS1 and QoS2 delivery state shared by the client and server sessions.
+#[derive(Default)]
+pub(crate) struct Inflight {
+    /// Ids of the outgoing publishes in flight, and of any other packet the
+    /// session allocated one for.
+    pkids: PacketIdAllocator,
+    /// QoS1 and QoS2 publishes waiting for PubAck or PubRec.
+    outgoing_publish: HashMap<u16, ::mqttbytes::v4::Publish>,
+    /// QoS2 publishes released with PubRel and waiting for PubComp.
+    outgoing_rel: HashSet<u16>,
+    /// QoS2 publishes received and waiting for PubRel.
+    incoming_pub: HashSet<u16>,
+}
+
+impl Inflight {
+    pub(crate) fn clear(&mut self) {
+        self.pkids.clear();
+        self.outgoing_publish.clear();
+        self.outgoing_rel.clear();
+        self.incoming_pub.clear();
+    }
+
+    /// Queues unacknowledged publishes again with `dup` set, and pending PubRels.
+    pub(crate) fn resend(&mut self, outgoing: &mut BytesMut) -> Result<(), WrapperMqttBytesError> {
+        for publish in self.outgoing_publish.values_mut() {
+            publish.dup = true;
+            publish.write(outgoing)?;
+        }
+        for pkid in self.outgoing_rel.iter() {
+            ::mqttbytes::v4::PubRel::new(*pkid).write(outgoing)?;
+        }
+        Ok(())
+    }
+
+    /// Packet ids of outgoing publishes not yet fully acknowledged.
+    pub(crate) fn outgoing(&self) -> Vec<u16> {
+        let mut inflight: Vec<u16> = self
+            .outgoing_publish
+            .keys()
+            .chain(self.outgoing_rel.iter())
+            .copied()
+            .collect();
+        inflight.sort_unstable();
+        inflight
+    }
+
+    /// Allocates the next free non-zero packet id, in flight until released.
+    pub(crate) fn next_pkid(&mut self) -> PyResult<u16> {
+        self.pkids.allocate()
+    }
+
+    /// Releases a packet id whose flow completed outside of this struct.
+    pub(crate) fn release(&mut self, pkid: u16) {
+        self.pkids.release(pkid);
+    }
+
+    /// Writes an outgoing publish, whose packet id was already allocated, and
+    /// tracks it until it is acknowledged.
+    pub(crate) fn publish(
+        &mut self,
+        publish: ::mqttbytes::v4::Publish,
+        outgoing: &mut BytesMut,
+    ) -> Result<(), WrapperMqttBytesError> {
+        if let Err(err) = publish.write(outgoing) {
+            self.pkids.release(publish.pkid);
+            return Err(err.into());
+        }
+        if publish.qos != ::mqttbytes::QoS::AtMostOnce {
+            self.outgoing_publish.insert(publish.pkid, publish);
+        }
+        Ok(())
+    }
+
+    /// Acknowledges an incoming publish and returns whether it should be
+    /// delivered. A QoS2 publish is delivered only the first time it is seen.
+    pub(crate) fn incoming_publish(
+        &mut self,
+        publish: &::mqttbytes::v4::Publish,
+        outgoing: &mut BytesMut,
+    ) -> Result<bool, WrapperMqttBytesError> {
+        match publish.qos {
+            ::mqttbytes::QoS::AtMostOnce => Ok(true),
+            ::mqttbytes::QoS::AtLeastOnce => {
+                ::mqttbytes::v4::PubAck::new(publish.pkid).write(outgoing)?;
+                Ok(true)
+            }
+            ::mqttbytes::QoS::ExactlyOnce => {
+                ::mqttbytes::v4::PubRec::new(publish.pkid).write(outgoing)?;
+                Ok(self.incoming_pub.insert(publish.pkid))
+            }
+        }
+    }
+
+    pub(crate) fn puback(&mut self, pkid: u16) -> PyResult<()> {
+        match self.outgoing_publish.get(&pkid) {
+            Some(publish) if publish.qos == ::mqttbytes::QoS::AtLeastOnce => {
+                self.outgoing_publish.remove(&pkid);
+                self.pkids.release(pkid);
+                Ok(())
+            }
+            _ => Err(unexpected("PubAck", pkid)),
+        }
+    }
+
+    pub(crate) fn pubrec(&mut self, pkid: u16, outgoing: &mut BytesMut) -> PyResult<()> {
+        match self.outgoing_publish.get(&pkid) {
+            Some(publish) if publish.qos == ::mqttbytes::QoS::ExactlyOnce => {
+                self.outgoing_publish.remove(&pkid);
+                self.outgoing_rel.insert(pkid);
+            }
+            _ if self.outgoing_rel.contains(&pkid) => {}
+            _ => return Err(unexpected("PubRec", pkid)),
+        }
+        ::mqttbytes::v4::PubRel::new(pkid)
+            .write(outgoing)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(())
+    }
+
+    pub(crate) fn pubrel(
+        &mut self,
+        pkid: u16,
+        outgoing: &mut BytesMut,
+    ) -> Result<(), WrapperMqttBytesError> {
+        self.incoming_pub.remove(&pkid);
+        ::mqttbytes::v4::PubComp::new(pkid).write(outgoing)?;
+        Ok(())
+    }
+
+    pub(crate) fn pubcomp(&mut self, pkid: u16) -> PyResult<()> {
+        match self.outgoing_rel.remove(&pkid) {
+            true => {
+                self.pkids.release(pkid);
+                Ok(())
+            }
+            false => Err(unexpected("PubComp", pkid)),
+        }
+    }
+}
+
+pub(crate) fn unexpected(packet: &str, pkid: u16) -> PyErr {
+    ProtocolError::new_err(format!("unexpected {packet} for packet id {pkid}"))
+}
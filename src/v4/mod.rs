@@ -1,12 +1,20 @@
+use std::cell::RefCell;
+
+use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
 
+use client_session::*;
 use connack::*;
 use connect::*;
+use decoder::*;
 use disconnect::*;
+use packet_id::*;
 use ping::*;
 use puback::*;
 use pubcomp::*;
-use publish::*;
 use pubrec::*;
 use pubrel::*;
 use suback::*;
@@ -14,50 +22,333 @@ use subscribe::*;
 use unsuback::*;
 use unsubscribe::*;
 
-use crate::WrapperMqttBytesError;
+pub(crate) use publish::Publish;
+pub(crate) use retained::RetainedStore;
+pub(crate) use server_session::ServerSession;
+pub(crate) use subscribe::{RetainForwardRule, SubscribeFilter};
+
+use crate::dict::required;
+use crate::{buffer_as_mut_slice, buffer_as_slice, WrapperMqttBytesError};
 
+mod client_session;
 mod connack;
 mod connect;
+mod decoder;
 mod disconnect;
+mod inflight;
+mod packet_id;
 mod ping;
 mod puback;
 mod pubcomp;
 mod publish;
 mod pubrec;
 mod pubrel;
+mod retained;
+mod server_session;
 mod suback;
 mod subscribe;
 mod unsuback;
 mod unsubscribe;
+mod validate;
 
 /// Reads a stream of bytes and extracts next MQTT packet out of it.
-#[pyfunction]
-fn read(_py: Python, bytes: Vec<u8>, max_size: usize) -> Result<PyObject, WrapperMqttBytesError> {
+///
+/// With `strict` set, the packet is also validated as with its `validate()`.
+#[pyfunction(strict = "false")]
+fn read(_py: Python, bytes: Vec<u8>, max_size: usize, strict: bool) -> PyResult<PyObject> {
     let bytes: &[u8] = &bytes;
-    ::mqttbytes::v4::read(&mut bytes.into(), max_size)
-        .map(|packet| match packet {
-            ::mqttbytes::v4::Packet::Connect(packet) => Connect::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::ConnAck(packet) => ConnAck::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::Publish(packet) => Publish::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::PubAck(packet) => PubAck::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::PubRec(packet) => PubRec::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::PubRel(packet) => PubRel::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::PubComp(packet) => PubComp::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::Subscribe(packet) => Subscribe::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::SubAck(packet) => SubAck::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::Unsubscribe(packet) => Unsubscribe::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::UnsubAck(packet) => UnsubAck::from(packet).into_py(_py),
-            ::mqttbytes::v4::Packet::PingReq => PingReq(::mqttbytes::v4::PingReq).into_py(_py),
-            ::mqttbytes::v4::Packet::PingResp => PingResp(::mqttbytes::v4::PingResp).into_py(_py),
-            ::mqttbytes::v4::Packet::Disconnect => {
-                Disconnect(::mqttbytes::v4::Disconnect).into_py(_py)
+    let packet =
+        ::mqttbytes::v4::read(&mut bytes.into(), max_size).map_err(WrapperMqttBytesError::from)?;
+    if strict {
+        validate::packet(&packet)?;
+    }
+    Ok(packet_into_py(_py, packet))
+}
+
+/// Reads the next MQTT packet from `buffer` starting at `offset`.
+///
+/// Accepts any object supporting the buffer protocol and only copies the bytes
+/// of the decoded packet. Returns the packet and the number of bytes consumed.
+/// With `strict` set, the packet is also validated as with its `validate()`.
+#[pyfunction(strict = "false")]
+fn read_from(
+    _py: Python,
+    buffer: PyBuffer<u8>,
+    offset: usize,
+    max_size: usize,
+    strict: bool,
+) -> PyResult<(PyObject, usize)> {
+    let bytes = buffer_as_slice(_py, &buffer)?
+        .get(offset..)
+        .ok_or_else(|| PyValueError::new_err("offset is past the end of the buffer"))?;
+    let fixed_header =
+        ::mqttbytes::check(bytes.iter(), max_size).map_err(WrapperMqttBytesError::from)?;
+    let consumed = fixed_header.frame_length();
+    let mut frame = BytesMut::from(&bytes[..consumed]);
+    let packet =
+        ::mqttbytes::v4::read(&mut frame, max_size).map_err(WrapperMqttBytesError::from)?;
+    if strict {
+        validate::packet(&packet)?;
+    }
+    Ok((packet_into_py(_py, packet), consumed))
+}
+
+/// Reads every complete MQTT packet from `buffer`.
+///
+/// Returns the packets and the offset of the first byte not consumed, where
+/// the next packet starts once more bytes are received. The complete packets
+/// are copied once and then decoded without further copies. An error is only
+/// raised when the first packet is invalid; packets before an invalid one are
+/// returned, and reading again from the returned offset raises the error.
+/// With `strict` set, packets are also validated as with their
+/// `validate()`, and an invalid packet is handled like one that can't be
+/// decoded.
+#[pyfunction(strict = "false")]
+fn read_all(
+    _py: Python,
+    buffer: PyBuffer<u8>,
+    max_size: usize,
+    strict: bool,
+) -> PyResult<(Vec<PyObject>, usize)> {
+    let bytes = buffer_as_slice(_py, &buffer)?;
+    let mut end = 0;
+    let mut error = None;
+    while end < bytes.len() {
+        match ::mqttbytes::check(bytes[end..].iter(), max_size) {
+            Ok(fixed_header) => end += fixed_header.frame_length(),
+            Err(::mqttbytes::Error::InsufficientBytes(_)) => break,
+            Err(err) => {
+                error = Some(err);
+                break;
+            }
+        }
+    }
+    let mut frames = BytesMut::from(&bytes[..end]);
+    // Each packet is decoded along with the offset where it ends.
+    let mut packets = Vec::new();
+    while !frames.is_empty() {
+        match ::mqttbytes::v4::read(&mut frames, max_size) {
+            Ok(packet) => packets.push((packet, end - frames.len())),
+            Err(err) => {
+                error = Some(err);
+                break;
             }
-        })
-        .map_err(Into::into)
+        }
+    }
+    let mut error = error.map(|err| PyErr::from(WrapperMqttBytesError::from(err)));
+    if strict {
+        let invalid = packets
+            .iter()
+            .enumerate()
+            .find_map(|(index, (packet, _))| Some((index, validate::packet(packet).err()?)));
+        if let Some((index, err)) = invalid {
+            packets.truncate(index);
+            error = Some(err);
+        }
+    }
+    let consumed = packets.last().map_or(0, |(_, end)| *end);
+    match error {
+        Some(err) if packets.is_empty() => Err(err),
+        _ => Ok((
+            packets
+                .into_iter()
+                .map(|(packet, _)| packet_into_py(_py, packet))
+                .collect(),
+            consumed,
+        )),
+    }
+}
+
+/// Encodes packets back to back and returns the bytes. `packets` can be any
+/// iterable of v4 packets.
+#[pyfunction]
+fn write_many(_py: Python, packets: &PyAny) -> PyResult<Py<PyBytes>> {
+    let mut buffer = BytesMut::new();
+    for packet in packets.iter()? {
+        write_packet(&packet_from_py(packet?)?, &mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+    }
+    Ok(PyBytes::new(_py, &buffer).into())
+}
+
+/// Clones the packet wrapped by a Python object of any v4 packet class.
+fn packet_from_py(packet: &PyAny) -> PyResult<::mqttbytes::v4::Packet> {
+    use ::mqttbytes::v4::Packet;
+
+    Ok(if let Ok(packet) = packet.downcast::<PyCell<Publish>>() {
+        Packet::Publish(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<PubAck>>() {
+        Packet::PubAck(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<PubRec>>() {
+        Packet::PubRec(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<PubRel>>() {
+        Packet::PubRel(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<PubComp>>() {
+        Packet::PubComp(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<Subscribe>>() {
+        Packet::Subscribe(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<SubAck>>() {
+        Packet::SubAck(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<Unsubscribe>>() {
+        Packet::Unsubscribe(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<UnsubAck>>() {
+        Packet::UnsubAck(packet.borrow().0.clone())
+    } else if packet.downcast::<PyCell<PingReq>>().is_ok() {
+        Packet::PingReq
+    } else if packet.downcast::<PyCell<PingResp>>().is_ok() {
+        Packet::PingResp
+    } else if let Ok(packet) = packet.downcast::<PyCell<Connect>>() {
+        Packet::Connect(packet.borrow().0.clone())
+    } else if let Ok(packet) = packet.downcast::<PyCell<ConnAck>>() {
+        Packet::ConnAck(packet.borrow().0.clone())
+    } else if packet.downcast::<PyCell<Disconnect>>().is_ok() {
+        Packet::Disconnect
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "expected a v4 packet, got {}",
+            packet.get_type().name()?
+        )));
+    })
+}
+
+/// Encodes a packet of any v4 type into `buffer`.
+fn write_packet(
+    packet: &::mqttbytes::v4::Packet,
+    buffer: &mut BytesMut,
+) -> Result<usize, ::mqttbytes::Error> {
+    use ::mqttbytes::v4::Packet;
+
+    match packet {
+        Packet::Connect(packet) => packet.write(buffer),
+        Packet::ConnAck(packet) => packet.write(buffer),
+        Packet::Publish(packet) => packet.write(buffer),
+        Packet::PubAck(packet) => packet.write(buffer),
+        Packet::PubRec(packet) => packet.write(buffer),
+        Packet::PubRel(packet) => packet.write(buffer),
+        Packet::PubComp(packet) => packet.write(buffer),
+        Packet::Subscribe(packet) => packet.write(buffer),
+        Packet::SubAck(packet) => packet.write(buffer),
+        Packet::Unsubscribe(packet) => packet.write(buffer),
+        Packet::UnsubAck(packet) => packet.write(buffer),
+        Packet::PingReq => ::mqttbytes::v4::PingReq.write(buffer),
+        Packet::PingResp => ::mqttbytes::v4::PingResp.write(buffer),
+        Packet::Disconnect => ::mqttbytes::v4::Disconnect.write(buffer),
+    }
+}
+
+thread_local! {
+    /// Buffer reused by `write_into`, which upstream can only encode into a `BytesMut`.
+    static SCRATCH: RefCell<BytesMut> = RefCell::new(BytesMut::new());
+}
+
+/// Encodes a packet with `write` and copies it into `buffer` at `offset`.
+/// Returns the number of bytes written.
+fn write_into(
+    _py: Python,
+    buffer: &PyBuffer<u8>,
+    offset: usize,
+    write: impl FnOnce(&mut BytesMut) -> Result<usize, ::mqttbytes::Error>,
+) -> PyResult<usize> {
+    let target = buffer_as_mut_slice(_py, buffer)?;
+    SCRATCH.with(|scratch| {
+        let mut scratch = scratch.borrow_mut();
+        scratch.clear();
+        write(&mut scratch).map_err(WrapperMqttBytesError::from)?;
+        let end = offset
+            .checked_add(scratch.len())
+            .filter(|end| *end <= target.len())
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "buffer too small: {} bytes needed at offset {offset}, {} available",
+                    scratch.len(),
+                    target.len().saturating_sub(offset)
+                ))
+            })?;
+        target[offset..end].copy_from_slice(&scratch);
+        Ok(scratch.len())
+    })
+}
+
+/// Builds a packet from a dict returned by its `to_dict`, dispatching on the
+/// `type` key. `encoding` applies to the `Publish` and `Connect` payloads.
+#[pyfunction(encoding = "\"base64\"")]
+fn packet_from_dict(_py: Python, data: &PyDict, encoding: &str) -> PyResult<PyObject> {
+    let packet_type: &str = required(data, "type")?;
+    Ok(match packet_type {
+        "Connect" => Connect::from_dict(data, encoding)?.into_py(_py),
+        "ConnAck" => ConnAck::from_dict(data)?.into_py(_py),
+        "Publish" => Publish::from_dict(data, encoding)?.into_py(_py),
+        "PubAck" => PubAck::from_dict(data)?.into_py(_py),
+        "PubRec" => PubRec::from_dict(data)?.into_py(_py),
+        "PubRel" => PubRel::from_dict(data)?.into_py(_py),
+        "PubComp" => PubComp::from_dict(data)?.into_py(_py),
+        "Subscribe" => Subscribe::from_dict(data)?.into_py(_py),
+        "SubAck" => SubAck::from_dict(data)?.into_py(_py),
+        "Unsubscribe" => Unsubscribe::from_dict(data)?.into_py(_py),
+        "UnsubAck" => UnsubAck::from_dict(data)?.into_py(_py),
+        "PingReq" => PingReq::from_dict(data)?.into_py(_py),
+        "PingResp" => PingResp::from_dict(data)?.into_py(_py),
+        "Disconnect" => Disconnect::from_dict(data)?.into_py(_py),
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "unknown packet type {packet_type:?}"
+            )))
+        }
+    })
 }
 
+/// Pickles a packet as a call to `packet_from_dict` with its `to_dict`, which
+/// keeps every field, even those a packet can't be encoded or decoded with.
+fn reduce<'py>(_py: Python<'py>, dict: &'py PyDict) -> PyResult<(PyObject, (&'py PyDict,))> {
+    let packet_from_dict = _py
+        .import("mqttbytes.v4")?
+        .getattr("packet_from_dict")?
+        .into();
+    Ok((packet_from_dict, (dict,)))
+}
+
+/// Wraps a decoded packet in its matching Python class.
+pub(crate) fn packet_into_py(_py: Python, packet: ::mqttbytes::v4::Packet) -> PyObject {
+    match packet {
+        ::mqttbytes::v4::Packet::Connect(packet) => Connect::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::ConnAck(packet) => ConnAck::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::Publish(packet) => Publish::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::PubAck(packet) => PubAck::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::PubRec(packet) => PubRec::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::PubRel(packet) => PubRel::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::PubComp(packet) => PubComp::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::Subscribe(packet) => Subscribe::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::SubAck(packet) => SubAck::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::Unsubscribe(packet) => Unsubscribe::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::UnsubAck(packet) => UnsubAck::from(packet).into_py(_py),
+        ::mqttbytes::v4::Packet::PingReq => PingReq(::mqttbytes::v4::PingReq).into_py(_py),
+        ::mqttbytes::v4::Packet::PingResp => PingResp(::mqttbytes::v4::PingResp).into_py(_py),
+        ::mqttbytes::v4::Packet::Disconnect => Disconnect(::mqttbytes::v4::Disconnect).into_py(_py),
+    }
+}
+
+/// Docstring of the `mqttbytes.v4` module.
+const DOC: &str = "MQTT 3.1.1 packets, readers and sans-IO sessions.
+
+Packets are encoded and decoded without checking every rule of the
+specification. `validate()` on each packet checks the rules left out and
+raises `ValidationError` for the first one broken. `write(strict=True)`
+validates a packet before encoding it, and `read`, `read_from`, `read_all`
+and `StreamDecoder` with `strict=True` validate packets once decoded. The
+rules are:
+
+- strings and binary fields are at most 65535 bytes long, and strings
+  have no null character;
+- topics are not empty and have no wildcards, and filters are valid topic
+  filters;
+- packets carrying a packet id have a non-zero one, except QoS0 publishes
+  which have none;
+- other rules specific to a packet, documented by its `validate()`.
+";
+
 #[pymodule]
 pub fn v4(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("__doc__", DOC)?;
     m.add_class::<ConnAck>()?;
     m.add_class::<Connect>()?;
     m.add_class::<Disconnect>()?;
@@ -77,6 +368,15 @@ pub fn v4(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<ConnectReturnCode>()?;
     m.add_class::<RetainForwardRule>()?;
     m.add_class::<SubscribeFilter>()?;
+    m.add_class::<StreamDecoder>()?;
+    m.add_class::<ClientSession>()?;
+    m.add_class::<ServerSession>()?;
+    m.add_class::<RetainedStore>()?;
+    m.add_class::<PacketIdAllocator>()?;
     m.add_function(wrap_pyfunction!(read, m)?)?;
+    m.add_function(wrap_pyfunction!(read_from, m)?)?;
+    m.add_function(wrap_pyfunction!(read_all, m)?)?;
+    m.add_function(wrap_pyfunction!(packet_from_dict, m)?)?;
+    m.add_function(wrap_pyfunction!(write_many, m)?)?;
     Ok(())
 }
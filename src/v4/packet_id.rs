@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+
+use super::{PubAck, PubComp, SubAck, UnsubAck};
+use crate::ProtocolError;
+
+/// Hands out packet ids for outgoing packets and tracks the ones in flight.
+///
+/// Ids are non-zero, increase and wrap after 65535, skipping ids still in
+/// flight. An id is released by `acknowledge` with the packet completing its
+/// flow: `PubAck` for QoS1 publishes, `PubComp` for QoS2 publishes (`PubRec`
+/// keeps the id in flight), `SubAck` and `UnsubAck`.
+#[pyclass(module = "mqttbytes.v4")]
+#[derive(Default)]
+pub struct PacketIdAllocator {
+    last_pkid: u16,
+    in_flight: HashSet<u16>,
+}
+
+#[pymethods]
+impl PacketIdAllocator {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the next free packet id and marks it in flight. Raises
+    /// `ProtocolError` when all 65535 ids are in flight.
+    pub(crate) fn allocate(&mut self) -> PyResult<u16> {
+        let mut pkid = self.last_pkid;
+        for _ in 0..u16::MAX {
+            pkid = pkid.checked_add(1).unwrap_or(1);
+            if self.in_flight.insert(pkid) {
+                self.last_pkid = pkid;
+                return Ok(pkid);
+            }
+        }
+        Err(ProtocolError::new_err("all packet ids are in flight"))
+    }
+
+    /// Releases `pkid` and returns whether it was in flight.
+    pub(crate) fn release(&mut self, pkid: u16) -> bool {
+        self.in_flight.remove(&pkid)
+    }
+
+    /// Releases the id of an acknowledgement and returns whether it was in
+    /// flight. Accepts `PubAck`, `PubComp`, `SubAck` and `UnsubAck`.
+    fn acknowledge(&mut self, packet: &PyAny) -> PyResult<bool> {
+        let pkid = if let Ok(packet) = packet.downcast::<PyCell<PubAck>>() {
+            packet.borrow().0.pkid
+        } else if let Ok(packet) = packet.downcast::<PyCell<PubComp>>() {
+            packet.borrow().0.pkid
+        } else if let Ok(packet) = packet.downcast::<PyCell<SubAck>>() {
+            packet.borrow().0.pkid
+        } else if let Ok(packet) = packet.downcast::<PyCell<UnsubAck>>() {
+            packet.borrow().0.pkid
+        } else {
+            return Err(PyTypeError::new_err(format!(
+                "expected PubAck, PubComp, SubAck or UnsubAck, got {}",
+                packet.get_type().name()?
+            )));
+        };
+        Ok(self.release(pkid))
+    }
+
+    /// Releases every id, e.g. when a clean session starts.
+    pub(crate) fn clear(&mut self) {
+        self.in_flight.clear();
+    }
+
+    fn __contains__(&self, pkid: u16) -> bool {
+        self.in_flight.contains(&pkid)
+    }
+
+    /// Returns the number of ids in flight.
+    fn __len__(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PacketIdAllocator(last_pkid={}, in_flight={})",
+            self.last_pkid,
+            self.in_flight.len()
+        )
+    }
+
+    /// Ids in flight, in increasing order.
+    #[getter]
+    fn get_in_flight(&self) -> Vec<u16> {
+        let mut in_flight: Vec<u16> = self.in_flight.iter().copied().collect();
+        in_flight.sort_unstable();
+        in_flight
+    }
+}
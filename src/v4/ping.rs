@@ -1,8 +1,12 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict};
 
-use crate::WrapperMqttBytesError;
+use super::{reduce, write_into};
+use crate::dict::{check_type, packet_dict};
+use crate::{richcmp, WrapperMqttBytesError};
 
 #[pyclass(module = "mqttbytes.v4")]
 pub struct PingReq(pub ::mqttbytes::v4::PingReq);
@@ -14,13 +18,60 @@ impl PingReq {
         ::mqttbytes::v4::PingReq.into()
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    fn validate(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
             .map_err(WrapperMqttBytesError::from)?;
         Ok(PyBytes::new(_py, &buffer).into())
     }
+
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(::mqttbytes::v4::PingReq)
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(::mqttbytes::v4::PingReq)
+    }
+
+    fn __richcmp__(&self, _other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &(), &(), op)
+    }
+
+    fn __hash__(&self) -> u64 {
+        0
+    }
+
+    fn __repr__(&self) -> String {
+        "PingReq()".to_owned()
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        packet_dict(_py, "PingReq")
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "PingReq")?;
+        Ok(::mqttbytes::v4::PingReq.into())
+    }
 }
 
 impl From<::mqttbytes::v4::PingReq> for PingReq {
@@ -39,13 +90,60 @@ impl PingResp {
         ::mqttbytes::v4::PingResp.into()
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    fn validate(&self) -> PyResult<()> {
+        Ok(())
+    }
+
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
             .map_err(WrapperMqttBytesError::from)?;
         Ok(PyBytes::new(_py, &buffer).into())
     }
+
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(::mqttbytes::v4::PingResp)
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(::mqttbytes::v4::PingResp)
+    }
+
+    fn __richcmp__(&self, _other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &(), &(), op)
+    }
+
+    fn __hash__(&self) -> u64 {
+        0
+    }
+
+    fn __repr__(&self) -> String {
+        "PingResp()".to_owned()
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        packet_dict(_py, "PingResp")
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "PingResp")?;
+        Ok(::mqttbytes::v4::PingResp.into())
+    }
 }
 
 impl From<::mqttbytes::v4::PingResp> for PingResp {
@@ -1,12 +1,16 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict};
 
-use crate::{FixedHeader, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{check_type, packet_dict, required};
+use crate::{richcmp, FixedHeader, WrapperMqttBytesError};
 
 /// Acknowledgement to QoS1 publish.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct PubAck(::mqttbytes::v4::PubAck);
+pub struct PubAck(pub ::mqttbytes::v4::PubAck);
 
 #[pymethods]
 impl PubAck {
@@ -16,13 +20,26 @@ impl PubAck {
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::PubAck::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::PubAck::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::puback(&packet)?;
+        }
+        Ok(packet.into())
+    }
+
+    /// Checks that the packet id is not 0.
+    fn validate(&self) -> PyResult<()> {
+        validate::puback(&self.0)
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -30,6 +47,43 @@ impl PubAck {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PubAck(pkid={})", self.0.pkid)
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = packet_dict(_py, "PubAck")?;
+        dict.set_item("pkid", self.0.pkid)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "PubAck")?;
+        Ok(::mqttbytes::v4::PubAck::new(required(data, "pkid")?).into())
+    }
+
     #[getter]
     fn get_pkid(&self) -> u16 {
         self.0.pkid
@@ -1,18 +1,40 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict};
 
-use crate::{FixedHeader, QoS, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{
+    check_type, optional, packet_dict, required, required_bytes, required_qos, Encoding,
+};
+use crate::payload::{buffer_to_bytes, memoryview};
+use crate::repr::{repr_bool, repr_bytes, repr_str};
+use crate::{richcmp, FixedHeader, QoS, QoSArg, WrapperMqttBytesError};
 
 /// Publish packet.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct Publish(::mqttbytes::v4::Publish);
+pub struct Publish(pub ::mqttbytes::v4::Publish);
 
 #[pymethods]
 impl Publish {
     #[new]
-    fn new(topic: String, qos: QoS, payload: Vec<u8>) -> Self {
-        ::mqttbytes::v4::Publish::new(topic, qos.into(), payload).into()
+    #[args(retain = "false", dup = "false", pkid = "0")]
+    fn new(
+        _py: Python,
+        topic: String,
+        qos: QoSArg,
+        payload: PyBuffer<u8>,
+        retain: bool,
+        dup: bool,
+        pkid: u16,
+    ) -> PyResult<Self> {
+        let mut publish = ::mqttbytes::v4::Publish::new(topic, qos.into(), Vec::new());
+        publish.payload = buffer_to_bytes(_py, &payload)?;
+        publish.retain = retain;
+        publish.dup = dup;
+        publish.pkid = pkid;
+        Ok(publish.into())
     }
 
     fn __len__(&self) -> usize {
@@ -20,13 +42,27 @@ impl Publish {
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::Publish::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::Publish::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::publish(&packet)?;
+        }
+        Ok(packet.into())
+    }
+
+    /// Checks the topic, and that the packet id is 0 and `dup` unset for QoS0,
+    /// and the packet id non-zero for QoS1 and QoS2.
+    fn validate(&self) -> PyResult<()> {
+        validate::publish(&self.0)
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -34,6 +70,72 @@ impl Publish {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py, "base64")?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Publish(topic={}, qos=QoS.{:?}, payload={}, pkid={}, retain={}, dup={})",
+            repr_str(&self.0.topic),
+            self.0.qos,
+            repr_bytes(&self.0.payload),
+            self.0.pkid,
+            repr_bool(self.0.retain),
+            repr_bool(self.0.dup),
+        )
+    }
+
+    /// Returns the packet as a JSON-safe dict, with the payload encoded as
+    /// `encoding` ('base64' or 'hex') and the QoS as its number.
+    #[args(encoding = "\"base64\"")]
+    fn to_dict<'py>(&self, _py: Python<'py>, encoding: &str) -> PyResult<&'py PyDict> {
+        let encoding = Encoding::parse(encoding)?;
+        let dict = packet_dict(_py, "Publish")?;
+        dict.set_item("topic", &self.0.topic)?;
+        dict.set_item("qos", self.0.qos as u8)?;
+        dict.set_item("payload", encoding.encode(&self.0.payload))?;
+        dict.set_item("pkid", self.0.pkid)?;
+        dict.set_item("retain", self.0.retain)?;
+        dict.set_item("dup", self.0.dup)?;
+        Ok(dict)
+    }
+
+    /// Builds the packet from a dict returned by `to_dict`. Only `topic`,
+    /// `qos` and `payload` are required.
+    #[staticmethod]
+    #[args(encoding = "\"base64\"")]
+    pub(crate) fn from_dict(data: &PyDict, encoding: &str) -> PyResult<Self> {
+        let encoding = Encoding::parse(encoding)?;
+        check_type(data, "Publish")?;
+        let mut publish = ::mqttbytes::v4::Publish::new(
+            required::<String>(data, "topic")?,
+            required_qos(data, "qos")?,
+            required_bytes(data, "payload", encoding)?,
+        );
+        publish.pkid = optional(data, "pkid")?.unwrap_or_default();
+        publish.retain = optional(data, "retain")?.unwrap_or_default();
+        publish.dup = optional(data, "dup")?.unwrap_or_default();
+        Ok(publish.into())
+    }
+
     #[getter]
     fn get_dup(&self) -> bool {
         self.0.dup
@@ -50,7 +152,7 @@ impl Publish {
     }
 
     #[setter]
-    fn set_qos(&mut self, qos: QoS) {
+    fn set_qos(&mut self, qos: QoSArg) {
         self.0.qos = qos.into();
     }
 
@@ -84,14 +186,17 @@ impl Publish {
         self.0.pkid = pkid;
     }
 
+    /// Read-only `memoryview` sharing the memory of the payload.
     #[getter]
-    fn get_payload(&self) -> Vec<u8> {
-        self.0.payload.to_vec()
+    fn get_payload(&self, _py: Python) -> PyResult<PyObject> {
+        memoryview(_py, self.0.payload.clone())
     }
 
+    /// Accepts any object supporting the buffer protocol.
     #[setter]
-    fn set_payload(&mut self, payload: Vec<u8>) {
-        self.0.payload = payload.into();
+    fn set_payload(&mut self, _py: Python, payload: PyBuffer<u8>) -> PyResult<()> {
+        self.0.payload = buffer_to_bytes(_py, &payload)?;
+        Ok(())
     }
 }
 
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use pyo3::prelude::*;
+
+use super::{Publish, RetainForwardRule, SubscribeFilter};
+use crate::tree::matches;
+
+/// Last retained publish of every topic, as kept by a broker.
+#[pyclass(module = "mqttbytes.v4")]
+#[derive(Default)]
+pub struct RetainedStore(BTreeMap<String, ::mqttbytes::v4::Publish>);
+
+#[pymethods]
+impl RetainedStore {
+    #[new]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the store from an incoming publish. Publishes without the retain
+    /// flag are ignored, and a retained publish with an empty payload deletes
+    /// the message retained for its topic.
+    pub(crate) fn store(&mut self, publish: &Publish) {
+        if !publish.0.retain {
+            return;
+        }
+        if publish.0.payload.is_empty() {
+            self.0.remove(&publish.0.topic);
+        } else {
+            self.0.insert(publish.0.topic.clone(), publish.0.clone());
+        }
+    }
+
+    /// Returns the retained publishes to forward for a subscription to `filter`.
+    ///
+    /// `new_subscription` tells whether the filter was not already subscribed
+    /// by the client, which `RetainForwardRule.OnNewSubscribe` depends on.
+    /// Returned publishes keep the retain flag, have no packet id and are
+    /// downgraded to the QoS granted by `filter`.
+    #[args(
+        rule = "RetainForwardRule::OnEverySubscribe",
+        new_subscription = "true"
+    )]
+    pub(crate) fn retained(
+        &self,
+        filter: &SubscribeFilter,
+        rule: RetainForwardRule,
+        new_subscription: bool,
+    ) -> Vec<Publish> {
+        match rule {
+            RetainForwardRule::OnEverySubscribe => {}
+            RetainForwardRule::OnNewSubscribe if new_subscription => {}
+            RetainForwardRule::OnNewSubscribe | RetainForwardRule::Never => return Vec::new(),
+        }
+        self.0
+            .values()
+            .filter(|publish| matches(&publish.topic, &filter.0.path))
+            .map(|publish| {
+                let mut publish = publish.clone();
+                if filter.0.qos < publish.qos {
+                    publish.qos = filter.0.qos;
+                }
+                publish.pkid = 0;
+                publish.dup = false;
+                publish.into()
+            })
+            .collect()
+    }
+
+    /// Deletes the message retained for `topic`, if any.
+    fn remove(&mut self, topic: &str) {
+        self.0.remove(topic);
+    }
+
+    /// Returns the message retained for `topic`, if any.
+    fn get(&self, topic: &str) -> Option<Publish> {
+        self.0.get(topic).cloned().map(Into::into)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RetainedStore(topics={})", self.0.len())
+    }
+
+    fn __contains__(&self, topic: &str) -> bool {
+        self.0.contains_key(topic)
+    }
+
+    /// Topics that have a retained message.
+    #[getter]
+    pub(crate) fn get_topics(&self) -> Vec<String> {
+        self.0.keys().cloned().collect()
+    }
+}
@@ -0,0 +1,314 @@
+use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use super::inflight::Inflight;
+use super::{packet_into_py, Login, Publish};
+use crate::{buffer_as_slice, ProtocolError, QoSArg, WrapperMqttBytesError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    AwaitingConnect,
+    Connected,
+    Closed,
+}
+
+/// Broker side of an MQTT 3.1.1 connection, without any I/O.
+///
+/// Bytes received from the client are passed to `receive_data`, which answers
+/// the packets the protocol requires (`ConnAck`, `SubAck`, `UnsubAck`,
+/// `PingResp` and the QoS acknowledgements) and returns the events the broker
+/// acts on: the accepted `Connect`, incoming `Publish` messages (delivered
+/// once, even for QoS2), `Subscribe`, `Unsubscribe`, `PubAck` and `PubComp`
+/// when a publish sent to the client completes, and `Disconnect`.
+/// Bytes to write to the client are collected with `data_to_send`. A publish
+/// to a topic that isn't valid closes the session.
+///
+/// When a packet can't be decoded or breaks the protocol, the events of the
+/// packets before it are returned and the error is raised by the next call.
+#[pyclass(module = "mqttbytes.v4")]
+pub struct ServerSession {
+    max_size: usize,
+    login: Option<::mqttbytes::v4::Login>,
+    max_qos: ::mqttbytes::QoS,
+    state: State,
+    incoming: BytesMut,
+    outgoing: BytesMut,
+    inflight: Inflight,
+    /// Error raised by the next `receive_data`, after the events preceding it
+    /// were returned.
+    error: Option<PyErr>,
+}
+
+#[pymethods]
+impl ServerSession {
+    /// When `login` is set, clients must present matching credentials.
+    /// Subscriptions are granted at most `max_qos`.
+    #[new]
+    #[args(login = "None", max_qos = "QoSArg(::mqttbytes::QoS::ExactlyOnce)")]
+    pub(crate) fn new(max_size: usize, login: Option<Login>, max_qos: QoSArg) -> Self {
+        Self {
+            max_size,
+            login: login.map(|login| login.0),
+            max_qos: max_qos.into(),
+            state: State::AwaitingConnect,
+            incoming: BytesMut::new(),
+            outgoing: BytesMut::new(),
+            inflight: Inflight::default(),
+            error: None,
+        }
+    }
+
+    /// Feeds bytes received from the client and returns the resulting events.
+    /// `data` can be any object supporting the buffer protocol.
+    fn receive_data(&mut self, _py: Python, data: PyBuffer<u8>) -> PyResult<Vec<PyObject>> {
+        let data = buffer_as_slice(_py, &data)?;
+        if let Some(err) = self.error.take() {
+            self.incoming.extend_from_slice(data);
+            return Err(err);
+        }
+        let (events, error) = self.receive(data)?;
+        match error {
+            Some(err) if events.is_empty() => Err(err),
+            error => {
+                self.error = error;
+                Ok(events
+                    .into_iter()
+                    .map(|event| packet_into_py(_py, event))
+                    .collect())
+            }
+        }
+    }
+
+    /// Queues a publish to the client and returns the packet id allocated to
+    /// it, or 0 for QoS0.
+    fn publish(&mut self, publish: &Publish) -> PyResult<u16> {
+        self.send(publish.0.clone())
+    }
+
+    /// Returns the bytes queued for the client and empties the queue.
+    fn data_to_send(&mut self, _py: Python) -> Py<PyBytes> {
+        let data = self.take_outgoing();
+        PyBytes::new(_py, &data).into()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ServerSession(state={:?}, max_qos=QoS.{:?}, inflight={})",
+            self.state,
+            self.max_qos,
+            self.inflight.outgoing().len()
+        )
+    }
+
+    /// Whether a `Connect` was received and accepted.
+    #[getter]
+    fn get_connected(&self) -> bool {
+        self.state == State::Connected
+    }
+
+    /// Whether the connection should be closed, after sending any queued bytes.
+    #[getter]
+    pub(crate) fn get_closed(&self) -> bool {
+        self.state == State::Closed
+    }
+
+    /// Packet ids of publishes to the client not yet fully acknowledged.
+    #[getter]
+    fn get_inflight(&self) -> Vec<u16> {
+        self.inflight.outgoing()
+    }
+}
+
+impl ServerSession {
+    /// Feeds bytes received from the client and returns the resulting events,
+    /// stopping at the first packet that can't be decoded or breaks the
+    /// protocol, along with its error.
+    pub(crate) fn receive(
+        &mut self,
+        data: &[u8],
+    ) -> PyResult<(Vec<::mqttbytes::v4::Packet>, Option<PyErr>)> {
+        if self.state == State::Closed {
+            return Err(ProtocolError::new_err("session is closed"));
+        }
+        self.incoming.extend_from_slice(data);
+        let mut events = Vec::new();
+        while self.state != State::Closed {
+            let event = match ::mqttbytes::v4::read(&mut self.incoming, self.max_size) {
+                Ok(packet) => self.handle(packet),
+                Err(::mqttbytes::Error::InsufficientBytes(_)) => break,
+                Err(
+                    ::mqttbytes::Error::InvalidProtocol
+                    | ::mqttbytes::Error::InvalidProtocolLevel(_),
+                ) if self.state == State::AwaitingConnect => {
+                    self.refuse(::mqttbytes::v4::ConnectReturnCode::RefusedProtocolVersion)?;
+                    break;
+                }
+                Err(err) => Err(WrapperMqttBytesError::from(err).into()),
+            };
+            match event {
+                Ok(Some(event)) => events.push(event),
+                Ok(None) => {}
+                Err(err) => return Ok((events, Some(err))),
+            }
+        }
+        Ok((events, None))
+    }
+
+    /// Queues a publish to the client, allocating its packet id.
+    pub(crate) fn send(&mut self, mut publish: ::mqttbytes::v4::Publish) -> PyResult<u16> {
+        if self.state != State::Connected {
+            return Err(ProtocolError::new_err("session is not connected"));
+        }
+        publish.pkid = match publish.qos {
+            ::mqttbytes::QoS::AtMostOnce => 0,
+            _ => self.inflight.next_pkid()?,
+        };
+        let pkid = publish.pkid;
+        self.inflight.publish(publish, &mut self.outgoing)?;
+        Ok(pkid)
+    }
+
+    /// Takes the bytes queued for the client.
+    pub(crate) fn take_outgoing(&mut self) -> BytesMut {
+        self.outgoing.split()
+    }
+
+    /// Checks a `Connect` packet and returns the return code to answer with.
+    fn validate(&self, connect: &::mqttbytes::v4::Connect) -> ::mqttbytes::v4::ConnectReturnCode {
+        use ::mqttbytes::v4::ConnectReturnCode;
+
+        if connect.protocol != ::mqttbytes::Protocol::V4 {
+            return ConnectReturnCode::RefusedProtocolVersion;
+        }
+        if connect.client_id.is_empty() && !connect.clean_session {
+            return ConnectReturnCode::BadClientId;
+        }
+        match (&self.login, &connect.login) {
+            (None, _) => ConnectReturnCode::Success,
+            (Some(_), None) => ConnectReturnCode::NotAuthorized,
+            (Some(expected), Some(login)) => {
+                match login.validate(&expected.username, &expected.password) {
+                    true => ConnectReturnCode::Success,
+                    false => ConnectReturnCode::BadUserNamePassword,
+                }
+            }
+        }
+    }
+
+    /// Answers with a refusing `ConnAck` and closes the session.
+    fn refuse(
+        &mut self,
+        code: ::mqttbytes::v4::ConnectReturnCode,
+    ) -> Result<(), WrapperMqttBytesError> {
+        ::mqttbytes::v4::ConnAck::new(code, false).write(&mut self.outgoing)?;
+        self.state = State::Closed;
+        Ok(())
+    }
+
+    /// Advances the protocol state for an incoming packet and returns the
+    /// packet to surface as an event, if any.
+    fn handle(
+        &mut self,
+        packet: ::mqttbytes::v4::Packet,
+    ) -> PyResult<Option<::mqttbytes::v4::Packet>> {
+        use ::mqttbytes::v4::Packet;
+
+        match (self.state, packet) {
+            (State::AwaitingConnect, Packet::Connect(connect)) => match self.validate(&connect) {
+                ::mqttbytes::v4::ConnectReturnCode::Success => {
+                    ::mqttbytes::v4::ConnAck::new(
+                        ::mqttbytes::v4::ConnectReturnCode::Success,
+                        false,
+                    )
+                    .write(&mut self.outgoing)
+                    .map_err(WrapperMqttBytesError::from)?;
+                    self.state = State::Connected;
+                    Ok(Some(Packet::Connect(connect)))
+                }
+                code => {
+                    self.refuse(code)?;
+                    Ok(None)
+                }
+            },
+            (State::Connected, Packet::Publish(publish))
+                if !::mqttbytes::valid_topic(&publish.topic) =>
+            {
+                self.state = State::Closed;
+                Err(ProtocolError::new_err(format!(
+                    "invalid topic in Publish: {:?}",
+                    publish.topic
+                )))
+            }
+            (State::Connected, Packet::Publish(publish)) => {
+                let deliver = self
+                    .inflight
+                    .incoming_publish(&publish, &mut self.outgoing)?;
+                Ok(deliver.then_some(Packet::Publish(publish)))
+            }
+            (State::Connected, Packet::PubAck(puback)) => {
+                self.inflight.puback(puback.pkid)?;
+                Ok(Some(Packet::PubAck(puback)))
+            }
+            (State::Connected, Packet::PubRec(pubrec)) => {
+                self.inflight.pubrec(pubrec.pkid, &mut self.outgoing)?;
+                Ok(None)
+            }
+            (State::Connected, Packet::PubRel(pubrel)) => {
+                self.inflight.pubrel(pubrel.pkid, &mut self.outgoing)?;
+                Ok(None)
+            }
+            (State::Connected, Packet::PubComp(pubcomp)) => {
+                self.inflight.pubcomp(pubcomp.pkid)?;
+                Ok(Some(Packet::PubComp(pubcomp)))
+            }
+            (State::Connected, Packet::Subscribe(subscribe)) => {
+                let return_codes = subscribe
+                    .filters
+                    .iter()
+                    .map(|filter| match ::mqttbytes::valid_filter(&filter.path) {
+                        true if filter.qos > self.max_qos => {
+                            ::mqttbytes::v4::SubscribeReasonCode::Success(self.max_qos)
+                        }
+                        true => ::mqttbytes::v4::SubscribeReasonCode::Success(filter.qos),
+                        false => ::mqttbytes::v4::SubscribeReasonCode::Failure,
+                    })
+                    .collect();
+                ::mqttbytes::v4::SubAck::new(subscribe.pkid, return_codes)
+                    .write(&mut self.outgoing)
+                    .map_err(WrapperMqttBytesError::from)?;
+                Ok(Some(Packet::Subscribe(subscribe)))
+            }
+            (State::Connected, Packet::Unsubscribe(unsubscribe)) => {
+                ::mqttbytes::v4::UnsubAck::new(unsubscribe.pkid)
+                    .write(&mut self.outgoing)
+                    .map_err(WrapperMqttBytesError::from)?;
+                Ok(Some(Packet::Unsubscribe(unsubscribe)))
+            }
+            (State::Connected, Packet::PingReq) => {
+                ::mqttbytes::v4::PingResp
+                    .write(&mut self.outgoing)
+                    .map_err(WrapperMqttBytesError::from)?;
+                Ok(None)
+            }
+            (State::Connected, Packet::Disconnect) => {
+                self.state = State::Closed;
+                Ok(Some(Packet::Disconnect))
+            }
+            (State::AwaitingConnect, _) => {
+                self.state = State::Closed;
+                Err(ProtocolError::new_err(
+                    "expected Connect as the first packet from the client",
+                ))
+            }
+            (State::Connected, Packet::Connect(_)) => {
+                self.state = State::Closed;
+                Err(ProtocolError::new_err("client sent a second Connect"))
+            }
+            (_, _) => Err(ProtocolError::new_err(
+                "packet is not allowed from the client",
+            )),
+        }
+    }
+}
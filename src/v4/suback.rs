@@ -1,17 +1,22 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict};
 
-use crate::{FixedHeader, QoS, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{check_type, packet_dict, required};
+use crate::{richcmp, FixedHeader, QoS, QoSArg, WrapperMqttBytesError};
 
 /// Acknowledgement to subscribe.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct SubAck(::mqttbytes::v4::SubAck);
+pub struct SubAck(pub ::mqttbytes::v4::SubAck);
 
 #[pymethods]
 impl SubAck {
     #[new]
-    fn new(pkid: u16, return_codes: Vec<Option<QoS>>) -> Self {
+    fn new(pkid: u16, return_codes: Vec<Option<QoSArg>>) -> Self {
         ::mqttbytes::v4::SubAck::new(
             pkid,
             from_vec_option_qos_to_vec_subscribe_reason_code(return_codes),
@@ -24,13 +29,26 @@ impl SubAck {
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::SubAck::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::SubAck::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::suback(&packet)?;
+        }
+        Ok(packet.into())
+    }
+
+    /// Checks the packet id, and that there is at least one return code.
+    fn validate(&self) -> PyResult<()> {
+        validate::suback(&self.0)
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -38,6 +56,77 @@ impl SubAck {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        let return_codes: Vec<String> = self
+            .0
+            .return_codes
+            .iter()
+            .map(|code| match code {
+                ::mqttbytes::v4::SubscribeReasonCode::Success(qos) => format!("QoS.{qos:?}"),
+                ::mqttbytes::v4::SubscribeReasonCode::Failure => "None".to_owned(),
+            })
+            .collect();
+        format!(
+            "SubAck(pkid={}, return_codes=[{}])",
+            self.0.pkid,
+            return_codes.join(", ")
+        )
+    }
+
+    /// Returns the packet as a JSON-safe dict, with each granted QoS as its
+    /// number and failures as `None`.
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = packet_dict(_py, "SubAck")?;
+        dict.set_item("pkid", self.0.pkid)?;
+        let return_codes: Vec<Option<u8>> = self
+            .0
+            .return_codes
+            .iter()
+            .map(|code| match code {
+                ::mqttbytes::v4::SubscribeReasonCode::Success(qos) => Some(*qos as u8),
+                ::mqttbytes::v4::SubscribeReasonCode::Failure => None,
+            })
+            .collect();
+        dict.set_item("return_codes", return_codes)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "SubAck")?;
+        let return_codes = required::<Vec<Option<u8>>>(data, "return_codes")?
+            .into_iter()
+            .map(|code| match code {
+                Some(num) => ::mqttbytes::qos(num)
+                    .map(::mqttbytes::v4::SubscribeReasonCode::Success)
+                    .map_err(|_| PyValueError::new_err(format!("invalid QoS {num}"))),
+                None => Ok(::mqttbytes::v4::SubscribeReasonCode::Failure),
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(::mqttbytes::v4::SubAck::new(required(data, "pkid")?, return_codes).into())
+    }
+
     #[getter]
     fn get_pkid(&self) -> u16 {
         self.0.pkid
@@ -54,7 +143,7 @@ impl SubAck {
     }
 
     #[setter]
-    fn set_return_codes(&mut self, return_codes: Vec<Option<QoS>>) {
+    fn set_return_codes(&mut self, return_codes: Vec<Option<QoSArg>>) {
         self.0.return_codes = from_vec_option_qos_to_vec_subscribe_reason_code(return_codes);
     }
 }
@@ -66,7 +155,7 @@ impl From<::mqttbytes::v4::SubAck> for SubAck {
 }
 
 fn from_option_qos_to_subscribe_reason_code(
-    return_code: Option<QoS>,
+    return_code: Option<QoSArg>,
 ) -> ::mqttbytes::v4::SubscribeReasonCode {
     match return_code {
         Some(qos) => ::mqttbytes::v4::SubscribeReasonCode::Success(qos.into()),
@@ -75,7 +164,7 @@ fn from_option_qos_to_subscribe_reason_code(
 }
 
 fn from_vec_option_qos_to_vec_subscribe_reason_code(
-    return_codes: Vec<Option<QoS>>,
+    return_codes: Vec<Option<QoSArg>>,
 ) -> Vec<::mqttbytes::v4::SubscribeReasonCode> {
     return_codes
         .into_iter()
@@ -1,51 +1,145 @@
 use bytes::BytesMut;
-use pyo3::exceptions::PyTypeError;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyIndexError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict, PyString, PyTuple};
 
-use crate::{FixedHeader, QoS, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{check_type, optional, packet_dict, required, required_qos};
+use crate::repr::repr_str;
+use crate::{richcmp, FixedHeader, QoS, QoSArg, WrapperMqttBytesError};
 
 /// Subscription packet.
+///
+/// Behaves as a list of its filters: it can be iterated, reversed, indexed
+/// and tested with `in`, and filters are added with `append` and removed with
+/// `remove`. Filters are returned as copies. `len()` is the encoded length
+/// like for other packets, `len(subscribe.filters)` is the number of filters.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct Subscribe(::mqttbytes::v4::Subscribe);
+pub struct Subscribe(pub ::mqttbytes::v4::Subscribe);
 
 #[pymethods]
 impl Subscribe {
+    /// `path` is a single filter path subscribed at `qos`, a single `(path,
+    /// qos)` tuple, or an iterable of `SubscribeFilter`s, `(path, qos)` tuples
+    /// and paths subscribed at `qos`.
     #[new]
-    fn new(path: Option<&PyAny>, qos: Option<QoS>) -> PyResult<Self> {
-        match (path, qos) {
-            (Some(path), Some(qos)) => {
-                let path = path.extract::<String>()?;
-                Ok(::mqttbytes::v4::Subscribe::new(path, qos.into()).into())
+    #[args(path = "None", qos = "None", pkid = "0")]
+    fn new(path: Option<&PyAny>, qos: Option<QoSArg>, pkid: u16) -> PyResult<Self> {
+        let qos = qos.map(Into::into);
+        let filters = match path {
+            None if qos.is_some() => {
+                return Err(PyTypeError::new_err(
+                    "Subscribe got a qos but no path to subscribe to",
+                ))
             }
-            (Some(path), None) => {
-                let topics = path.extract::<Vec<SubscribeFilter>>()?;
-                Ok(
-                    ::mqttbytes::v4::Subscribe::new_many(topics.into_iter().map(|topic| topic.0))
-                        .into(),
-                )
+            None => Vec::new(),
+            Some(path)
+                if path.is_instance_of::<PyString>()?
+                    || path.is_instance_of::<SubscribeFilter>()? =>
+            {
+                vec![extract_filter(path, qos)?]
             }
-            (None, None) => Ok(::mqttbytes::v4::Subscribe::empty_subscribe().into()),
-            _ => Err(PyTypeError::new_err("Invalid arguments")),
-        }
+            // Not iterated, which would take the QoS for a second filter.
+            Some(path) if is_filter_tuple(path)? => vec![extract_filter(path, None)?],
+            Some(filters) => match filters.iter() {
+                Ok(filters) => filters
+                    .map(|filter| extract_filter(filter?, qos))
+                    .collect::<PyResult<_>>()?,
+                Err(_) => {
+                    return Err(PyTypeError::new_err(format!(
+                        "expected a path or an iterable of filters, got {}",
+                        filters.get_type().name()?
+                    )))
+                }
+            },
+        };
+        let mut subscribe = ::mqttbytes::v4::Subscribe::new_many(filters);
+        subscribe.pkid = pkid;
+        Ok(subscribe.into())
     }
 
-    fn add(&mut self, path: String, qos: QoS) {
+    fn add(&mut self, path: String, qos: QoSArg) {
         self.0.add(path, qos.into());
     }
 
+    /// Adds a `SubscribeFilter` or a `(path, qos)` tuple.
+    fn append(&mut self, filter: &PyAny) -> PyResult<()> {
+        self.0.filters.push(extract_filter(filter, None)?);
+        Ok(())
+    }
+
+    /// Removes the first filter equal to `filter`, or with path `filter` when
+    /// given a string. Raises `ValueError` if there is none.
+    fn remove(&mut self, filter: &PyAny) -> PyResult<()> {
+        match self.position(filter)? {
+            Some(index) => {
+                self.0.filters.remove(index);
+                Ok(())
+            }
+            None => Err(PyValueError::new_err(format!(
+                "Subscribe has no filter {}",
+                filter.repr()?
+            ))),
+        }
+    }
+
+    /// Tells whether a filter equals `filter`, or has path `filter` when given
+    /// a string. False for objects that are not filters.
+    fn __contains__(&self, filter: &PyAny) -> bool {
+        matches!(self.position(filter), Ok(Some(_)))
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<SubscribeFilter> {
+        let len = self.0.filters.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&index) {
+            return Err(PyIndexError::new_err(format!(
+                "Subscribe filter index out of range, it has {len} filters"
+            )));
+        }
+        Ok(self.0.filters[index as usize].clone().into())
+    }
+
+    fn __iter__(&self, _py: Python) -> PyResult<PyObject> {
+        self.get_filters()
+            .into_py(_py)
+            .call_method0(_py, "__iter__")
+    }
+
+    fn __reversed__(&self, _py: Python) -> PyResult<PyObject> {
+        let mut filters = self.get_filters();
+        filters.reverse();
+        filters.into_py(_py).call_method0(_py, "__iter__")
+    }
+
     fn __len__(&self) -> usize {
         self.0.len()
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::Subscribe::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::Subscribe::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::subscribe(&packet)?;
+        }
+        Ok(packet.into())
     }
 
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    /// Checks the packet id, and that there is at least one filter and every
+    /// filter is valid.
+    fn validate(&self) -> PyResult<()> {
+        validate::subscribe(&self.0)
+    }
+
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -53,6 +147,65 @@ impl Subscribe {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        let filters: Vec<String> = self
+            .0
+            .filters
+            .iter()
+            .map(|filter| SubscribeFilter(filter.clone()).__repr__())
+            .collect();
+        format!(
+            "Subscribe(pkid={}, filters=[{}])",
+            self.0.pkid,
+            filters.join(", ")
+        )
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = packet_dict(_py, "Subscribe")?;
+        dict.set_item("pkid", self.0.pkid)?;
+        let filters = self
+            .get_filters()
+            .iter()
+            .map(|filter| filter.to_dict(_py))
+            .collect::<PyResult<Vec<_>>>()?;
+        dict.set_item("filters", filters)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "Subscribe")?;
+        let filters = required::<Vec<&PyDict>>(data, "filters")?
+            .into_iter()
+            .map(|filter| SubscribeFilter::from_dict(filter).map(|filter| filter.0))
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut subscribe = ::mqttbytes::v4::Subscribe::new_many(filters);
+        subscribe.pkid = optional(data, "pkid")?.unwrap_or_default();
+        Ok(subscribe.into())
+    }
+
     #[getter]
     fn get_pkid(&self) -> u16 {
         self.0.pkid
@@ -74,24 +227,98 @@ impl Subscribe {
     }
 }
 
+impl Subscribe {
+    fn position(&self, filter: &PyAny) -> PyResult<Option<usize>> {
+        if let Ok(path) = filter.extract::<&str>() {
+            return Ok(self.0.filters.iter().position(|other| other.path == path));
+        }
+        let filter = extract_filter(filter, None)?;
+        Ok(self.0.filters.iter().position(|other| *other == filter))
+    }
+}
+
 impl From<::mqttbytes::v4::Subscribe> for Subscribe {
     fn from(p: ::mqttbytes::v4::Subscribe) -> Self {
         Self(p)
     }
 }
 
+/// Tells whether `filter` is a `(path, qos)` tuple rather than a tuple of
+/// filters.
+fn is_filter_tuple(filter: &PyAny) -> PyResult<bool> {
+    Ok(filter.is_instance_of::<PyTuple>()?
+        && filter.len()? == 2
+        && filter.get_item(0)?.is_instance_of::<PyString>()?
+        && !filter.get_item(1)?.is_instance_of::<PyString>()?)
+}
+
+/// Extracts a `SubscribeFilter`, a `(path, qos)` tuple or, when `qos` is set,
+/// a path.
+fn extract_filter(
+    filter: &PyAny,
+    qos: Option<::mqttbytes::QoS>,
+) -> PyResult<::mqttbytes::v4::SubscribeFilter> {
+    if let Ok(filter) = filter.extract::<SubscribeFilter>() {
+        return Ok(filter.0);
+    }
+    if let Ok(path) = filter.extract::<String>() {
+        return match qos {
+            Some(qos) => Ok(::mqttbytes::v4::SubscribeFilter::new(path, qos)),
+            None => Err(PyTypeError::new_err(format!(
+                "filter {path:?} has no QoS, pass a (path, qos) tuple or a SubscribeFilter"
+            ))),
+        };
+    }
+    if let Ok((path, qos)) = filter.extract::<(String, &PyAny)>() {
+        let qos: QoSArg = qos.extract()?;
+        return Ok(::mqttbytes::v4::SubscribeFilter::new(path, qos.into()));
+    }
+    Err(PyTypeError::new_err(format!(
+        "expected a SubscribeFilter or a (path, qos) tuple, got {}",
+        filter.get_type().name()?
+    )))
+}
+
 /// Subscription filter.
 #[pyclass(module = "mqttbytes.v4")]
 #[derive(Clone)]
-pub struct SubscribeFilter(::mqttbytes::v4::SubscribeFilter);
+pub struct SubscribeFilter(pub ::mqttbytes::v4::SubscribeFilter);
 
 #[pymethods]
 impl SubscribeFilter {
     #[new]
-    fn new(path: String, qos: QoS) -> Self {
+    fn new(path: String, qos: QoSArg) -> Self {
         ::mqttbytes::v4::SubscribeFilter::new(path, qos.into()).into()
     }
 
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SubscribeFilter(path={}, qos=QoS.{:?})",
+            repr_str(&self.0.path),
+            self.0.qos
+        )
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(_py);
+        dict.set_item("path", &self.0.path)?;
+        dict.set_item("qos", self.0.qos as u8)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        Ok(::mqttbytes::v4::SubscribeFilter::new(
+            required::<String>(data, "path")?,
+            required_qos(data, "qos")?,
+        )
+        .into())
+    }
+
     fn __len__(&self) -> usize {
         self.0.len()
     }
@@ -112,7 +339,7 @@ impl SubscribeFilter {
     }
 
     #[setter]
-    fn set_qos(&mut self, qos: QoS) {
+    fn set_qos(&mut self, qos: QoSArg) {
         self.0.qos = qos.into();
     }
 }
@@ -124,8 +351,40 @@ impl From<::mqttbytes::v4::SubscribeFilter> for SubscribeFilter {
 }
 
 #[pyclass(module = "mqttbytes.v4")]
+#[derive(Clone)]
 pub enum RetainForwardRule {
     OnEverySubscribe,
     OnNewSubscribe,
     Never,
 }
+
+#[pymethods]
+impl RetainForwardRule {
+    fn __hash__(&self) -> u64 {
+        self.clone() as u64
+    }
+}
+
+impl From<::mqttbytes::v4::RetainForwardRule> for RetainForwardRule {
+    fn from(rule: ::mqttbytes::v4::RetainForwardRule) -> Self {
+        match rule {
+            ::mqttbytes::v4::RetainForwardRule::OnEverySubscribe => {
+                RetainForwardRule::OnEverySubscribe
+            }
+            ::mqttbytes::v4::RetainForwardRule::OnNewSubscribe => RetainForwardRule::OnNewSubscribe,
+            ::mqttbytes::v4::RetainForwardRule::Never => RetainForwardRule::Never,
+        }
+    }
+}
+
+impl From<RetainForwardRule> for ::mqttbytes::v4::RetainForwardRule {
+    fn from(rule: RetainForwardRule) -> Self {
+        match rule {
+            RetainForwardRule::OnEverySubscribe => {
+                ::mqttbytes::v4::RetainForwardRule::OnEverySubscribe
+            }
+            RetainForwardRule::OnNewSubscribe => ::mqttbytes::v4::RetainForwardRule::OnNewSubscribe,
+            RetainForwardRule::Never => ::mqttbytes::v4::RetainForwardRule::Never,
+        }
+    }
+}
@@ -1,29 +1,118 @@
 use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::{PyIndexError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::pyclass::CompareOp;
+use pyo3::types::{PyBytes, PyDict, PyString};
 
-use crate::{FixedHeader, WrapperMqttBytesError};
+use super::{reduce, validate, write_into};
+use crate::dict::{check_type, optional, packet_dict, required};
+use crate::repr::repr_str;
+use crate::{richcmp, FixedHeader, WrapperMqttBytesError};
 
 /// Unsubscribe packet.
+///
+/// Behaves as a list of its topic filters: it can be iterated, reversed,
+/// indexed and tested with `in`, and filters are added with `append` and
+/// removed with `remove`. `len(unsubscribe.topics)` is the number of filters.
 #[pyclass(module = "mqttbytes.v4")]
-pub struct Unsubscribe(::mqttbytes::v4::Unsubscribe);
+pub struct Unsubscribe(pub ::mqttbytes::v4::Unsubscribe);
 
 #[pymethods]
 impl Unsubscribe {
+    /// `topic` is a single topic filter or an iterable of them.
     #[new]
-    fn new(topic: String) -> Self {
-        ::mqttbytes::v4::Unsubscribe::new(topic).into()
+    #[args(topic = "None", pkid = "0")]
+    fn new(topic: Option<&PyAny>, pkid: u16) -> PyResult<Self> {
+        let topics = match topic {
+            None => Vec::new(),
+            Some(topic) if topic.is_instance_of::<PyString>()? => vec![topic.extract()?],
+            Some(topics) => match topics.iter() {
+                Ok(topics) => topics
+                    .map(|topic| extract_topic(topic?))
+                    .collect::<PyResult<_>>()?,
+                Err(_) => {
+                    return Err(PyTypeError::new_err(format!(
+                        "expected a topic filter or an iterable of them, got {}",
+                        topics.get_type().name()?
+                    )))
+                }
+            },
+        };
+        Ok(::mqttbytes::v4::Unsubscribe { pkid, topics }.into())
+    }
+
+    fn append(&mut self, topic: String) {
+        self.0.topics.push(topic);
+    }
+
+    /// Removes the first occurrence of `topic`. Raises `ValueError` if there
+    /// is none.
+    fn remove(&mut self, topic: &str) -> PyResult<()> {
+        match self.0.topics.iter().position(|other| other == topic) {
+            Some(index) => {
+                self.0.topics.remove(index);
+                Ok(())
+            }
+            None => Err(PyValueError::new_err(format!(
+                "Unsubscribe has no topic filter {}",
+                repr_str(topic)
+            ))),
+        }
+    }
+
+    /// False for objects that are not strings.
+    fn __contains__(&self, topic: &PyAny) -> bool {
+        match topic.extract::<&str>() {
+            Ok(topic) => self.0.topics.iter().any(|other| other == topic),
+            Err(_) => false,
+        }
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<String> {
+        let len = self.0.topics.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&index) {
+            return Err(PyIndexError::new_err(format!(
+                "Unsubscribe topic index out of range, it has {len} topics"
+            )));
+        }
+        Ok(self.0.topics[index as usize].clone())
+    }
+
+    fn __iter__(&self, _py: Python) -> PyResult<PyObject> {
+        self.get_topics().into_py(_py).call_method0(_py, "__iter__")
+    }
+
+    fn __reversed__(&self, _py: Python) -> PyResult<PyObject> {
+        let mut topics = self.get_topics();
+        topics.reverse();
+        topics.into_py(_py).call_method0(_py, "__iter__")
     }
 
     #[staticmethod]
-    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
-        ::mqttbytes::v4::Unsubscribe::read(fixed_header.0, bytes.into())
-            .map(Into::into)
-            .map_err(Into::into)
+    #[args(strict = "false")]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>, strict: bool) -> PyResult<Self> {
+        let packet = ::mqttbytes::v4::Unsubscribe::read(fixed_header.0, bytes.into())
+            .map_err(WrapperMqttBytesError::from)?;
+        if strict {
+            validate::unsubscribe(&packet)?;
+        }
+        Ok(packet.into())
+    }
+
+    /// Checks the packet id, and that there is at least one filter and every
+    /// filter is valid.
+    fn validate(&self) -> PyResult<()> {
+        validate::unsubscribe(&self.0)
     }
 
     // Rewrite write method to return Python bytes instead of Vec<u8>
-    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+    #[args(strict = "false")]
+    fn write(&self, _py: Python, strict: bool) -> PyResult<Py<PyBytes>> {
+        if strict {
+            self.validate()?;
+        }
         let mut buffer: BytesMut = BytesMut::new();
         self.0
             .write(&mut buffer)
@@ -31,6 +120,53 @@ impl Unsubscribe {
         Ok(PyBytes::new(_py, &buffer).into())
     }
 
+    #[args(offset = "0")]
+    fn write_into(&self, _py: Python, buffer: PyBuffer<u8>, offset: usize) -> PyResult<usize> {
+        write_into(_py, &buffer, offset, |bytes| self.0.write(bytes))
+    }
+
+    fn __reduce__<'py>(&self, _py: Python<'py>) -> PyResult<(PyObject, (&'py PyDict,))> {
+        reduce(_py, self.to_dict(_py)?)
+    }
+
+    fn __copy__(&self) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __deepcopy__(&self, _memo: &PyAny) -> Self {
+        Self(self.0.clone())
+    }
+
+    fn __richcmp__(&self, other: PyRef<Self>, op: CompareOp, _py: Python) -> PyObject {
+        richcmp(_py, &self.0, &other.0, op)
+    }
+
+    fn __repr__(&self) -> String {
+        let topics: Vec<String> = self.0.topics.iter().map(|topic| repr_str(topic)).collect();
+        format!(
+            "Unsubscribe(pkid={}, topics=[{}])",
+            self.0.pkid,
+            topics.join(", ")
+        )
+    }
+
+    fn to_dict<'py>(&self, _py: Python<'py>) -> PyResult<&'py PyDict> {
+        let dict = packet_dict(_py, "Unsubscribe")?;
+        dict.set_item("pkid", self.0.pkid)?;
+        dict.set_item("topics", &self.0.topics)?;
+        Ok(dict)
+    }
+
+    #[staticmethod]
+    pub(crate) fn from_dict(data: &PyDict) -> PyResult<Self> {
+        check_type(data, "Unsubscribe")?;
+        Ok(::mqttbytes::v4::Unsubscribe {
+            pkid: optional(data, "pkid")?.unwrap_or_default(),
+            topics: required(data, "topics")?,
+        }
+        .into())
+    }
+
     #[getter]
     fn get_pkid(&self) -> u16 {
         self.0.pkid
@@ -57,3 +193,13 @@ impl From<::mqttbytes::v4::Unsubscribe> for Unsubscribe {
         Self(p)
     }
 }
+
+fn extract_topic(topic: &PyAny) -> PyResult<String> {
+    if !topic.is_instance_of::<PyString>()? {
+        return Err(PyTypeError::new_err(format!(
+            "expected a topic filter str, got {}",
+            topic.get_type().name()?
+        )));
+    }
+    topic.extract()
+}
@@ -0,0 +1,178 @@
+//! Checks of MQTT 3.1.1 rules that upstream does not enforce when encoding or
+//! decoding packets, used by `validate()` and the `strict` flag of `read` and
+//! `write`.
+
+use ::mqttbytes::v4::Packet;
+use ::mqttbytes::QoS;
+use pyo3::prelude::*;
+
+use crate::ValidationError;
+
+/// Longest string or binary field, whose length is encoded on two bytes.
+const MAX_FIELD_LEN: usize = u16::MAX as usize;
+
+fn invalid(message: String) -> PyResult<()> {
+    Err(ValidationError::new_err(message))
+}
+
+fn length(packet: &str, field: &str, len: usize) -> PyResult<()> {
+    if len > MAX_FIELD_LEN {
+        return invalid(format!(
+            "{packet} {field} is {len} bytes long, at most {MAX_FIELD_LEN} are allowed"
+        ));
+    }
+    Ok(())
+}
+
+fn string(packet: &str, field: &str, value: &str) -> PyResult<()> {
+    length(packet, field, value.len())?;
+    if value.contains('\0') {
+        return invalid(format!(
+            "{packet} {field} {value:?} contains a null character"
+        ));
+    }
+    Ok(())
+}
+
+fn topic(packet: &str, value: &str) -> PyResult<()> {
+    string(packet, "topic", value)?;
+    if value.is_empty() {
+        return invalid(format!("{packet} topic is empty"));
+    }
+    if !::mqttbytes::valid_topic(value) {
+        return invalid(format!("{packet} topic {value:?} contains wildcards"));
+    }
+    Ok(())
+}
+
+fn filter(packet: &str, value: &str) -> PyResult<()> {
+    string(packet, "filter", value)?;
+    if !::mqttbytes::valid_filter(value) {
+        return invalid(format!(
+            "{packet} filter {value:?} is not a valid topic filter"
+        ));
+    }
+    Ok(())
+}
+
+fn pkid(packet: &str, pkid: u16) -> PyResult<()> {
+    if pkid == 0 {
+        return invalid(format!("{packet} needs a non-zero packet id"));
+    }
+    Ok(())
+}
+
+pub(crate) fn connect(connect: &::mqttbytes::v4::Connect) -> PyResult<()> {
+    string("Connect", "client_id", &connect.client_id)?;
+    if connect.client_id.is_empty() && !connect.clean_session {
+        return invalid("Connect with an empty client_id needs clean_session".to_owned());
+    }
+    if let Some(last_will) = &connect.last_will {
+        topic("LastWill", &last_will.topic)?;
+        length("LastWill", "message", last_will.message.len())?;
+    }
+    if let Some(login) = &connect.login {
+        string("Login", "username", &login.username)?;
+        string("Login", "password", &login.password)?;
+        if login.username.is_empty() && !login.password.is_empty() {
+            return invalid("Login password needs a username".to_owned());
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn connack(connack: &::mqttbytes::v4::ConnAck) -> PyResult<()> {
+    if connack.session_present && connack.code != ::mqttbytes::v4::ConnectReturnCode::Success {
+        return invalid(format!(
+            "ConnAck with code {:?} cannot have session_present",
+            connack.code
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn publish(publish: &::mqttbytes::v4::Publish) -> PyResult<()> {
+    topic("Publish", &publish.topic)?;
+    match publish.qos {
+        QoS::AtMostOnce if publish.pkid != 0 => invalid(format!(
+            "Publish with QoS AtMostOnce must have packet id 0, got {}",
+            publish.pkid
+        )),
+        QoS::AtMostOnce if publish.dup => {
+            invalid("Publish with QoS AtMostOnce cannot have dup set".to_owned())
+        }
+        QoS::AtMostOnce => Ok(()),
+        qos if publish.pkid == 0 => invalid(format!(
+            "Publish with QoS {qos:?} needs a non-zero packet id"
+        )),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn puback(puback: &::mqttbytes::v4::PubAck) -> PyResult<()> {
+    pkid("PubAck", puback.pkid)
+}
+
+pub(crate) fn pubrec(pubrec: &::mqttbytes::v4::PubRec) -> PyResult<()> {
+    pkid("PubRec", pubrec.pkid)
+}
+
+pub(crate) fn pubrel(pubrel: &::mqttbytes::v4::PubRel) -> PyResult<()> {
+    pkid("PubRel", pubrel.pkid)
+}
+
+pub(crate) fn pubcomp(pubcomp: &::mqttbytes::v4::PubComp) -> PyResult<()> {
+    pkid("PubComp", pubcomp.pkid)
+}
+
+pub(crate) fn subscribe(subscribe: &::mqttbytes::v4::Subscribe) -> PyResult<()> {
+    pkid("Subscribe", subscribe.pkid)?;
+    if subscribe.filters.is_empty() {
+        return invalid("Subscribe has no filters".to_owned());
+    }
+    subscribe
+        .filters
+        .iter()
+        .try_for_each(|subscribe_filter| filter("Subscribe", &subscribe_filter.path))
+}
+
+pub(crate) fn suback(suback: &::mqttbytes::v4::SubAck) -> PyResult<()> {
+    pkid("SubAck", suback.pkid)?;
+    if suback.return_codes.is_empty() {
+        return invalid("SubAck has no return codes".to_owned());
+    }
+    Ok(())
+}
+
+pub(crate) fn unsubscribe(unsubscribe: &::mqttbytes::v4::Unsubscribe) -> PyResult<()> {
+    pkid("Unsubscribe", unsubscribe.pkid)?;
+    if unsubscribe.topics.is_empty() {
+        return invalid("Unsubscribe has no filters".to_owned());
+    }
+    unsubscribe
+        .topics
+        .iter()
+        .try_for_each(|topic| filter("Unsubscribe", topic))
+}
+
+pub(crate) fn unsuback(unsuback: &::mqttbytes::v4::UnsubAck) -> PyResult<()> {
+    pkid("UnsubAck", unsuback.pkid)
+}
+
+/// Validates a packet of any v4 type.
+pub(crate) fn packet(packet: &Packet) -> PyResult<()> {
+    match packet {
+        Packet::Connect(packet) => connect(packet),
+        Packet::ConnAck(packet) => connack(packet),
+        Packet::Publish(packet) => publish(packet),
+        Packet::PubAck(packet) => puback(packet),
+        Packet::PubRec(packet) => pubrec(packet),
+        Packet::PubRel(packet) => pubrel(packet),
+        Packet::PubComp(packet) => pubcomp(packet),
+        Packet::Subscribe(packet) => subscribe(packet),
+        Packet::SubAck(packet) => suback(packet),
+        Packet::Unsubscribe(packet) => unsubscribe(packet),
+        Packet::UnsubAck(packet) => unsuback(packet),
+        Packet::PingReq | Packet::PingResp | Packet::Disconnect => Ok(()),
+    }
+}
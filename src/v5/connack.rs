@@ -0,0 +1,406 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// Acknowledgement to connect packet.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct ConnAck(::mqttbytes::v5::ConnAck);
+
+#[pymethods]
+impl ConnAck {
+    #[new]
+    fn new(code: ConnectReturnCode, session_present: bool) -> Self {
+        ::mqttbytes::v5::ConnAck::new(code.into(), session_present).into()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::ConnAck::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_session_present(&self) -> bool {
+        self.0.session_present
+    }
+
+    #[setter]
+    fn set_session_present(&mut self, session_present: bool) {
+        self.0.session_present = session_present;
+    }
+
+    #[getter]
+    fn get_code(&self) -> ConnectReturnCode {
+        self.0.code.into()
+    }
+
+    #[setter]
+    fn set_code(&mut self, code: ConnectReturnCode) {
+        self.0.code = code.into();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<ConnAckProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<ConnAckProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::ConnAck> for ConnAck {
+    fn from(connack: ::mqttbytes::v5::ConnAck) -> Self {
+        Self(connack)
+    }
+}
+
+/// Properties of the connack packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct ConnAckProperties(::mqttbytes::v5::ConnAckProperties);
+
+#[pymethods]
+impl ConnAckProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::ConnAckProperties::new().into()
+    }
+
+    #[getter]
+    fn get_session_expiry_interval(&self) -> Option<u32> {
+        self.0.session_expiry_interval
+    }
+
+    #[setter]
+    fn set_session_expiry_interval(&mut self, session_expiry_interval: Option<u32>) {
+        self.0.session_expiry_interval = session_expiry_interval;
+    }
+
+    #[getter]
+    fn get_receive_max(&self) -> Option<u16> {
+        self.0.receive_max
+    }
+
+    #[setter]
+    fn set_receive_max(&mut self, receive_max: Option<u16>) {
+        self.0.receive_max = receive_max;
+    }
+
+    #[getter]
+    fn get_max_qos(&self) -> Option<u8> {
+        self.0.max_qos
+    }
+
+    #[setter]
+    fn set_max_qos(&mut self, max_qos: Option<u8>) {
+        self.0.max_qos = max_qos;
+    }
+
+    #[getter]
+    fn get_retain_available(&self) -> Option<u8> {
+        self.0.retain_available
+    }
+
+    #[setter]
+    fn set_retain_available(&mut self, retain_available: Option<u8>) {
+        self.0.retain_available = retain_available;
+    }
+
+    #[getter]
+    fn get_max_packet_size(&self) -> Option<u32> {
+        self.0.max_packet_size
+    }
+
+    #[setter]
+    fn set_max_packet_size(&mut self, max_packet_size: Option<u32>) {
+        self.0.max_packet_size = max_packet_size;
+    }
+
+    #[getter]
+    fn get_assigned_client_identifier(&self) -> Option<String> {
+        self.0.assigned_client_identifier.clone()
+    }
+
+    #[setter]
+    fn set_assigned_client_identifier(&mut self, assigned_client_identifier: Option<String>) {
+        self.0.assigned_client_identifier = assigned_client_identifier;
+    }
+
+    #[getter]
+    fn get_topic_alias_max(&self) -> Option<u16> {
+        self.0.topic_alias_max
+    }
+
+    #[setter]
+    fn set_topic_alias_max(&mut self, topic_alias_max: Option<u16>) {
+        self.0.topic_alias_max = topic_alias_max;
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+
+    #[getter]
+    fn get_wildcard_subscription_available(&self) -> Option<u8> {
+        self.0.wildcard_subscription_available
+    }
+
+    #[setter]
+    fn set_wildcard_subscription_available(&mut self, wildcard_subscription_available: Option<u8>) {
+        self.0.wildcard_subscription_available = wildcard_subscription_available;
+    }
+
+    #[getter]
+    fn get_subscription_identifiers_available(&self) -> Option<u8> {
+        self.0.subscription_identifiers_available
+    }
+
+    #[setter]
+    fn set_subscription_identifiers_available(
+        &mut self,
+        subscription_identifiers_available: Option<u8>,
+    ) {
+        self.0.subscription_identifiers_available = subscription_identifiers_available;
+    }
+
+    #[getter]
+    fn get_shared_subscription_available(&self) -> Option<u8> {
+        self.0.shared_subscription_available
+    }
+
+    #[setter]
+    fn set_shared_subscription_available(&mut self, shared_subscription_available: Option<u8>) {
+        self.0.shared_subscription_available = shared_subscription_available;
+    }
+
+    #[getter]
+    fn get_server_keep_alive(&self) -> Option<u16> {
+        self.0.server_keep_alive
+    }
+
+    #[setter]
+    fn set_server_keep_alive(&mut self, server_keep_alive: Option<u16>) {
+        self.0.server_keep_alive = server_keep_alive;
+    }
+
+    #[getter]
+    fn get_response_information(&self) -> Option<String> {
+        self.0.response_information.clone()
+    }
+
+    #[setter]
+    fn set_response_information(&mut self, response_information: Option<String>) {
+        self.0.response_information = response_information;
+    }
+
+    #[getter]
+    fn get_server_reference(&self) -> Option<String> {
+        self.0.server_reference.clone()
+    }
+
+    #[setter]
+    fn set_server_reference(&mut self, server_reference: Option<String>) {
+        self.0.server_reference = server_reference;
+    }
+
+    #[getter]
+    fn get_authentication_method(&self) -> Option<String> {
+        self.0.authentication_method.clone()
+    }
+
+    #[setter]
+    fn set_authentication_method(&mut self, authentication_method: Option<String>) {
+        self.0.authentication_method = authentication_method;
+    }
+
+    #[getter]
+    fn get_authentication_data(&self) -> Option<Vec<u8>> {
+        self.0
+            .authentication_data
+            .as_ref()
+            .map(|authentication_data| authentication_data.to_vec())
+    }
+
+    #[setter]
+    fn set_authentication_data(&mut self, authentication_data: Option<Vec<u8>>) {
+        self.0.authentication_data = authentication_data.map(Into::into);
+    }
+}
+
+impl From<::mqttbytes::v5::ConnAckProperties> for ConnAckProperties {
+    fn from(properties: ::mqttbytes::v5::ConnAckProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Return code in connack.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum ConnectReturnCode {
+    Success = 0,
+    UnspecifiedError = 128,
+    MalformedPacket = 129,
+    ProtocolError = 130,
+    ImplementationSpecificError = 131,
+    UnsupportedProtocolVersion = 132,
+    ClientIdentifierNotValid = 133,
+    BadUserNamePassword = 134,
+    NotAuthorized = 135,
+    ServerUnavailable = 136,
+    ServerBusy = 137,
+    Banned = 138,
+    BadAuthenticationMethod = 140,
+    TopicNameInvalid = 144,
+    PacketTooLarge = 149,
+    QuotaExceeded = 151,
+    PayloadFormatInvalid = 153,
+    RetainNotSupported = 154,
+    QoSNotSupported = 155,
+    UseAnotherServer = 156,
+    ServerMoved = 157,
+    ConnectionRateExceeded = 159,
+}
+
+impl From<::mqttbytes::v5::ConnectReturnCode> for ConnectReturnCode {
+    fn from(code: ::mqttbytes::v5::ConnectReturnCode) -> Self {
+        match code {
+            ::mqttbytes::v5::ConnectReturnCode::Success => ConnectReturnCode::Success,
+            ::mqttbytes::v5::ConnectReturnCode::UnspecifiedError => {
+                ConnectReturnCode::UnspecifiedError
+            }
+            ::mqttbytes::v5::ConnectReturnCode::MalformedPacket => {
+                ConnectReturnCode::MalformedPacket
+            }
+            ::mqttbytes::v5::ConnectReturnCode::ProtocolError => ConnectReturnCode::ProtocolError,
+            ::mqttbytes::v5::ConnectReturnCode::ImplementationSpecificError => {
+                ConnectReturnCode::ImplementationSpecificError
+            }
+            ::mqttbytes::v5::ConnectReturnCode::UnsupportedProtocolVersion => {
+                ConnectReturnCode::UnsupportedProtocolVersion
+            }
+            ::mqttbytes::v5::ConnectReturnCode::ClientIdentifierNotValid => {
+                ConnectReturnCode::ClientIdentifierNotValid
+            }
+            ::mqttbytes::v5::ConnectReturnCode::BadUserNamePassword => {
+                ConnectReturnCode::BadUserNamePassword
+            }
+            ::mqttbytes::v5::ConnectReturnCode::NotAuthorized => ConnectReturnCode::NotAuthorized,
+            ::mqttbytes::v5::ConnectReturnCode::ServerUnavailable => {
+                ConnectReturnCode::ServerUnavailable
+            }
+            ::mqttbytes::v5::ConnectReturnCode::ServerBusy => ConnectReturnCode::ServerBusy,
+            ::mqttbytes::v5::ConnectReturnCode::Banned => ConnectReturnCode::Banned,
+            ::mqttbytes::v5::ConnectReturnCode::BadAuthenticationMethod => {
+                ConnectReturnCode::BadAuthenticationMethod
+            }
+            ::mqttbytes::v5::ConnectReturnCode::TopicNameInvalid => {
+                ConnectReturnCode::TopicNameInvalid
+            }
+            ::mqttbytes::v5::ConnectReturnCode::PacketTooLarge => ConnectReturnCode::PacketTooLarge,
+            ::mqttbytes::v5::ConnectReturnCode::QuotaExceeded => ConnectReturnCode::QuotaExceeded,
+            ::mqttbytes::v5::ConnectReturnCode::PayloadFormatInvalid => {
+                ConnectReturnCode::PayloadFormatInvalid
+            }
+            ::mqttbytes::v5::ConnectReturnCode::RetainNotSupported => {
+                ConnectReturnCode::RetainNotSupported
+            }
+            ::mqttbytes::v5::ConnectReturnCode::QoSNotSupported => {
+                ConnectReturnCode::QoSNotSupported
+            }
+            ::mqttbytes::v5::ConnectReturnCode::UseAnotherServer => {
+                ConnectReturnCode::UseAnotherServer
+            }
+            ::mqttbytes::v5::ConnectReturnCode::ServerMoved => ConnectReturnCode::ServerMoved,
+            ::mqttbytes::v5::ConnectReturnCode::ConnectionRateExceeded => {
+                ConnectReturnCode::ConnectionRateExceeded
+            }
+        }
+    }
+}
+
+impl From<ConnectReturnCode> for ::mqttbytes::v5::ConnectReturnCode {
+    fn from(code: ConnectReturnCode) -> Self {
+        match code {
+            ConnectReturnCode::Success => ::mqttbytes::v5::ConnectReturnCode::Success,
+            ConnectReturnCode::UnspecifiedError => {
+                ::mqttbytes::v5::ConnectReturnCode::UnspecifiedError
+            }
+            ConnectReturnCode::MalformedPacket => {
+                ::mqttbytes::v5::ConnectReturnCode::MalformedPacket
+            }
+            ConnectReturnCode::ProtocolError => ::mqttbytes::v5::ConnectReturnCode::ProtocolError,
+            ConnectReturnCode::ImplementationSpecificError => {
+                ::mqttbytes::v5::ConnectReturnCode::ImplementationSpecificError
+            }
+            ConnectReturnCode::UnsupportedProtocolVersion => {
+                ::mqttbytes::v5::ConnectReturnCode::UnsupportedProtocolVersion
+            }
+            ConnectReturnCode::ClientIdentifierNotValid => {
+                ::mqttbytes::v5::ConnectReturnCode::ClientIdentifierNotValid
+            }
+            ConnectReturnCode::BadUserNamePassword => {
+                ::mqttbytes::v5::ConnectReturnCode::BadUserNamePassword
+            }
+            ConnectReturnCode::NotAuthorized => ::mqttbytes::v5::ConnectReturnCode::NotAuthorized,
+            ConnectReturnCode::ServerUnavailable => {
+                ::mqttbytes::v5::ConnectReturnCode::ServerUnavailable
+            }
+            ConnectReturnCode::ServerBusy => ::mqttbytes::v5::ConnectReturnCode::ServerBusy,
+            ConnectReturnCode::Banned => ::mqttbytes::v5::ConnectReturnCode::Banned,
+            ConnectReturnCode::BadAuthenticationMethod => {
+                ::mqttbytes::v5::ConnectReturnCode::BadAuthenticationMethod
+            }
+            ConnectReturnCode::TopicNameInvalid => {
+                ::mqttbytes::v5::ConnectReturnCode::TopicNameInvalid
+            }
+            ConnectReturnCode::PacketTooLarge => ::mqttbytes::v5::ConnectReturnCode::PacketTooLarge,
+            ConnectReturnCode::QuotaExceeded => ::mqttbytes::v5::ConnectReturnCode::QuotaExceeded,
+            ConnectReturnCode::PayloadFormatInvalid => {
+                ::mqttbytes::v5::ConnectReturnCode::PayloadFormatInvalid
+            }
+            ConnectReturnCode::RetainNotSupported => {
+                ::mqttbytes::v5::ConnectReturnCode::RetainNotSupported
+            }
+            ConnectReturnCode::QoSNotSupported => {
+                ::mqttbytes::v5::ConnectReturnCode::QoSNotSupported
+            }
+            ConnectReturnCode::UseAnotherServer => {
+                ::mqttbytes::v5::ConnectReturnCode::UseAnotherServer
+            }
+            ConnectReturnCode::ServerMoved => ::mqttbytes::v5::ConnectReturnCode::ServerMoved,
+            ConnectReturnCode::ConnectionRateExceeded => {
+                ::mqttbytes::v5::ConnectReturnCode::ConnectionRateExceeded
+            }
+        }
+    }
+}
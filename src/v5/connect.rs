@@ -0,0 +1,456 @@
+use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::payload::{buffer_to_bytes, memoryview};
+use crate::{FixedHeader, Protocol, QoS, WrapperMqttBytesError};
+
+/// Connection packet initiated by the client.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct Connect(::mqttbytes::v5::Connect);
+
+#[pymethods]
+impl Connect {
+    #[new]
+    fn new(id: String) -> Self {
+        ::mqttbytes::v5::Connect::new(id).into()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::Connect::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_protocol(&self) -> Protocol {
+        self.0.protocol.into()
+    }
+
+    #[setter]
+    fn set_protocol(&mut self, protocol: Protocol) {
+        self.0.protocol = protocol.into();
+    }
+
+    #[getter]
+    fn get_keep_alive(&self) -> u16 {
+        self.0.keep_alive
+    }
+
+    #[setter]
+    fn set_keep_alive(&mut self, keep_alive: u16) {
+        self.0.keep_alive = keep_alive;
+    }
+
+    #[getter]
+    fn get_client_id(&self) -> String {
+        self.0.client_id.clone()
+    }
+
+    #[setter]
+    fn set_client_id(&mut self, client_id: String) {
+        self.0.client_id = client_id;
+    }
+
+    #[getter]
+    fn get_clean_session(&self) -> bool {
+        self.0.clean_session
+    }
+
+    #[setter]
+    fn set_clean_session(&mut self, clean_session: bool) {
+        self.0.clean_session = clean_session;
+    }
+
+    #[getter]
+    fn get_last_will(&self) -> Option<LastWill> {
+        self.0.last_will.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_last_will(&mut self, last_will: Option<LastWill>) {
+        self.0.last_will = last_will.map(|last_will| last_will.0);
+    }
+
+    #[getter]
+    fn get_login(&self) -> Option<Login> {
+        self.0.login.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_login(&mut self, login: Option<Login>) {
+        self.0.login = login.map(|login| login.0);
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<ConnectProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<ConnectProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::Connect> for Connect {
+    fn from(connect: ::mqttbytes::v5::Connect) -> Self {
+        Self(connect)
+    }
+}
+
+/// LastWill that broker forwards on behalf of the client.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct LastWill(::mqttbytes::v5::LastWill);
+
+#[pymethods]
+impl LastWill {
+    #[new]
+    fn new(
+        _py: Python,
+        topic: String,
+        payload: PyBuffer<u8>,
+        qos: QoS,
+        retain: bool,
+    ) -> PyResult<Self> {
+        let mut last_will = ::mqttbytes::v5::LastWill::new(topic, Vec::new(), qos.into(), retain);
+        last_will.message = buffer_to_bytes(_py, &payload)?;
+        Ok(last_will.into())
+    }
+
+    #[getter]
+    fn get_topic(&self) -> String {
+        self.0.topic.clone()
+    }
+
+    #[setter]
+    fn set_topic(&mut self, topic: String) {
+        self.0.topic = topic;
+    }
+
+    /// Read-only `memoryview` sharing the memory of the message.
+    #[getter]
+    fn get_message(&self, _py: Python) -> PyResult<PyObject> {
+        memoryview(_py, self.0.message.clone())
+    }
+
+    /// Accepts any object supporting the buffer protocol.
+    #[setter]
+    fn set_message(&mut self, _py: Python, message: PyBuffer<u8>) -> PyResult<()> {
+        self.0.message = buffer_to_bytes(_py, &message)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn get_qos(&self) -> QoS {
+        self.0.qos.into()
+    }
+
+    #[setter]
+    fn set_qos(&mut self, qos: QoS) {
+        self.0.qos = qos.into();
+    }
+
+    #[getter]
+    fn get_retain(&self) -> bool {
+        self.0.retain
+    }
+
+    #[setter]
+    fn set_retain(&mut self, retain: bool) {
+        self.0.retain = retain;
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<WillProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<WillProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::LastWill> for LastWill {
+    fn from(last_will: ::mqttbytes::v5::LastWill) -> Self {
+        Self(last_will)
+    }
+}
+
+/// Properties of the LastWill.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct WillProperties(::mqttbytes::v5::WillProperties);
+
+#[pymethods]
+impl WillProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::WillProperties {
+            delay_interval: None,
+            payload_format_indicator: None,
+            message_expiry_interval: None,
+            content_type: None,
+            response_topic: None,
+            correlation_data: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_delay_interval(&self) -> Option<u32> {
+        self.0.delay_interval
+    }
+
+    #[setter]
+    fn set_delay_interval(&mut self, delay_interval: Option<u32>) {
+        self.0.delay_interval = delay_interval;
+    }
+
+    #[getter]
+    fn get_payload_format_indicator(&self) -> Option<u8> {
+        self.0.payload_format_indicator
+    }
+
+    #[setter]
+    fn set_payload_format_indicator(&mut self, payload_format_indicator: Option<u8>) {
+        self.0.payload_format_indicator = payload_format_indicator;
+    }
+
+    #[getter]
+    fn get_message_expiry_interval(&self) -> Option<u32> {
+        self.0.message_expiry_interval
+    }
+
+    #[setter]
+    fn set_message_expiry_interval(&mut self, message_expiry_interval: Option<u32>) {
+        self.0.message_expiry_interval = message_expiry_interval;
+    }
+
+    #[getter]
+    fn get_content_type(&self) -> Option<String> {
+        self.0.content_type.clone()
+    }
+
+    #[setter]
+    fn set_content_type(&mut self, content_type: Option<String>) {
+        self.0.content_type = content_type;
+    }
+
+    #[getter]
+    fn get_response_topic(&self) -> Option<String> {
+        self.0.response_topic.clone()
+    }
+
+    #[setter]
+    fn set_response_topic(&mut self, response_topic: Option<String>) {
+        self.0.response_topic = response_topic;
+    }
+
+    #[getter]
+    fn get_correlation_data(&self) -> Option<Vec<u8>> {
+        self.0
+            .correlation_data
+            .as_ref()
+            .map(|correlation_data| correlation_data.to_vec())
+    }
+
+    #[setter]
+    fn set_correlation_data(&mut self, correlation_data: Option<Vec<u8>>) {
+        self.0.correlation_data = correlation_data.map(Into::into);
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::WillProperties> for WillProperties {
+    fn from(properties: ::mqttbytes::v5::WillProperties) -> Self {
+        Self(properties)
+    }
+}
+
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct Login(::mqttbytes::v5::Login);
+
+#[pymethods]
+impl Login {
+    #[new]
+    fn new(u: String, p: String) -> Self {
+        ::mqttbytes::v5::Login::new(u, p).into()
+    }
+
+    #[getter]
+    fn get_username(&self) -> String {
+        self.0.username.clone()
+    }
+
+    #[setter]
+    fn set_username(&mut self, username: String) {
+        self.0.username = username;
+    }
+
+    #[getter]
+    fn get_password(&self) -> String {
+        self.0.password.clone()
+    }
+
+    #[setter]
+    fn set_password(&mut self, password: String) {
+        self.0.password = password;
+    }
+}
+
+impl From<::mqttbytes::v5::Login> for Login {
+    fn from(login: ::mqttbytes::v5::Login) -> Self {
+        Self(login)
+    }
+}
+
+/// Properties of the connect packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct ConnectProperties(::mqttbytes::v5::ConnectProperties);
+
+#[pymethods]
+impl ConnectProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::ConnectProperties {
+            session_expiry_interval: None,
+            receive_maximum: None,
+            max_packet_size: None,
+            topic_alias_max: None,
+            request_response_info: None,
+            request_problem_info: None,
+            user_properties: Vec::new(),
+            authentication_method: None,
+            authentication_data: None,
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_session_expiry_interval(&self) -> Option<u32> {
+        self.0.session_expiry_interval
+    }
+
+    #[setter]
+    fn set_session_expiry_interval(&mut self, session_expiry_interval: Option<u32>) {
+        self.0.session_expiry_interval = session_expiry_interval;
+    }
+
+    #[getter]
+    fn get_receive_maximum(&self) -> Option<u16> {
+        self.0.receive_maximum
+    }
+
+    #[setter]
+    fn set_receive_maximum(&mut self, receive_maximum: Option<u16>) {
+        self.0.receive_maximum = receive_maximum;
+    }
+
+    #[getter]
+    fn get_max_packet_size(&self) -> Option<u32> {
+        self.0.max_packet_size
+    }
+
+    #[setter]
+    fn set_max_packet_size(&mut self, max_packet_size: Option<u32>) {
+        self.0.max_packet_size = max_packet_size;
+    }
+
+    #[getter]
+    fn get_topic_alias_max(&self) -> Option<u16> {
+        self.0.topic_alias_max
+    }
+
+    #[setter]
+    fn set_topic_alias_max(&mut self, topic_alias_max: Option<u16>) {
+        self.0.topic_alias_max = topic_alias_max;
+    }
+
+    #[getter]
+    fn get_request_response_info(&self) -> Option<u8> {
+        self.0.request_response_info
+    }
+
+    #[setter]
+    fn set_request_response_info(&mut self, request_response_info: Option<u8>) {
+        self.0.request_response_info = request_response_info;
+    }
+
+    #[getter]
+    fn get_request_problem_info(&self) -> Option<u8> {
+        self.0.request_problem_info
+    }
+
+    #[setter]
+    fn set_request_problem_info(&mut self, request_problem_info: Option<u8>) {
+        self.0.request_problem_info = request_problem_info;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+
+    #[getter]
+    fn get_authentication_method(&self) -> Option<String> {
+        self.0.authentication_method.clone()
+    }
+
+    #[setter]
+    fn set_authentication_method(&mut self, authentication_method: Option<String>) {
+        self.0.authentication_method = authentication_method;
+    }
+
+    #[getter]
+    fn get_authentication_data(&self) -> Option<Vec<u8>> {
+        self.0
+            .authentication_data
+            .as_ref()
+            .map(|authentication_data| authentication_data.to_vec())
+    }
+
+    #[setter]
+    fn set_authentication_data(&mut self, authentication_data: Option<Vec<u8>>) {
+        self.0.authentication_data = authentication_data.map(Into::into);
+    }
+}
+
+impl From<::mqttbytes::v5::ConnectProperties> for ConnectProperties {
+    fn from(properties: ::mqttbytes::v5::ConnectProperties) -> Self {
+        Self(properties)
+    }
+}
@@ -0,0 +1,332 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+#[pyclass(module = "mqttbytes.v5")]
+pub struct Disconnect(::mqttbytes::v5::Disconnect);
+
+#[pymethods]
+impl Disconnect {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::Disconnect::new().into()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::Disconnect::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_reason_code(&self) -> DisconnectReasonCode {
+        self.0.reason_code.into()
+    }
+
+    #[setter]
+    fn set_reason_code(&mut self, reason_code: DisconnectReasonCode) {
+        self.0.reason_code = reason_code.into();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<DisconnectProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<DisconnectProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::Disconnect> for Disconnect {
+    fn from(disconnect: ::mqttbytes::v5::Disconnect) -> Self {
+        Self(disconnect)
+    }
+}
+
+/// Properties of the disconnect packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct DisconnectProperties(::mqttbytes::v5::DisconnectProperties);
+
+#[pymethods]
+impl DisconnectProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::DisconnectProperties::new().into()
+    }
+
+    #[getter]
+    fn get_session_expiry_interval(&self) -> Option<u32> {
+        self.0.session_expiry_interval
+    }
+
+    #[setter]
+    fn set_session_expiry_interval(&mut self, session_expiry_interval: Option<u32>) {
+        self.0.session_expiry_interval = session_expiry_interval;
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+
+    #[getter]
+    fn get_server_reference(&self) -> Option<String> {
+        self.0.server_reference.clone()
+    }
+
+    #[setter]
+    fn set_server_reference(&mut self, server_reference: Option<String>) {
+        self.0.server_reference = server_reference;
+    }
+}
+
+impl From<::mqttbytes::v5::DisconnectProperties> for DisconnectProperties {
+    fn from(properties: ::mqttbytes::v5::DisconnectProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code in disconnect.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum DisconnectReasonCode {
+    NormalDisconnection = 0,
+    DisconnectWithWillMessage = 4,
+    UnspecifiedError = 128,
+    MalformedPacket = 129,
+    ProtocolError = 130,
+    ImplementationSpecificError = 131,
+    NotAuthorized = 135,
+    ServerBusy = 137,
+    ServerShuttingDown = 139,
+    KeepAliveTimeout = 141,
+    SessionTakenOver = 142,
+    TopicFilterInvalid = 143,
+    TopicNameInvalid = 144,
+    ReceiveMaximumExceeded = 147,
+    TopicAliasInvalid = 148,
+    PacketTooLarge = 149,
+    MessageRateTooHigh = 150,
+    QuotaExceeded = 151,
+    AdministrativeAction = 152,
+    PayloadFormatInvalid = 153,
+    RetainNotSupported = 154,
+    QoSNotSupported = 155,
+    UseAnotherServer = 156,
+    ServerMoved = 157,
+    SharedSubscriptionNotSupported = 158,
+    ConnectionRateExceeded = 159,
+    MaximumConnectTime = 160,
+    SubscriptionIdentifiersNotSupported = 161,
+    WildcardSubscriptionsNotSupported = 162,
+}
+
+impl From<::mqttbytes::v5::DisconnectReasonCode> for DisconnectReasonCode {
+    fn from(code: ::mqttbytes::v5::DisconnectReasonCode) -> Self {
+        match code {
+            ::mqttbytes::v5::DisconnectReasonCode::NormalDisconnection => {
+                DisconnectReasonCode::NormalDisconnection
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::DisconnectWithWillMessage => {
+                DisconnectReasonCode::DisconnectWithWillMessage
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::UnspecifiedError => {
+                DisconnectReasonCode::UnspecifiedError
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::MalformedPacket => {
+                DisconnectReasonCode::MalformedPacket
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::ProtocolError => {
+                DisconnectReasonCode::ProtocolError
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::ImplementationSpecificError => {
+                DisconnectReasonCode::ImplementationSpecificError
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::NotAuthorized => {
+                DisconnectReasonCode::NotAuthorized
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::ServerBusy => DisconnectReasonCode::ServerBusy,
+            ::mqttbytes::v5::DisconnectReasonCode::ServerShuttingDown => {
+                DisconnectReasonCode::ServerShuttingDown
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::KeepAliveTimeout => {
+                DisconnectReasonCode::KeepAliveTimeout
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::SessionTakenOver => {
+                DisconnectReasonCode::SessionTakenOver
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::TopicFilterInvalid => {
+                DisconnectReasonCode::TopicFilterInvalid
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::TopicNameInvalid => {
+                DisconnectReasonCode::TopicNameInvalid
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::ReceiveMaximumExceeded => {
+                DisconnectReasonCode::ReceiveMaximumExceeded
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::TopicAliasInvalid => {
+                DisconnectReasonCode::TopicAliasInvalid
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::PacketTooLarge => {
+                DisconnectReasonCode::PacketTooLarge
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::MessageRateTooHigh => {
+                DisconnectReasonCode::MessageRateTooHigh
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::QuotaExceeded => {
+                DisconnectReasonCode::QuotaExceeded
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::AdministrativeAction => {
+                DisconnectReasonCode::AdministrativeAction
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::PayloadFormatInvalid => {
+                DisconnectReasonCode::PayloadFormatInvalid
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::RetainNotSupported => {
+                DisconnectReasonCode::RetainNotSupported
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::QoSNotSupported => {
+                DisconnectReasonCode::QoSNotSupported
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::UseAnotherServer => {
+                DisconnectReasonCode::UseAnotherServer
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::ServerMoved => DisconnectReasonCode::ServerMoved,
+            ::mqttbytes::v5::DisconnectReasonCode::SharedSubscriptionNotSupported => {
+                DisconnectReasonCode::SharedSubscriptionNotSupported
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::ConnectionRateExceeded => {
+                DisconnectReasonCode::ConnectionRateExceeded
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::MaximumConnectTime => {
+                DisconnectReasonCode::MaximumConnectTime
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::SubscriptionIdentifiersNotSupported => {
+                DisconnectReasonCode::SubscriptionIdentifiersNotSupported
+            }
+            ::mqttbytes::v5::DisconnectReasonCode::WildcardSubscriptionsNotSupported => {
+                DisconnectReasonCode::WildcardSubscriptionsNotSupported
+            }
+        }
+    }
+}
+
+impl From<DisconnectReasonCode> for ::mqttbytes::v5::DisconnectReasonCode {
+    fn from(code: DisconnectReasonCode) -> Self {
+        match code {
+            DisconnectReasonCode::NormalDisconnection => {
+                ::mqttbytes::v5::DisconnectReasonCode::NormalDisconnection
+            }
+            DisconnectReasonCode::DisconnectWithWillMessage => {
+                ::mqttbytes::v5::DisconnectReasonCode::DisconnectWithWillMessage
+            }
+            DisconnectReasonCode::UnspecifiedError => {
+                ::mqttbytes::v5::DisconnectReasonCode::UnspecifiedError
+            }
+            DisconnectReasonCode::MalformedPacket => {
+                ::mqttbytes::v5::DisconnectReasonCode::MalformedPacket
+            }
+            DisconnectReasonCode::ProtocolError => {
+                ::mqttbytes::v5::DisconnectReasonCode::ProtocolError
+            }
+            DisconnectReasonCode::ImplementationSpecificError => {
+                ::mqttbytes::v5::DisconnectReasonCode::ImplementationSpecificError
+            }
+            DisconnectReasonCode::NotAuthorized => {
+                ::mqttbytes::v5::DisconnectReasonCode::NotAuthorized
+            }
+            DisconnectReasonCode::ServerBusy => ::mqttbytes::v5::DisconnectReasonCode::ServerBusy,
+            DisconnectReasonCode::ServerShuttingDown => {
+                ::mqttbytes::v5::DisconnectReasonCode::ServerShuttingDown
+            }
+            DisconnectReasonCode::KeepAliveTimeout => {
+                ::mqttbytes::v5::DisconnectReasonCode::KeepAliveTimeout
+            }
+            DisconnectReasonCode::SessionTakenOver => {
+                ::mqttbytes::v5::DisconnectReasonCode::SessionTakenOver
+            }
+            DisconnectReasonCode::TopicFilterInvalid => {
+                ::mqttbytes::v5::DisconnectReasonCode::TopicFilterInvalid
+            }
+            DisconnectReasonCode::TopicNameInvalid => {
+                ::mqttbytes::v5::DisconnectReasonCode::TopicNameInvalid
+            }
+            DisconnectReasonCode::ReceiveMaximumExceeded => {
+                ::mqttbytes::v5::DisconnectReasonCode::ReceiveMaximumExceeded
+            }
+            DisconnectReasonCode::TopicAliasInvalid => {
+                ::mqttbytes::v5::DisconnectReasonCode::TopicAliasInvalid
+            }
+            DisconnectReasonCode::PacketTooLarge => {
+                ::mqttbytes::v5::DisconnectReasonCode::PacketTooLarge
+            }
+            DisconnectReasonCode::MessageRateTooHigh => {
+                ::mqttbytes::v5::DisconnectReasonCode::MessageRateTooHigh
+            }
+            DisconnectReasonCode::QuotaExceeded => {
+                ::mqttbytes::v5::DisconnectReasonCode::QuotaExceeded
+            }
+            DisconnectReasonCode::AdministrativeAction => {
+                ::mqttbytes::v5::DisconnectReasonCode::AdministrativeAction
+            }
+            DisconnectReasonCode::PayloadFormatInvalid => {
+                ::mqttbytes::v5::DisconnectReasonCode::PayloadFormatInvalid
+            }
+            DisconnectReasonCode::RetainNotSupported => {
+                ::mqttbytes::v5::DisconnectReasonCode::RetainNotSupported
+            }
+            DisconnectReasonCode::QoSNotSupported => {
+                ::mqttbytes::v5::DisconnectReasonCode::QoSNotSupported
+            }
+            DisconnectReasonCode::UseAnotherServer => {
+                ::mqttbytes::v5::DisconnectReasonCode::UseAnotherServer
+            }
+            DisconnectReasonCode::ServerMoved => ::mqttbytes::v5::DisconnectReasonCode::ServerMoved,
+            DisconnectReasonCode::SharedSubscriptionNotSupported => {
+                ::mqttbytes::v5::DisconnectReasonCode::SharedSubscriptionNotSupported
+            }
+            DisconnectReasonCode::ConnectionRateExceeded => {
+                ::mqttbytes::v5::DisconnectReasonCode::ConnectionRateExceeded
+            }
+            DisconnectReasonCode::MaximumConnectTime => {
+                ::mqttbytes::v5::DisconnectReasonCode::MaximumConnectTime
+            }
+            DisconnectReasonCode::SubscriptionIdentifiersNotSupported => {
+                ::mqttbytes::v5::DisconnectReasonCode::SubscriptionIdentifiersNotSupported
+            }
+            DisconnectReasonCode::WildcardSubscriptionsNotSupported => {
+                ::mqttbytes::v5::DisconnectReasonCode::WildcardSubscriptionsNotSupported
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::WrapperMqttBytesError;
+
+#[pyclass(module = "mqttbytes.v5")]
+pub struct PingReq(pub ::mqttbytes::v5::PingReq);
+
+#[pymethods]
+impl PingReq {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PingReq.into()
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+}
+
+impl From<::mqttbytes::v5::PingReq> for PingReq {
+    fn from(ping_req: ::mqttbytes::v5::PingReq) -> Self {
+        Self(ping_req)
+    }
+}
+
+#[pyclass(module = "mqttbytes.v5")]
+pub struct PingResp(pub ::mqttbytes::v5::PingResp);
+
+#[pymethods]
+impl PingResp {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PingResp.into()
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+}
+
+impl From<::mqttbytes::v5::PingResp> for PingResp {
+    fn from(ping_resp: ::mqttbytes::v5::PingResp) -> Self {
+        Self(ping_resp)
+    }
+}
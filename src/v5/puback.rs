@@ -0,0 +1,175 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// Acknowledgement to QoS1 publish.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct PubAck(::mqttbytes::v5::PubAck);
+
+#[pymethods]
+impl PubAck {
+    #[new]
+    fn new(pkid: u16) -> Self {
+        ::mqttbytes::v5::PubAck::new(pkid).into()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::PubAck::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_reason(&self) -> PubAckReason {
+        self.0.reason.into()
+    }
+
+    #[setter]
+    fn set_reason(&mut self, reason: PubAckReason) {
+        self.0.reason = reason.into();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<PubAckProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<PubAckProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::PubAck> for PubAck {
+    fn from(p: ::mqttbytes::v5::PubAck) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the puback packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct PubAckProperties(::mqttbytes::v5::PubAckProperties);
+
+#[pymethods]
+impl PubAckProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PubAckProperties {
+            reason_string: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::PubAckProperties> for PubAckProperties {
+    fn from(properties: ::mqttbytes::v5::PubAckProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code in puback.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum PubAckReason {
+    Success = 0,
+    NoMatchingSubscribers = 16,
+    UnspecifiedError = 128,
+    ImplementationSpecificError = 131,
+    NotAuthorized = 135,
+    TopicNameInvalid = 144,
+    PacketIdentifierInUse = 145,
+    QuotaExceeded = 151,
+    PayloadFormatInvalid = 153,
+}
+
+impl From<::mqttbytes::v5::PubAckReason> for PubAckReason {
+    fn from(code: ::mqttbytes::v5::PubAckReason) -> Self {
+        match code {
+            ::mqttbytes::v5::PubAckReason::Success => PubAckReason::Success,
+            ::mqttbytes::v5::PubAckReason::NoMatchingSubscribers => {
+                PubAckReason::NoMatchingSubscribers
+            }
+            ::mqttbytes::v5::PubAckReason::UnspecifiedError => PubAckReason::UnspecifiedError,
+            ::mqttbytes::v5::PubAckReason::ImplementationSpecificError => {
+                PubAckReason::ImplementationSpecificError
+            }
+            ::mqttbytes::v5::PubAckReason::NotAuthorized => PubAckReason::NotAuthorized,
+            ::mqttbytes::v5::PubAckReason::TopicNameInvalid => PubAckReason::TopicNameInvalid,
+            ::mqttbytes::v5::PubAckReason::PacketIdentifierInUse => {
+                PubAckReason::PacketIdentifierInUse
+            }
+            ::mqttbytes::v5::PubAckReason::QuotaExceeded => PubAckReason::QuotaExceeded,
+            ::mqttbytes::v5::PubAckReason::PayloadFormatInvalid => {
+                PubAckReason::PayloadFormatInvalid
+            }
+        }
+    }
+}
+
+impl From<PubAckReason> for ::mqttbytes::v5::PubAckReason {
+    fn from(code: PubAckReason) -> Self {
+        match code {
+            PubAckReason::Success => ::mqttbytes::v5::PubAckReason::Success,
+            PubAckReason::NoMatchingSubscribers => {
+                ::mqttbytes::v5::PubAckReason::NoMatchingSubscribers
+            }
+            PubAckReason::UnspecifiedError => ::mqttbytes::v5::PubAckReason::UnspecifiedError,
+            PubAckReason::ImplementationSpecificError => {
+                ::mqttbytes::v5::PubAckReason::ImplementationSpecificError
+            }
+            PubAckReason::NotAuthorized => ::mqttbytes::v5::PubAckReason::NotAuthorized,
+            PubAckReason::TopicNameInvalid => ::mqttbytes::v5::PubAckReason::TopicNameInvalid,
+            PubAckReason::PacketIdentifierInUse => {
+                ::mqttbytes::v5::PubAckReason::PacketIdentifierInUse
+            }
+            PubAckReason::QuotaExceeded => ::mqttbytes::v5::PubAckReason::QuotaExceeded,
+            PubAckReason::PayloadFormatInvalid => {
+                ::mqttbytes::v5::PubAckReason::PayloadFormatInvalid
+            }
+        }
+    }
+}
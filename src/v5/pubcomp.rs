@@ -0,0 +1,142 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// QoS2 publish complete, in response to PUBREL packet.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct PubComp(::mqttbytes::v5::PubComp);
+
+#[pymethods]
+impl PubComp {
+    #[new]
+    fn new(pkid: u16) -> Self {
+        ::mqttbytes::v5::PubComp::new(pkid).into()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::PubComp::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_reason(&self) -> PubCompReason {
+        self.0.reason.into()
+    }
+
+    #[setter]
+    fn set_reason(&mut self, reason: PubCompReason) {
+        self.0.reason = reason.into();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<PubCompProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<PubCompProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::PubComp> for PubComp {
+    fn from(p: ::mqttbytes::v5::PubComp) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the pubcomp packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct PubCompProperties(::mqttbytes::v5::PubCompProperties);
+
+#[pymethods]
+impl PubCompProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PubCompProperties {
+            reason_string: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::PubCompProperties> for PubCompProperties {
+    fn from(properties: ::mqttbytes::v5::PubCompProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code in pubcomp.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum PubCompReason {
+    Success = 0,
+    PacketIdentifierNotFound = 146,
+}
+
+impl From<::mqttbytes::v5::PubCompReason> for PubCompReason {
+    fn from(code: ::mqttbytes::v5::PubCompReason) -> Self {
+        match code {
+            ::mqttbytes::v5::PubCompReason::Success => PubCompReason::Success,
+            ::mqttbytes::v5::PubCompReason::PacketIdentifierNotFound => {
+                PubCompReason::PacketIdentifierNotFound
+            }
+        }
+    }
+}
+
+impl From<PubCompReason> for ::mqttbytes::v5::PubCompReason {
+    fn from(code: PubCompReason) -> Self {
+        match code {
+            PubCompReason::Success => ::mqttbytes::v5::PubCompReason::Success,
+            PubCompReason::PacketIdentifierNotFound => {
+                ::mqttbytes::v5::PubCompReason::PacketIdentifierNotFound
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use bytes::BytesMut;
+use pyo3::buffer::PyBuffer;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::payload::{buffer_to_bytes, memoryview};
+use crate::{FixedHeader, QoS, WrapperMqttBytesError};
+
+/// Publish packet.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct Publish(::mqttbytes::v5::Publish);
+
+#[pymethods]
+impl Publish {
+    #[new]
+    fn new(_py: Python, topic: String, qos: QoS, payload: PyBuffer<u8>) -> PyResult<Self> {
+        let mut publish = ::mqttbytes::v5::Publish::new(topic, qos.into(), Vec::new());
+        publish.payload = buffer_to_bytes(_py, &payload)?;
+        Ok(publish.into())
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::Publish::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_dup(&self) -> bool {
+        self.0.dup
+    }
+
+    #[setter]
+    fn set_dup(&mut self, dup: bool) {
+        self.0.dup = dup;
+    }
+
+    #[getter]
+    fn get_qos(&self) -> QoS {
+        self.0.qos.into()
+    }
+
+    #[setter]
+    fn set_qos(&mut self, qos: QoS) {
+        self.0.qos = qos.into();
+    }
+
+    #[getter]
+    fn get_retain(&self) -> bool {
+        self.0.retain
+    }
+
+    #[setter]
+    fn set_retain(&mut self, retain: bool) {
+        self.0.retain = retain;
+    }
+
+    #[getter]
+    fn get_topic(&self) -> String {
+        self.0.topic.clone()
+    }
+
+    #[setter]
+    fn set_topic(&mut self, topic: String) {
+        self.0.topic = topic;
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    /// Read-only `memoryview` sharing the memory of the payload.
+    #[getter]
+    fn get_payload(&self, _py: Python) -> PyResult<PyObject> {
+        memoryview(_py, self.0.payload.clone())
+    }
+
+    /// Accepts any object supporting the buffer protocol.
+    #[setter]
+    fn set_payload(&mut self, _py: Python, payload: PyBuffer<u8>) -> PyResult<()> {
+        self.0.payload = buffer_to_bytes(_py, &payload)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<PublishProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<PublishProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::Publish> for Publish {
+    fn from(p: ::mqttbytes::v5::Publish) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the publish packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct PublishProperties(::mqttbytes::v5::PublishProperties);
+
+#[pymethods]
+impl PublishProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PublishProperties {
+            payload_format_indicator: None,
+            message_expiry_interval: None,
+            topic_alias: None,
+            response_topic: None,
+            correlation_data: None,
+            user_properties: Vec::new(),
+            subscription_identifiers: Vec::new(),
+            content_type: None,
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_payload_format_indicator(&self) -> Option<u8> {
+        self.0.payload_format_indicator
+    }
+
+    #[setter]
+    fn set_payload_format_indicator(&mut self, payload_format_indicator: Option<u8>) {
+        self.0.payload_format_indicator = payload_format_indicator;
+    }
+
+    #[getter]
+    fn get_message_expiry_interval(&self) -> Option<u32> {
+        self.0.message_expiry_interval
+    }
+
+    #[setter]
+    fn set_message_expiry_interval(&mut self, message_expiry_interval: Option<u32>) {
+        self.0.message_expiry_interval = message_expiry_interval;
+    }
+
+    #[getter]
+    fn get_topic_alias(&self) -> Option<u16> {
+        self.0.topic_alias
+    }
+
+    #[setter]
+    fn set_topic_alias(&mut self, topic_alias: Option<u16>) {
+        self.0.topic_alias = topic_alias;
+    }
+
+    #[getter]
+    fn get_response_topic(&self) -> Option<String> {
+        self.0.response_topic.clone()
+    }
+
+    #[setter]
+    fn set_response_topic(&mut self, response_topic: Option<String>) {
+        self.0.response_topic = response_topic;
+    }
+
+    #[getter]
+    fn get_correlation_data(&self) -> Option<Vec<u8>> {
+        self.0
+            .correlation_data
+            .as_ref()
+            .map(|correlation_data| correlation_data.to_vec())
+    }
+
+    #[setter]
+    fn set_correlation_data(&mut self, correlation_data: Option<Vec<u8>>) {
+        self.0.correlation_data = correlation_data.map(Into::into);
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+
+    #[getter]
+    fn get_subscription_identifiers(&self) -> Vec<usize> {
+        self.0.subscription_identifiers.clone()
+    }
+
+    #[setter]
+    fn set_subscription_identifiers(&mut self, subscription_identifiers: Vec<usize>) {
+        self.0.subscription_identifiers = subscription_identifiers;
+    }
+
+    #[getter]
+    fn get_content_type(&self) -> Option<String> {
+        self.0.content_type.clone()
+    }
+
+    #[setter]
+    fn set_content_type(&mut self, content_type: Option<String>) {
+        self.0.content_type = content_type;
+    }
+}
+
+impl From<::mqttbytes::v5::PublishProperties> for PublishProperties {
+    fn from(properties: ::mqttbytes::v5::PublishProperties) -> Self {
+        Self(properties)
+    }
+}
@@ -0,0 +1,175 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// Acknowledgement to QoS2 publish.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct PubRec(::mqttbytes::v5::PubRec);
+
+#[pymethods]
+impl PubRec {
+    #[new]
+    fn new(pkid: u16) -> Self {
+        ::mqttbytes::v5::PubRec::new(pkid).into()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::PubRec::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_reason(&self) -> PubRecReason {
+        self.0.reason.into()
+    }
+
+    #[setter]
+    fn set_reason(&mut self, reason: PubRecReason) {
+        self.0.reason = reason.into();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<PubRecProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<PubRecProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::PubRec> for PubRec {
+    fn from(p: ::mqttbytes::v5::PubRec) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the pubrec packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct PubRecProperties(::mqttbytes::v5::PubRecProperties);
+
+#[pymethods]
+impl PubRecProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PubRecProperties {
+            reason_string: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::PubRecProperties> for PubRecProperties {
+    fn from(properties: ::mqttbytes::v5::PubRecProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code in pubrec.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum PubRecReason {
+    Success = 0,
+    NoMatchingSubscribers = 16,
+    UnspecifiedError = 128,
+    ImplementationSpecificError = 131,
+    NotAuthorized = 135,
+    TopicNameInvalid = 144,
+    PacketIdentifierInUse = 145,
+    QuotaExceeded = 151,
+    PayloadFormatInvalid = 153,
+}
+
+impl From<::mqttbytes::v5::PubRecReason> for PubRecReason {
+    fn from(code: ::mqttbytes::v5::PubRecReason) -> Self {
+        match code {
+            ::mqttbytes::v5::PubRecReason::Success => PubRecReason::Success,
+            ::mqttbytes::v5::PubRecReason::NoMatchingSubscribers => {
+                PubRecReason::NoMatchingSubscribers
+            }
+            ::mqttbytes::v5::PubRecReason::UnspecifiedError => PubRecReason::UnspecifiedError,
+            ::mqttbytes::v5::PubRecReason::ImplementationSpecificError => {
+                PubRecReason::ImplementationSpecificError
+            }
+            ::mqttbytes::v5::PubRecReason::NotAuthorized => PubRecReason::NotAuthorized,
+            ::mqttbytes::v5::PubRecReason::TopicNameInvalid => PubRecReason::TopicNameInvalid,
+            ::mqttbytes::v5::PubRecReason::PacketIdentifierInUse => {
+                PubRecReason::PacketIdentifierInUse
+            }
+            ::mqttbytes::v5::PubRecReason::QuotaExceeded => PubRecReason::QuotaExceeded,
+            ::mqttbytes::v5::PubRecReason::PayloadFormatInvalid => {
+                PubRecReason::PayloadFormatInvalid
+            }
+        }
+    }
+}
+
+impl From<PubRecReason> for ::mqttbytes::v5::PubRecReason {
+    fn from(code: PubRecReason) -> Self {
+        match code {
+            PubRecReason::Success => ::mqttbytes::v5::PubRecReason::Success,
+            PubRecReason::NoMatchingSubscribers => {
+                ::mqttbytes::v5::PubRecReason::NoMatchingSubscribers
+            }
+            PubRecReason::UnspecifiedError => ::mqttbytes::v5::PubRecReason::UnspecifiedError,
+            PubRecReason::ImplementationSpecificError => {
+                ::mqttbytes::v5::PubRecReason::ImplementationSpecificError
+            }
+            PubRecReason::NotAuthorized => ::mqttbytes::v5::PubRecReason::NotAuthorized,
+            PubRecReason::TopicNameInvalid => ::mqttbytes::v5::PubRecReason::TopicNameInvalid,
+            PubRecReason::PacketIdentifierInUse => {
+                ::mqttbytes::v5::PubRecReason::PacketIdentifierInUse
+            }
+            PubRecReason::QuotaExceeded => ::mqttbytes::v5::PubRecReason::QuotaExceeded,
+            PubRecReason::PayloadFormatInvalid => {
+                ::mqttbytes::v5::PubRecReason::PayloadFormatInvalid
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// QoS2 publish release, in response to PUBREC packet.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct PubRel(::mqttbytes::v5::PubRel);
+
+#[pymethods]
+impl PubRel {
+    #[new]
+    fn new(pkid: u16) -> Self {
+        ::mqttbytes::v5::PubRel::new(pkid).into()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::PubRel::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_reason(&self) -> PubRelReason {
+        self.0.reason.into()
+    }
+
+    #[setter]
+    fn set_reason(&mut self, reason: PubRelReason) {
+        self.0.reason = reason.into();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<PubRelProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<PubRelProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::PubRel> for PubRel {
+    fn from(p: ::mqttbytes::v5::PubRel) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the pubrel packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct PubRelProperties(::mqttbytes::v5::PubRelProperties);
+
+#[pymethods]
+impl PubRelProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::PubRelProperties {
+            reason_string: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::PubRelProperties> for PubRelProperties {
+    fn from(properties: ::mqttbytes::v5::PubRelProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code in pubrel.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum PubRelReason {
+    Success = 0,
+    PacketIdentifierNotFound = 146,
+}
+
+impl From<::mqttbytes::v5::PubRelReason> for PubRelReason {
+    fn from(code: ::mqttbytes::v5::PubRelReason) -> Self {
+        match code {
+            ::mqttbytes::v5::PubRelReason::Success => PubRelReason::Success,
+            ::mqttbytes::v5::PubRelReason::PacketIdentifierNotFound => {
+                PubRelReason::PacketIdentifierNotFound
+            }
+        }
+    }
+}
+
+impl From<PubRelReason> for ::mqttbytes::v5::PubRelReason {
+    fn from(code: PubRelReason) -> Self {
+        match code {
+            PubRelReason::Success => ::mqttbytes::v5::PubRelReason::Success,
+            PubRelReason::PacketIdentifierNotFound => {
+                ::mqttbytes::v5::PubRelReason::PacketIdentifierNotFound
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// Acknowledgement to subscribe.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct SubAck(::mqttbytes::v5::SubAck);
+
+#[pymethods]
+impl SubAck {
+    #[new]
+    fn new(pkid: u16, return_codes: Vec<SubscribeReasonCode>) -> Self {
+        ::mqttbytes::v5::SubAck::new(pkid, return_codes.into_iter().map(Into::into).collect())
+            .into()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::SubAck::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_return_codes(&self) -> Vec<SubscribeReasonCode> {
+        self.0
+            .return_codes
+            .iter()
+            .copied()
+            .map(Into::into)
+            .collect()
+    }
+
+    #[setter]
+    fn set_return_codes(&mut self, return_codes: Vec<SubscribeReasonCode>) {
+        self.0.return_codes = return_codes.into_iter().map(Into::into).collect();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<SubAckProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<SubAckProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::SubAck> for SubAck {
+    fn from(p: ::mqttbytes::v5::SubAck) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the suback packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct SubAckProperties(::mqttbytes::v5::SubAckProperties);
+
+#[pymethods]
+impl SubAckProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::SubAckProperties {
+            reason_string: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::SubAckProperties> for SubAckProperties {
+    fn from(properties: ::mqttbytes::v5::SubAckProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code for each filter in suback.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum SubscribeReasonCode {
+    QoS0 = 0,
+    QoS1 = 1,
+    QoS2 = 2,
+    Unspecified = 128,
+    ImplementationSpecific = 131,
+    NotAuthorized = 135,
+    TopicFilterInvalid = 143,
+    PkidInUse = 145,
+    QuotaExceeded = 151,
+    SharedSubscriptionsNotSupported = 158,
+    SubscriptionIdNotSupported = 161,
+    WildcardSubscriptionsNotSupported = 162,
+}
+
+impl From<::mqttbytes::v5::SubscribeReasonCode> for SubscribeReasonCode {
+    fn from(code: ::mqttbytes::v5::SubscribeReasonCode) -> Self {
+        match code {
+            ::mqttbytes::v5::SubscribeReasonCode::QoS0 => SubscribeReasonCode::QoS0,
+            ::mqttbytes::v5::SubscribeReasonCode::QoS1 => SubscribeReasonCode::QoS1,
+            ::mqttbytes::v5::SubscribeReasonCode::QoS2 => SubscribeReasonCode::QoS2,
+            ::mqttbytes::v5::SubscribeReasonCode::Unspecified => SubscribeReasonCode::Unspecified,
+            ::mqttbytes::v5::SubscribeReasonCode::ImplementationSpecific => {
+                SubscribeReasonCode::ImplementationSpecific
+            }
+            ::mqttbytes::v5::SubscribeReasonCode::NotAuthorized => {
+                SubscribeReasonCode::NotAuthorized
+            }
+            ::mqttbytes::v5::SubscribeReasonCode::TopicFilterInvalid => {
+                SubscribeReasonCode::TopicFilterInvalid
+            }
+            ::mqttbytes::v5::SubscribeReasonCode::PkidInUse => SubscribeReasonCode::PkidInUse,
+            ::mqttbytes::v5::SubscribeReasonCode::QuotaExceeded => {
+                SubscribeReasonCode::QuotaExceeded
+            }
+            ::mqttbytes::v5::SubscribeReasonCode::SharedSubscriptionsNotSupported => {
+                SubscribeReasonCode::SharedSubscriptionsNotSupported
+            }
+            ::mqttbytes::v5::SubscribeReasonCode::SubscriptionIdNotSupported => {
+                SubscribeReasonCode::SubscriptionIdNotSupported
+            }
+            ::mqttbytes::v5::SubscribeReasonCode::WildcardSubscriptionsNotSupported => {
+                SubscribeReasonCode::WildcardSubscriptionsNotSupported
+            }
+        }
+    }
+}
+
+impl From<SubscribeReasonCode> for ::mqttbytes::v5::SubscribeReasonCode {
+    fn from(code: SubscribeReasonCode) -> Self {
+        match code {
+            SubscribeReasonCode::QoS0 => ::mqttbytes::v5::SubscribeReasonCode::QoS0,
+            SubscribeReasonCode::QoS1 => ::mqttbytes::v5::SubscribeReasonCode::QoS1,
+            SubscribeReasonCode::QoS2 => ::mqttbytes::v5::SubscribeReasonCode::QoS2,
+            SubscribeReasonCode::Unspecified => ::mqttbytes::v5::SubscribeReasonCode::Unspecified,
+            SubscribeReasonCode::ImplementationSpecific => {
+                ::mqttbytes::v5::SubscribeReasonCode::ImplementationSpecific
+            }
+            SubscribeReasonCode::NotAuthorized => {
+                ::mqttbytes::v5::SubscribeReasonCode::NotAuthorized
+            }
+            SubscribeReasonCode::TopicFilterInvalid => {
+                ::mqttbytes::v5::SubscribeReasonCode::TopicFilterInvalid
+            }
+            SubscribeReasonCode::PkidInUse => ::mqttbytes::v5::SubscribeReasonCode::PkidInUse,
+            SubscribeReasonCode::QuotaExceeded => {
+                ::mqttbytes::v5::SubscribeReasonCode::QuotaExceeded
+            }
+            SubscribeReasonCode::SharedSubscriptionsNotSupported => {
+                ::mqttbytes::v5::SubscribeReasonCode::SharedSubscriptionsNotSupported
+            }
+            SubscribeReasonCode::SubscriptionIdNotSupported => {
+                ::mqttbytes::v5::SubscribeReasonCode::SubscriptionIdNotSupported
+            }
+            SubscribeReasonCode::WildcardSubscriptionsNotSupported => {
+                ::mqttbytes::v5::SubscribeReasonCode::WildcardSubscriptionsNotSupported
+            }
+        }
+    }
+}
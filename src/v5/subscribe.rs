@@ -0,0 +1,239 @@
+use bytes::BytesMut;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, QoS, WrapperMqttBytesError};
+
+/// Subscription packet.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct Subscribe(::mqttbytes::v5::Subscribe);
+
+#[pymethods]
+impl Subscribe {
+    #[new]
+    fn new(path: Option<&PyAny>, qos: Option<QoS>) -> PyResult<Self> {
+        match (path, qos) {
+            (Some(path), Some(qos)) => {
+                let path = path.extract::<String>()?;
+                Ok(::mqttbytes::v5::Subscribe::new(path, qos.into()).into())
+            }
+            (Some(path), None) => {
+                let topics = path.extract::<Vec<SubscribeFilter>>()?;
+                Ok(
+                    ::mqttbytes::v5::Subscribe::new_many(topics.into_iter().map(|topic| topic.0))
+                        .into(),
+                )
+            }
+            (None, None) => Ok(::mqttbytes::v5::Subscribe::empty_subscribe().into()),
+            _ => Err(PyTypeError::new_err("Invalid arguments")),
+        }
+    }
+
+    fn add(&mut self, path: String, qos: QoS) {
+        self.0.add(path, qos.into());
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::Subscribe::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_filters(&self) -> Vec<SubscribeFilter> {
+        self.0.filters.iter().cloned().map(Into::into).collect()
+    }
+
+    #[setter]
+    fn set_filters(&mut self, filters: Vec<SubscribeFilter>) {
+        self.0.filters = filters.into_iter().map(|filter| filter.0).collect();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<SubscribeProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<SubscribeProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::Subscribe> for Subscribe {
+    fn from(p: ::mqttbytes::v5::Subscribe) -> Self {
+        Self(p)
+    }
+}
+
+/// Subscription filter.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct SubscribeFilter(::mqttbytes::v5::SubscribeFilter);
+
+#[pymethods]
+impl SubscribeFilter {
+    #[new]
+    fn new(path: String, qos: QoS) -> Self {
+        ::mqttbytes::v5::SubscribeFilter::new(path, qos.into()).into()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[getter]
+    fn get_path(&self) -> String {
+        self.0.path.clone()
+    }
+
+    #[setter]
+    fn set_path(&mut self, path: String) {
+        self.0.path = path;
+    }
+
+    #[getter]
+    fn get_qos(&self) -> QoS {
+        self.0.qos.into()
+    }
+
+    #[setter]
+    fn set_qos(&mut self, qos: QoS) {
+        self.0.qos = qos.into();
+    }
+
+    #[getter]
+    fn get_nolocal(&self) -> bool {
+        self.0.nolocal
+    }
+
+    #[setter]
+    fn set_nolocal(&mut self, nolocal: bool) {
+        self.0.nolocal = nolocal;
+    }
+
+    #[getter]
+    fn get_preserve_retain(&self) -> bool {
+        self.0.preserve_retain
+    }
+
+    #[setter]
+    fn set_preserve_retain(&mut self, preserve_retain: bool) {
+        self.0.preserve_retain = preserve_retain;
+    }
+
+    #[getter]
+    fn get_retain_forward_rule(&self) -> RetainForwardRule {
+        self.0.retain_forward_rule.clone().into()
+    }
+
+    #[setter]
+    fn set_retain_forward_rule(&mut self, retain_forward_rule: RetainForwardRule) {
+        self.0.retain_forward_rule = retain_forward_rule.into();
+    }
+}
+
+impl From<::mqttbytes::v5::SubscribeFilter> for SubscribeFilter {
+    fn from(p: ::mqttbytes::v5::SubscribeFilter) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the subscribe packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct SubscribeProperties(::mqttbytes::v5::SubscribeProperties);
+
+#[pymethods]
+impl SubscribeProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::SubscribeProperties {
+            id: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_id(&self) -> Option<usize> {
+        self.0.id
+    }
+
+    #[setter]
+    fn set_id(&mut self, id: Option<usize>) {
+        self.0.id = id;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::SubscribeProperties> for SubscribeProperties {
+    fn from(properties: ::mqttbytes::v5::SubscribeProperties) -> Self {
+        Self(properties)
+    }
+}
+
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub enum RetainForwardRule {
+    OnEverySubscribe,
+    OnNewSubscribe,
+    Never,
+}
+
+impl From<::mqttbytes::v5::RetainForwardRule> for RetainForwardRule {
+    fn from(rule: ::mqttbytes::v5::RetainForwardRule) -> Self {
+        match rule {
+            ::mqttbytes::v5::RetainForwardRule::OnEverySubscribe => {
+                RetainForwardRule::OnEverySubscribe
+            }
+            ::mqttbytes::v5::RetainForwardRule::OnNewSubscribe => RetainForwardRule::OnNewSubscribe,
+            ::mqttbytes::v5::RetainForwardRule::Never => RetainForwardRule::Never,
+        }
+    }
+}
+
+impl From<RetainForwardRule> for ::mqttbytes::v5::RetainForwardRule {
+    fn from(rule: RetainForwardRule) -> Self {
+        match rule {
+            RetainForwardRule::OnEverySubscribe => {
+                ::mqttbytes::v5::RetainForwardRule::OnEverySubscribe
+            }
+            RetainForwardRule::OnNewSubscribe => ::mqttbytes::v5::RetainForwardRule::OnNewSubscribe,
+            RetainForwardRule::Never => ::mqttbytes::v5::RetainForwardRule::Never,
+        }
+    }
+}
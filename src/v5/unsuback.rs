@@ -0,0 +1,173 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// Acknowledgement to unsubscribe.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct UnsubAck(::mqttbytes::v5::UnsubAck);
+
+#[pymethods]
+impl UnsubAck {
+    #[new]
+    fn new(pkid: u16) -> Self {
+        ::mqttbytes::v5::UnsubAck::new(pkid).into()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::UnsubAck::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_reasons(&self) -> Vec<UnsubAckReason> {
+        self.0.reasons.iter().copied().map(Into::into).collect()
+    }
+
+    #[setter]
+    fn set_reasons(&mut self, reasons: Vec<UnsubAckReason>) {
+        self.0.reasons = reasons.into_iter().map(Into::into).collect();
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<UnsubAckProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<UnsubAckProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::UnsubAck> for UnsubAck {
+    fn from(p: ::mqttbytes::v5::UnsubAck) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the unsuback packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct UnsubAckProperties(::mqttbytes::v5::UnsubAckProperties);
+
+#[pymethods]
+impl UnsubAckProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::UnsubAckProperties {
+            reason_string: None,
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_reason_string(&self) -> Option<String> {
+        self.0.reason_string.clone()
+    }
+
+    #[setter]
+    fn set_reason_string(&mut self, reason_string: Option<String>) {
+        self.0.reason_string = reason_string;
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::UnsubAckProperties> for UnsubAckProperties {
+    fn from(properties: ::mqttbytes::v5::UnsubAckProperties) -> Self {
+        Self(properties)
+    }
+}
+
+/// Reason code for each filter in unsuback.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+#[repr(u8)]
+pub enum UnsubAckReason {
+    Success = 0,
+    NoSubscriptionExisted = 17,
+    UnspecifiedError = 128,
+    ImplementationSpecificError = 131,
+    NotAuthorized = 135,
+    TopicFilterInvalid = 143,
+    PacketIdentifierInUse = 145,
+}
+
+impl From<::mqttbytes::v5::UnsubAckReason> for UnsubAckReason {
+    fn from(code: ::mqttbytes::v5::UnsubAckReason) -> Self {
+        match code {
+            ::mqttbytes::v5::UnsubAckReason::Success => UnsubAckReason::Success,
+            ::mqttbytes::v5::UnsubAckReason::NoSubscriptionExisted => {
+                UnsubAckReason::NoSubscriptionExisted
+            }
+            ::mqttbytes::v5::UnsubAckReason::UnspecifiedError => UnsubAckReason::UnspecifiedError,
+            ::mqttbytes::v5::UnsubAckReason::ImplementationSpecificError => {
+                UnsubAckReason::ImplementationSpecificError
+            }
+            ::mqttbytes::v5::UnsubAckReason::NotAuthorized => UnsubAckReason::NotAuthorized,
+            ::mqttbytes::v5::UnsubAckReason::TopicFilterInvalid => {
+                UnsubAckReason::TopicFilterInvalid
+            }
+            ::mqttbytes::v5::UnsubAckReason::PacketIdentifierInUse => {
+                UnsubAckReason::PacketIdentifierInUse
+            }
+        }
+    }
+}
+
+impl From<UnsubAckReason> for ::mqttbytes::v5::UnsubAckReason {
+    fn from(code: UnsubAckReason) -> Self {
+        match code {
+            UnsubAckReason::Success => ::mqttbytes::v5::UnsubAckReason::Success,
+            UnsubAckReason::NoSubscriptionExisted => {
+                ::mqttbytes::v5::UnsubAckReason::NoSubscriptionExisted
+            }
+            UnsubAckReason::UnspecifiedError => ::mqttbytes::v5::UnsubAckReason::UnspecifiedError,
+            UnsubAckReason::ImplementationSpecificError => {
+                ::mqttbytes::v5::UnsubAckReason::ImplementationSpecificError
+            }
+            UnsubAckReason::NotAuthorized => ::mqttbytes::v5::UnsubAckReason::NotAuthorized,
+            UnsubAckReason::TopicFilterInvalid => {
+                ::mqttbytes::v5::UnsubAckReason::TopicFilterInvalid
+            }
+            UnsubAckReason::PacketIdentifierInUse => {
+                ::mqttbytes::v5::UnsubAckReason::PacketIdentifierInUse
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use bytes::BytesMut;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::{FixedHeader, WrapperMqttBytesError};
+
+/// Unsubscribe packet.
+#[pyclass(module = "mqttbytes.v5")]
+pub struct Unsubscribe(::mqttbytes::v5::Unsubscribe);
+
+#[pymethods]
+impl Unsubscribe {
+    #[new]
+    fn new(topic: String) -> Self {
+        ::mqttbytes::v5::Unsubscribe::new(topic).into()
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    #[staticmethod]
+    fn read(fixed_header: FixedHeader, bytes: Vec<u8>) -> Result<Self, WrapperMqttBytesError> {
+        ::mqttbytes::v5::Unsubscribe::read(fixed_header.0, bytes.into())
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+
+    // Rewrite write method to return Python bytes instead of Vec<u8>
+    fn write(&self, _py: Python) -> Result<Py<PyBytes>, WrapperMqttBytesError> {
+        let mut buffer: BytesMut = BytesMut::new();
+        self.0
+            .write(&mut buffer)
+            .map_err(WrapperMqttBytesError::from)?;
+        Ok(PyBytes::new(_py, &buffer).into())
+    }
+
+    #[getter]
+    fn get_pkid(&self) -> u16 {
+        self.0.pkid
+    }
+
+    #[setter]
+    fn set_pkid(&mut self, pkid: u16) {
+        self.0.pkid = pkid;
+    }
+
+    #[getter]
+    fn get_filters(&self) -> Vec<String> {
+        self.0.filters.clone()
+    }
+
+    #[setter]
+    fn set_filters(&mut self, filters: Vec<String>) {
+        self.0.filters = filters;
+    }
+
+    #[getter]
+    fn get_properties(&self) -> Option<UnsubscribeProperties> {
+        self.0.properties.clone().map(Into::into)
+    }
+
+    #[setter]
+    fn set_properties(&mut self, properties: Option<UnsubscribeProperties>) {
+        self.0.properties = properties.map(|properties| properties.0);
+    }
+}
+
+impl From<::mqttbytes::v5::Unsubscribe> for Unsubscribe {
+    fn from(p: ::mqttbytes::v5::Unsubscribe) -> Self {
+        Self(p)
+    }
+}
+
+/// Properties of the unsubscribe packet.
+#[pyclass(module = "mqttbytes.v5")]
+#[derive(Clone)]
+pub struct UnsubscribeProperties(::mqttbytes::v5::UnsubscribeProperties);
+
+#[pymethods]
+impl UnsubscribeProperties {
+    #[new]
+    fn new() -> Self {
+        ::mqttbytes::v5::UnsubscribeProperties {
+            user_properties: Vec::new(),
+        }
+        .into()
+    }
+
+    #[getter]
+    fn get_user_properties(&self) -> Vec<(String, String)> {
+        self.0.user_properties.clone()
+    }
+
+    #[setter]
+    fn set_user_properties(&mut self, user_properties: Vec<(String, String)>) {
+        self.0.user_properties = user_properties;
+    }
+}
+
+impl From<::mqttbytes::v5::UnsubscribeProperties> for UnsubscribeProperties {
+    fn from(properties: ::mqttbytes::v5::UnsubscribeProperties) -> Self {
+        Self(properties)
+    }
+}
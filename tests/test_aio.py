@@ -0,0 +1,60 @@
+"""Tests of `mqttbytes.aio` against `testing.Broker`."""
+
+import asyncio
+import unittest
+
+try:
+    from mqttbytes import QoS, aio, testing, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+class AioTest(unittest.IsolatedAsyncioTestCase):
+    def setUp(self):
+        self.broker = testing.Broker()
+        self.addCleanup(self.broker.close)
+
+    async def connect(self, client_id):
+        reader, writer = await asyncio.open_connection(*self.broker.address)
+        self.addAsyncCleanup(self.close, writer)
+        await aio.write_packet(writer, v4.Connect(client_id))
+        connack = await aio.read_packet(reader, 1024)
+        self.assertEqual(connack.code, v4.ConnectReturnCode.Success)
+        return reader, writer
+
+    async def close(self, writer):
+        writer.close()
+        try:
+            await writer.wait_closed()
+        except OSError:
+            pass
+
+    async def test_publish_to_subscriber(self):
+        sub_reader, sub_writer = await self.connect("sub")
+        await aio.write_packet(sub_writer, v4.Subscribe("t/#", QoS.AtMostOnce, pkid=1))
+        self.assertEqual(await aio.read_packet(sub_reader, 1024), v4.SubAck(1, [QoS.AtMostOnce]))
+
+        _, pub_writer = await self.connect("pub")
+        publish = v4.Publish("t/a", QoS.AtMostOnce, b"payload")
+        await aio.write_packet(pub_writer, publish)
+        self.assertEqual(await aio.read_packet(sub_reader, 1024), publish)
+
+    async def test_iter_packets_ends_with_the_stream(self):
+        reader, writer = await self.connect("client")
+        await aio.write_packet(writer, v4.PingReq())
+        await aio.write_packet(writer, v4.Disconnect())
+        packets = [packet async for packet in aio.iter_packets(reader, 1024)]
+        self.assertEqual(packets, [v4.PingResp()])
+
+    async def test_read_packet_stops_at_the_end_of_a_packet(self):
+        reader = asyncio.StreamReader()
+        reader.feed_data(v4.PingReq().write() + b"\x30")
+        self.assertEqual(await aio.read_packet(reader, 1024), v4.PingReq())
+        reader.feed_eof()
+        with self.assertRaises(asyncio.IncompleteReadError) as cm:
+            await aio.read_packet(reader, 1024)
+        self.assertEqual(cm.exception.partial, b"\x30")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,50 @@
+"""Tests of the arguments accepted by packet constructors."""
+
+import unittest
+
+try:
+    from mqttbytes import InvalidQoS, QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+class ArgsTest(unittest.TestCase):
+    def test_connect_client_id_keyword(self):
+        self.assertEqual(v4.Connect(client_id="c").client_id, "c")
+
+    def test_connect_id_is_deprecated(self):
+        with self.assertWarnsRegex(DeprecationWarning, "use `client_id`"):
+            self.assertEqual(v4.Connect(id="c").client_id, "c")
+        with self.assertRaisesRegex(TypeError, "both `client_id` and `id`"):
+            v4.Connect("c", id="d")
+        with self.assertRaisesRegex(TypeError, "missing argument `client_id`"):
+            v4.Connect()
+
+    def test_last_will_message(self):
+        will = v4.LastWill("w", message=b"gone", qos=QoS.AtMostOnce)
+        self.assertEqual(bytes(will.message), b"gone")
+        self.assertEqual(will.to_dict("hex")["message"], "676f6e65")
+        self.assertIn("message=b'gone'", repr(will))
+
+    def test_qos_accepts_enum_and_int(self):
+        for qos, expected in ((QoS.ExactlyOnce, QoS.ExactlyOnce), (1, QoS.AtLeastOnce)):
+            with self.subTest(qos=qos):
+                self.assertEqual(v4.Publish("t", qos, b"").qos, expected)
+
+    def test_qos_rejects_bool(self):
+        for qos in (True, False):
+            with self.subTest(qos=qos):
+                with self.assertRaisesRegex(TypeError, "expected QoS or int, got bool"):
+                    v4.Publish("t", qos, b"")
+
+    def test_qos_rejects_out_of_range(self):
+        with self.assertRaises(InvalidQoS):
+            v4.Publish("t", 3, b"")
+        for qos in (-1, 256):
+            with self.subTest(qos=qos):
+                with self.assertRaisesRegex(ValueError, "invalid QoS"):
+                    v4.Publish("t", qos, b"")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,108 @@
+"""Tests of `testing.Broker`."""
+
+import socket
+import unittest
+
+try:
+    from mqttbytes import InsufficientBytes, QoS, check, testing, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+TIMEOUT = 5.0
+MAX_SIZE = 1024 * 1024
+
+
+class Connection:
+    """Blocking connection sending and receiving packets one at a time."""
+
+    def __init__(self, address):
+        self.sock = socket.create_connection(address, TIMEOUT)
+        self.incoming = b""
+
+    def send(self, *packets):
+        self.sock.sendall(b"".join(packet.write() for packet in packets))
+
+    def receive(self):
+        """Returns the next packet, or None when the broker closed the stream."""
+        while True:
+            try:
+                check(self.incoming, MAX_SIZE)
+                break
+            except InsufficientBytes:
+                data = self.sock.recv(65536)
+                if not data:
+                    return None
+                self.incoming += data
+        packet, end = v4.read_from(self.incoming, 0, MAX_SIZE)
+        self.incoming = self.incoming[end:]
+        return packet
+
+    def close(self):
+        self.sock.close()
+
+
+class BrokerTest(unittest.TestCase):
+    def setUp(self):
+        self.broker = testing.Broker()
+        self.addCleanup(self.broker.close)
+
+    def connect(self, client_id, **kwargs):
+        connection = Connection(self.broker.address)
+        self.addCleanup(connection.close)
+        connection.send(v4.Connect(client_id, **kwargs))
+        self.assertEqual(connection.receive(), v4.ConnAck(v4.ConnectReturnCode.Success))
+        return connection
+
+    def subscribe(self, connection, path, qos=QoS.AtMostOnce):
+        connection.send(v4.Subscribe(path, qos, pkid=1))
+        self.assertEqual(connection.receive(), v4.SubAck(1, [qos]))
+
+    def test_refused_connect_is_answered_then_closed(self):
+        connection = Connection(self.broker.address)
+        self.addCleanup(connection.close)
+        connection.send(v4.Connect("", clean_session=False))
+        self.assertEqual(connection.receive(), v4.ConnAck(v4.ConnectReturnCode.BadClientId))
+        self.assertIsNone(connection.receive())
+        self.assertEqual(self.broker.clients, [])
+
+    def test_last_will(self):
+        subscriber = self.connect("sub")
+        self.subscribe(subscriber, "will")
+        will = v4.LastWill("will", b"gone", QoS.AtMostOnce, False)
+        self.connect("a", last_will=will).close()
+        self.assertEqual(subscriber.receive(), v4.Publish("will", QoS.AtMostOnce, b"gone"))
+        # The will of a client that disconnects is discarded.
+        b = self.connect("b", last_will=v4.LastWill("will", b"b", QoS.AtMostOnce, False))
+        b.send(v4.Disconnect())
+        self.assertIsNone(b.receive())
+        subscriber.send(v4.PingReq())
+        self.assertEqual(subscriber.receive(), v4.PingResp())
+
+    def test_takeover(self):
+        first = self.connect("client")
+        self.connect("client")
+        self.assertIsNone(first.receive())
+        self.assertEqual(self.broker.clients, ["client"])
+
+    def test_invalid_topic_closes_the_connection(self):
+        connection = self.connect("client")
+        connection.send(v4.Publish("a/+", QoS.AtMostOnce, b""))
+        self.assertIsNone(connection.receive())
+
+    def test_client_not_reading_blocks_no_one(self):
+        # The broker keeps writing to the subscriber from its own thread.
+        slow = self.connect("slow")
+        self.subscribe(slow, "#")
+        publisher = self.connect("pub")
+        payload = bytes(64 * 1024)
+        for _ in range(128):
+            publisher.send(v4.Publish("t", QoS.AtMostOnce, payload))
+        publisher.send(v4.PingReq())
+        self.assertEqual(publisher.receive(), v4.PingResp())
+        other = self.connect("other")
+        other.send(v4.PingReq())
+        self.assertEqual(other.receive(), v4.PingResp())
+
+
+if __name__ == "__main__":
+    unittest.main()
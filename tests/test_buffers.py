@@ -0,0 +1,54 @@
+"""Tests of reading from and writing into buffers, with small and large
+payloads."""
+
+import unittest
+
+try:
+    from mqttbytes import QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+SIZES = [0, 16 * 1024 - 1, 16 * 1024, 256 * 1024]
+
+
+class BufferTest(unittest.TestCase):
+    def test_read_from_copies_the_packet(self):
+        for size in SIZES:
+            with self.subTest(size=size):
+                publish = v4.Publish("t", QoS.AtLeastOnce, bytes(range(256)) * (size // 256), pkid=1)
+                buffer = bytearray(b"\xff" + publish.write())
+                packet, consumed = v4.read_from(memoryview(buffer), 1, len(buffer))
+                buffer[:] = bytes(len(buffer))
+                self.assertEqual(packet, publish)
+                self.assertEqual(consumed, len(buffer) - 1)
+
+    def test_read_all(self):
+        for size in SIZES:
+            with self.subTest(size=size):
+                publish = v4.Publish("t", QoS.AtMostOnce, b"x" * size)
+                buffer = bytearray(publish.write() * 3 + b"\x30")
+                packets, consumed = v4.read_all(buffer, len(buffer))
+                buffer[:] = bytes(len(buffer))
+                self.assertEqual(packets, [publish] * 3)
+                self.assertEqual(consumed, len(buffer) - 1)
+
+    def test_write_into(self):
+        for size in SIZES:
+            with self.subTest(size=size):
+                publish = v4.Publish("t", QoS.AtMostOnce, b"x" * size)
+                encoded = publish.write()
+                buffer = bytearray(len(encoded) + 2)
+                self.assertEqual(publish.write_into(buffer, 2), len(encoded))
+                self.assertEqual(bytes(buffer), b"\x00\x00" + encoded)
+                with self.assertRaisesRegex(ValueError, "buffer too small"):
+                    publish.write_into(buffer, 3)
+
+    def test_payload_from_buffer(self):
+        payload = bytearray(b"x" * 32 * 1024)
+        publish = v4.Publish("t", QoS.AtMostOnce, payload)
+        payload[0] = 0
+        self.assertEqual(bytes(publish.payload[:1]), b"x")
+
+
+if __name__ == "__main__":
+    unittest.main()
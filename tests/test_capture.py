@@ -0,0 +1,334 @@
+"""Tests of `capture.PcapReader` on generated pcap and pcapng files."""
+
+import ipaddress
+import os
+import struct
+import tempfile
+import unittest
+
+try:
+    from mqttbytes import QoS, capture, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+FIN, SYN, RST, PSH, ACK = 0x01, 0x02, 0x04, 0x08, 0x10
+CLIENT = ("10.0.0.1", 50000)
+SERVER = ("10.0.0.2", 1883)
+CLIENT6 = ("fd00::1", 50000)
+SERVER6 = ("fd00::2", 1883)
+
+LINKTYPE_NULL = 0
+LINKTYPE_ETHERNET = 1
+LINKTYPE_RAW = 101
+LINKTYPE_LINUX_SLL = 113
+
+CONNECT = v4.Connect("client")
+CONNACK = v4.ConnAck(v4.ConnectReturnCode.Success)
+
+
+def tcp(source, destination, seq, payload=b"", flags=PSH | ACK):
+    """TCP segment, without checksum as the reader doesn't check it."""
+    header = struct.pack("!HHIIBBHHH", source[1], destination[1], seq % 2**32, 0, 5 << 4, flags, 65535, 0, 0)
+    return header + payload
+
+
+def ip(source, destination, segment, options=False):
+    """IPv4 or IPv6 packet, with a destination options header if `options`."""
+    src, dst = ipaddress.ip_address(source[0]), ipaddress.ip_address(destination[0])
+    if src.version == 4:
+        header = struct.pack("!BBHHHBBH", 0x45, 0, 20 + len(segment), 0, 0x4000, 64, 6, 0)
+        return header + src.packed + dst.packed + segment
+    next_header = 6
+    if options:
+        segment = bytes([6, 0]) + bytes(6) + segment
+        next_header = 60
+    return struct.pack("!IHBB", 6 << 28, len(segment), next_header, 64) + src.packed + dst.packed + segment
+
+
+def ethernet(packet, vlan=False):
+    ethertype = 0x86DD if packet[0] >> 4 == 6 else 0x0800
+    header = bytes(12)
+    if vlan:
+        header += struct.pack("!HH", 0x8100, 5)
+    return header + struct.pack("!H", ethertype) + packet
+
+
+def linux_sll(packet):
+    ethertype = 0x86DD if packet[0] >> 4 == 6 else 0x0800
+    return struct.pack("!HHH8sH", 0, 1, 6, bytes(8), ethertype) + packet
+
+
+def loopback(packet):
+    # The address family is in host byte order, here little endian.
+    return struct.pack("<I", 24 if packet[0] >> 4 == 6 else 2) + packet
+
+
+def pcap(records, link_type=LINKTYPE_RAW, big_endian=False, nanos=False):
+    order = ">" if big_endian else "<"
+    units = 10**9 if nanos else 10**6
+    data = struct.pack(order + "IHHiIII", 0xA1B23C4D if nanos else 0xA1B2C3D4, 2, 4, 0, 0, 65535, link_type)
+    for timestamp, frame in records:
+        seconds, fraction = divmod(round(timestamp * units), units)
+        data += struct.pack(order + "IIII", seconds, fraction, len(frame), len(frame)) + frame
+    return data
+
+
+def pcapng(records, link_type=LINKTYPE_RAW, big_endian=False, tsresol=None):
+    order = ">" if big_endian else "<"
+
+    def block(block_type, body):
+        body += bytes(-len(body) % 4)
+        return struct.pack(order + "II", block_type, len(body) + 12) + body + struct.pack(order + "I", len(body) + 12)
+
+    data = block(0x0A0D0D0A, struct.pack(order + "IHHq", 0x1A2B3C4D, 1, 0, -1))
+    options = b""
+    units = 10**6
+    if tsresol is not None:
+        options = struct.pack(order + "HHB3xHH", 9, 1, tsresol, 0, 0)
+        units = 2 ** (tsresol & 0x7F) if tsresol & 0x80 else 10**tsresol
+    data += block(1, struct.pack(order + "HHI", link_type, 0, 0) + options)
+    # Unknown blocks are skipped.
+    data += block(0x0BAD, b"skip")
+    for timestamp, frame in records:
+        units_since_epoch = round(timestamp * units)
+        header = struct.pack(order + "IIIII", 0, units_since_epoch >> 32, units_since_epoch & 0xFFFFFFFF, len(frame), len(frame))
+        data += block(6, header + frame)
+    return data
+
+
+class Connection:
+    """Builds the IP packets of a TCP connection, tracking sequence numbers."""
+
+    def __init__(self, client=CLIENT, server=SERVER, seq=(1000, 5000)):
+        self.addresses = (client, server)
+        self.seq = list(seq)
+
+    def segment(self, sender, payload=b"", flags=PSH | ACK, **kwargs):
+        """Packet sent by the client (0) or the server (1), advancing its seq."""
+        source, destination = self.addresses[sender], self.addresses[1 - sender]
+        packet = ip(source, destination, tcp(source, destination, self.seq[sender], payload, flags), **kwargs)
+        self.seq[sender] += len(payload) + bool(flags & (SYN | FIN))
+        return packet
+
+    def handshake(self):
+        return [self.segment(0, flags=SYN), self.segment(1, flags=SYN | ACK), self.segment(0, flags=ACK)]
+
+    def close(self):
+        return [self.segment(0, flags=FIN | ACK), self.segment(1, flags=FIN | ACK), self.segment(0, flags=ACK)]
+
+
+def session(connection, **kwargs):
+    """Packets of a connection exchanging a Connect and a ConnAck."""
+    return (
+        connection.handshake()
+        + [connection.segment(0, CONNECT.write(), **kwargs), connection.segment(1, CONNACK.write(), **kwargs)]
+        + connection.close()
+    )
+
+
+def segments(data):
+    """`(timestamp, source, destination, seq, ack, flags, payload)` of the TCP
+    segments over raw IP in the pcap file `data` written by `PcapWriter`."""
+    magic, *_, link_type = struct.unpack_from("<IHHiIII", data)
+    assert (magic, link_type) == (0xA1B2C3D4, LINKTYPE_RAW)
+    offset, found = 24, []
+    while offset < len(data):
+        seconds, micros, length, _ = struct.unpack_from("<IIII", data, offset)
+        packet = data[offset + 16 : offset + 16 + length]
+        offset += 16 + length
+        if packet[0] >> 4 == 4:
+            header_len, addresses = (packet[0] & 0x0F) * 4, (packet[12:16], packet[16:20])
+        else:
+            header_len, addresses = 40, (packet[8:24], packet[24:40])
+        segment = packet[header_len:]
+        ports = struct.unpack_from("!HH", segment)
+        seq, ack, data_offset, flags = struct.unpack_from("!IIBB", segment, 4)
+        source, destination = ((str(ipaddress.ip_address(a)), port) for a, port in zip(addresses, ports))
+        payload = segment[(data_offset >> 4) * 4 :]
+        found.append((seconds + micros / 10**6, source, destination, seq, ack, flags, payload))
+    return found
+
+
+class CaptureTest(unittest.TestCase):
+    def read(self, data, **kwargs):
+        """Returns the packets of a capture file with `data`, and its reader."""
+        with tempfile.NamedTemporaryFile(suffix=".pcap", delete=False) as file:
+            file.write(data)
+        self.addCleanup(os.unlink, file.name)
+        reader = capture.PcapReader(file.name, **kwargs)
+        return list(reader), reader
+
+    def assertPackets(self, captured, expected):
+        self.assertEqual([p.packet for p in captured], expected)
+
+    def test_pcap_byte_orders_and_resolutions(self):
+        frames = session(Connection())
+        records = [(1.5 + i * 0.25, frame) for i, frame in enumerate(frames)]
+        for big_endian in (False, True):
+            for nanos in (False, True):
+                with self.subTest(big_endian=big_endian, nanos=nanos):
+                    captured, reader = self.read(pcap(records, big_endian=big_endian, nanos=nanos))
+                    self.assertPackets(captured, [CONNECT, CONNACK])
+                    self.assertEqual([p.timestamp for p in captured], [2.25, 2.5])
+                    self.assertEqual(reader.errors, [])
+
+    def test_pcapng_byte_orders_and_resolutions(self):
+        frames = session(Connection())
+        records = [(1.5 + i * 0.25, frame) for i, frame in enumerate(frames)]
+        for big_endian in (False, True):
+            # Microseconds by default, then nanoseconds and 2^-10 seconds.
+            for tsresol in (None, 9, 0x80 | 10):
+                with self.subTest(big_endian=big_endian, tsresol=tsresol):
+                    captured, _ = self.read(pcapng(records, big_endian=big_endian, tsresol=tsresol))
+                    self.assertPackets(captured, [CONNECT, CONNACK])
+                    self.assertEqual([p.timestamp for p in captured], [2.25, 2.5])
+
+    def test_link_types(self):
+        cases = {
+            "ethernet": (LINKTYPE_ETHERNET, ethernet, {}),
+            "vlan": (LINKTYPE_ETHERNET, lambda packet: ethernet(packet, vlan=True), {}),
+            "linux_sll": (LINKTYPE_LINUX_SLL, linux_sll, {}),
+            "loopback": (LINKTYPE_NULL, loopback, {}),
+            "ipv6_options": (LINKTYPE_RAW, lambda packet: packet, {"options": True}),
+        }
+        for name, (link_type, frame, kwargs) in cases.items():
+            with self.subTest(name):
+                connection = Connection(CLIENT6, SERVER6) if kwargs else Connection()
+                frames = [frame(packet) for packet in session(connection, **kwargs)]
+                captured, _ = self.read(pcap(list(enumerate(frames)), link_type=link_type))
+                self.assertPackets(captured, [CONNECT, CONNACK])
+                self.assertEqual(captured[0].client, connection.addresses[0])
+                self.assertEqual(captured[1].direction, capture.Direction.ServerToClient)
+
+    def test_reordered_segments(self):
+        connection = Connection()
+        frames = connection.handshake()
+        data = CONNECT.write()
+        parts = [connection.segment(0, data[i : i + 5]) for i in range(0, len(data), 5)]
+        frames += parts[::-1]
+        captured, reader = self.read(pcap(list(enumerate(frames))))
+        self.assertPackets(captured, [CONNECT])
+        self.assertEqual(reader.errors, [])
+
+    def test_retransmitted_segments(self):
+        connection = Connection()
+        frames = connection.handshake()
+        data = CONNECT.write()
+        seq = connection.seq[0]
+        frames.append(connection.segment(0, data[:6]))
+        # The first bytes again, then overlapping the rest.
+        frames.append(ip(CLIENT, SERVER, tcp(CLIENT, SERVER, seq, data[:6])))
+        frames.append(ip(CLIENT, SERVER, tcp(CLIENT, SERVER, seq + 3, data[3:])))
+        captured, reader = self.read(pcap(list(enumerate(frames))))
+        self.assertPackets(captured, [CONNECT])
+        self.assertEqual(reader.errors, [])
+
+    def test_missing_segment(self):
+        connection = Connection()
+        frames = connection.handshake()
+        connection.segment(0, b"\x30")
+        # Segments are kept waiting for the missing one up to a limit.
+        frames += [connection.segment(0, b"x") for _ in range(1025)]
+        captured, reader = self.read(pcap(list(enumerate(frames))))
+        self.assertEqual(captured, [])
+        self.assertEqual(len(reader.errors), 1)
+        self.assertIn("bytes missing from the capture", reader.errors[0])
+
+    def test_capture_starting_mid_packet(self):
+        connection = Connection()
+        publish = v4.Publish("t", QoS.AtMostOnce, b"\x00" * 8)
+        # The first captured byte is part of the payload of a publish.
+        frames = [connection.segment(0, publish.write()[-4:]), connection.segment(0, publish.write())]
+        captured, reader = self.read(pcap(list(enumerate(frames))))
+        self.assertEqual(captured, [])
+        self.assertIn("invalid fixed header byte 0x00", reader.errors[0])
+
+    def test_closed_connection_is_forgotten(self):
+        first = Connection()
+        frames = session(first)
+        # The same ports reused, captured without the handshake.
+        second = Connection(seq=(900_000, 700_000))
+        frames += [second.segment(0, CONNECT.write())]
+        captured, reader = self.read(pcap(list(enumerate(frames))))
+        self.assertPackets(captured, [CONNECT, CONNACK, CONNECT])
+        self.assertEqual(reader.errors, [])
+
+    def test_writer_round_trip(self):
+        with tempfile.NamedTemporaryFile(suffix=".pcap", delete=False) as file:
+            pass
+        self.addCleanup(os.unlink, file.name)
+        publish = v4.Publish("t", QoS.AtMostOnce, bytes(4000))
+        with capture.PcapWriter(file.name) as writer:
+            writer.write(CONNECT.write(), capture.Direction.ClientToServer, 1.0)
+            writer.write(publish.write(), capture.Direction.ClientToServer, 2.0)
+        captured = list(capture.PcapReader(file.name))
+        self.assertPackets(captured, [CONNECT, publish])
+        self.assertTrue(repr(captured[0]).startswith("CapturedPacket(timestamp=1.0, "))
+
+    def write(self, writes, **kwargs):
+        """Returns the segments written by a `PcapWriter` given `writes` of
+        `(data, direction, timestamp)`."""
+        with tempfile.NamedTemporaryFile(suffix=".pcap", delete=False) as file:
+            pass
+        self.addCleanup(os.unlink, file.name)
+        with capture.PcapWriter(file.name, **kwargs) as writer:
+            for data, direction, timestamp in writes:
+                writer.write(data, direction, timestamp)
+        with open(file.name, "rb") as file:
+            return segments(file.read())
+
+    def test_writer_splits_large_payloads(self):
+        data = bytes(range(256)) * 12
+        written = self.write([(data, capture.Direction.ClientToServer, 1.0)])
+        # After the 3 segments of the handshake, before the 3 of the close.
+        sent = written[3:-3]
+        self.assertEqual([len(s[6]) for s in sent], [1460, 1460, 152])
+        self.assertEqual(b"".join(s[6] for s in sent), data)
+        # Only the last segment is pushed.
+        self.assertEqual([s[5] for s in sent], [ACK, ACK, PSH | ACK])
+        self.assertEqual([s[3] - sent[0][3] for s in sent], [0, 1460, 2920])
+
+    def test_writer_ipv6(self):
+        written = self.write(
+            [(CONNECT.write(), capture.Direction.ClientToServer, 1.0)], client=CLIENT6, server=SERVER6
+        )
+        self.assertEqual({(s[1], s[2]) for s in written}, {(CLIENT6, SERVER6), (SERVER6, CLIENT6)})
+        with tempfile.NamedTemporaryFile(suffix=".pcap") as file:
+            with self.assertRaisesRegex(ValueError, "different families"):
+                capture.PcapWriter(file.name, client=CLIENT, server=SERVER6)
+
+    def test_writer_sequence_numbers(self):
+        writes = [
+            (CONNECT.write(), capture.Direction.ClientToServer, 1.0),
+            (CONNACK.write(), capture.Direction.ServerToClient, 2.0),
+        ]
+        written = self.write(writes)
+        flags = [s[5] for s in written]
+        self.assertEqual(flags, [SYN, SYN | ACK, ACK, PSH | ACK, PSH | ACK, FIN | ACK, FIN | ACK, ACK])
+        client, server = 0x0001_0000, 0x0002_0000
+        # SYN and FIN take a sequence number, as payload bytes do.
+        expected = [
+            (client, 0),
+            (server, client + 1),
+            (client + 1, server + 1),
+            (client + 1, server + 1),
+            (server + 1, client + 1 + len(CONNECT.write())),
+        ]
+        self.assertEqual([(s[3], s[4]) for s in written[:5]], expected)
+        client_end, server_end = client + 1 + len(CONNECT.write()), server + 1 + len(CONNACK.write())
+        expected = [(client_end, server_end), (server_end, client_end + 1), (client_end + 1, server_end + 1)]
+        self.assertEqual([(s[3], s[4]) for s in written[5:]], expected)
+        self.assertEqual([s[0] for s in written], [1.0] * 4 + [2.0] * 4)
+
+    def test_writer_timestamp_range(self):
+        with tempfile.NamedTemporaryFile(suffix=".pcap") as file:
+            with capture.PcapWriter(file.name) as writer:
+                for timestamp in (-0.5, 2.0**32, float("nan")):
+                    with self.subTest(timestamp=timestamp):
+                        with self.assertRaisesRegex(ValueError, "out of the range of pcap files"):
+                            writer.write(b"\xc0\x00", capture.Direction.ClientToServer, timestamp)
+                writer.write(b"\xc0\x00", capture.Direction.ClientToServer, 2.0**32 - 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,183 @@
+"""Tests of `Client` and `AsyncClient` against `testing.Broker`."""
+
+import asyncio
+import queue
+import unittest
+
+try:
+    from mqttbytes import AsyncClient, Client, QoS, testing
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+TIMEOUT = 5.0
+LEVELS = [QoS.AtMostOnce, QoS.AtLeastOnce, QoS.ExactlyOnce]
+
+
+class ClientTest(unittest.TestCase):
+    def setUp(self):
+        self.broker = testing.Broker()
+        self.addCleanup(self.broker.close)
+
+    def client(self, client_id, **kwargs):
+        client = Client(client_id, *self.broker.address, timeout=TIMEOUT, **kwargs)
+        client.connect()
+        self.addCleanup(client.disconnect)
+        return client
+
+    def test_publish_to_subscriber(self):
+        received = queue.Queue()
+        subscriber = self.client("sub")
+        self.assertEqual(subscriber.subscribe("t/+", QoS.AtLeastOnce, received.put), QoS.AtLeastOnce)
+        publisher = self.client("pub")
+        for qos in LEVELS:
+            with self.subTest(qos=qos):
+                publisher.publish("t/a", b"payload", qos)
+                publish = received.get(timeout=TIMEOUT)
+                self.assertEqual((publish.topic, publish.payload), ("t/a", b"payload"))
+                self.assertEqual(publish.qos, min(qos, QoS.AtLeastOnce, key=LEVELS.index))
+
+    def test_unsubscribe(self):
+        received = queue.Queue()
+        client = self.client("client", on_message=received.put)
+        client.subscribe("a")
+        client.subscribe("b")
+        client.unsubscribe("a")
+        client.publish("a", b"1", QoS.AtLeastOnce)
+        client.publish("b", b"2", QoS.AtLeastOnce)
+        self.assertEqual(received.get(timeout=TIMEOUT).topic, "b")
+        self.assertTrue(received.empty())
+
+    def test_failing_callback_is_logged(self):
+        received = queue.Queue()
+
+        def callback(publish):
+            received.put(publish)
+            if publish.payload == b"1":
+                raise RuntimeError("callback failed")
+
+        client = self.client("client")
+        client.subscribe("t", callback=callback)
+        with self.assertLogs("mqttbytes.client", "ERROR") as logs:
+            client.publish("t", b"1")
+            received.get(timeout=TIMEOUT)
+            # The network loop goes on.
+            client.publish("t", b"2", QoS.AtLeastOnce)
+            self.assertEqual(received.get(timeout=TIMEOUT).payload, b"2")
+        self.assertIn("RuntimeError: callback failed", logs.output[0])
+        self.assertTrue(client.connected)
+
+    def test_invalid_filter_is_rejected_before_queueing(self):
+        client = self.client("client")
+        for topic in ("a/#/b", "a+"):
+            with self.subTest(topic), self.assertRaisesRegex(ValueError, "invalid filter"):
+                client.subscribe(topic, callback=print)
+        self.assertEqual(client._waiters, {})
+        self.assertEqual(client.session.data_to_send(), b"")
+        self.assertEqual(client.subscribe("a"), QoS.AtMostOnce)
+
+    def test_retained_message(self):
+        self.client("pub").publish("r", b"kept", QoS.AtLeastOnce, retain=True)
+        received = queue.Queue()
+        self.client("sub").subscribe("r", callback=received.put)
+        publish = received.get(timeout=TIMEOUT)
+        self.assertEqual((publish.payload, publish.retain), (b"kept", True))
+        self.assertIn("r", self.broker.retained)
+
+    def test_connected(self):
+        client = self.client("client")
+        self.assertTrue(client.connected)
+        self.assertEqual(self.broker.clients, ["client"])
+        client.disconnect()
+        self.assertFalse(client.connected)
+
+
+class AsyncClientTest(unittest.IsolatedAsyncioTestCase):
+    def setUp(self):
+        self.broker = testing.Broker()
+        self.addCleanup(self.broker.close)
+
+    async def client(self, client_id, **kwargs):
+        client = AsyncClient(client_id, *self.broker.address, timeout=TIMEOUT, **kwargs)
+        await client.connect()
+        self.addAsyncCleanup(client.disconnect)
+        return client
+
+    async def test_publish_to_subscriber(self):
+        received = asyncio.Queue()
+        subscriber = await self.client("sub")
+        granted = await subscriber.subscribe("t/#", QoS.ExactlyOnce, received.put)
+        self.assertEqual(granted, QoS.ExactlyOnce)
+        publisher = await self.client("pub")
+        for qos in LEVELS:
+            with self.subTest(qos=qos):
+                await publisher.publish("t/a/b", b"payload", qos)
+                publish = await asyncio.wait_for(received.get(), TIMEOUT)
+                self.assertEqual((publish.topic, publish.payload, publish.qos), ("t/a/b", b"payload", qos))
+
+    async def test_unsubscribe(self):
+        received = asyncio.Queue()
+        client = await self.client("client", on_message=received.put)
+        await client.subscribe("a")
+        await client.subscribe("b")
+        await client.unsubscribe("a")
+        await client.publish("a", b"1", QoS.AtLeastOnce)
+        await client.publish("b", b"2", QoS.AtLeastOnce)
+        self.assertEqual((await asyncio.wait_for(received.get(), TIMEOUT)).topic, "b")
+        self.assertTrue(received.empty())
+
+    async def test_requests_drain_the_writer(self):
+        client = await self.client("client")
+        drain = client._writer.drain
+        drained = []
+
+        async def counting_drain():
+            drained.append(True)
+            await drain()
+
+        client._writer.drain = counting_drain
+        await client.subscribe("a")
+        await client.unsubscribe("a")
+        self.assertEqual(len(drained), 2)
+
+    async def test_failing_callback_is_logged(self):
+        received = asyncio.Queue()
+
+        async def callback(publish):
+            await received.put(publish)
+            if publish.payload == b"1":
+                raise RuntimeError("callback failed")
+
+        client = await self.client("client")
+        await client.subscribe("t", callback=callback)
+        with self.assertLogs("mqttbytes.client", "ERROR") as logs:
+            await client.publish("t", b"1")
+            await asyncio.wait_for(received.get(), TIMEOUT)
+            await client.publish("t", b"2", QoS.AtLeastOnce)
+            self.assertEqual((await asyncio.wait_for(received.get(), TIMEOUT)).payload, b"2")
+        self.assertIn("RuntimeError: callback failed", logs.output[0])
+        self.assertTrue(client.connected)
+
+    async def test_callback_awaiting_a_request(self):
+        acknowledged = asyncio.Queue()
+        client = await self.client("client")
+
+        async def callback(publish):
+            # The network loop must go on to read the PubAck.
+            await acknowledged.put(await client.publish("ack", publish.payload, QoS.AtLeastOnce))
+
+        await client.subscribe("t", callback=callback)
+        await client.publish("t", b"x")
+        self.assertGreater(await asyncio.wait_for(acknowledged.get(), TIMEOUT), 0)
+
+    async def test_invalid_filter_is_rejected_before_queueing(self):
+        client = await self.client("client")
+        for topic in ("a/#/b", "a+"):
+            with self.subTest(topic), self.assertRaisesRegex(ValueError, "invalid filter"):
+                await client.subscribe(topic, callback=print)
+        self.assertEqual(client._waiters, {})
+        self.assertEqual(client.session.data_to_send(), b"")
+        self.assertEqual(await client.subscribe("a"), QoS.AtMostOnce)
+
+
+if __name__ == "__main__":
+    unittest.main()
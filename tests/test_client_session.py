@@ -0,0 +1,69 @@
+"""Tests of `v4.ClientSession`."""
+
+import unittest
+
+try:
+    from mqttbytes import ProtocolError, QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+def connected_session():
+    session = v4.ClientSession(v4.Connect("client"), 1024)
+    session.connect()
+    session.data_to_send()
+    session.receive_data(v4.ConnAck(v4.ConnectReturnCode.Success).write())
+    return session
+
+
+class ClientSessionTest(unittest.TestCase):
+    def test_error_after_events_is_raised_by_next_call(self):
+        session = connected_session()
+        publish = v4.Publish("t", QoS.AtMostOnce, b"x")
+        data = publish.write() + v4.PubAck(55).write() + v4.PingResp().write()
+        self.assertEqual(session.receive_data(data), [publish])
+        with self.assertRaisesRegex(ProtocolError, "PubAck"):
+            session.receive_data(b"")
+        # Packets after the invalid one are still decoded.
+        self.assertEqual(session.receive_data(b""), [v4.PingResp()])
+
+    def test_error_without_events_is_raised(self):
+        session = connected_session()
+        with self.assertRaises(ProtocolError):
+            session.receive_data(v4.PubAck(55).write())
+        self.assertEqual(session.receive_data(v4.PingResp().write()), [v4.PingResp()])
+
+    def test_packet_ids_are_shared_and_released_by_acknowledgements(self):
+        session = connected_session()
+        self.assertEqual(session.publish(v4.Publish("t", QoS.AtLeastOnce, b"")), 1)
+        self.assertEqual(session.subscribe(v4.Subscribe("t", QoS.AtMostOnce)), 2)
+        self.assertEqual(session.publish(v4.Publish("t", QoS.ExactlyOnce, b"")), 3)
+        data = v4.SubAck(2, [QoS.AtMostOnce]).write() + v4.PubAck(1).write() + v4.PubRec(3).write()
+        session.receive_data(data)
+        for pkid in range(4, 65536):
+            self.assertEqual(session.unsubscribe(v4.Unsubscribe("t")), pkid)
+            session.data_to_send()
+        # After wrapping, released ids are reused and 3 waits for its PubComp.
+        self.assertEqual(session.publish(v4.Publish("t", QoS.AtLeastOnce, b"")), 1)
+        self.assertEqual(session.publish(v4.Publish("t", QoS.AtLeastOnce, b"")), 2)
+        with self.assertRaisesRegex(ProtocolError, "all packet ids are in flight"):
+            session.publish(v4.Publish("t", QoS.AtLeastOnce, b""))
+        session.receive_data(v4.PubComp(3).write())
+        self.assertEqual(session.subscribe(v4.Subscribe("t", QoS.AtMostOnce)), 3)
+
+    def test_qos1_publish_is_acknowledged(self):
+        session = connected_session()
+        publish = v4.Publish("t", QoS.AtLeastOnce, b"x", pkid=3)
+        self.assertEqual(session.receive_data(publish.write()), [publish])
+        self.assertEqual(session.data_to_send(), v4.PubAck(3).write())
+
+    def test_receive_data_accepts_buffers(self):
+        session = connected_session()
+        publish = v4.Publish("t", QoS.AtMostOnce, b"x")
+        buffer = bytearray(publish.write() + v4.PingResp().write())
+        self.assertEqual(session.receive_data(memoryview(buffer)[:3]), [])
+        self.assertEqual(session.receive_data(buffer[3:]), [publish, v4.PingResp()])
+
+
+if __name__ == "__main__":
+    unittest.main()
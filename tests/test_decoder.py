@@ -0,0 +1,42 @@
+"""Tests of `v4.StreamDecoder`."""
+
+import unittest
+
+try:
+    from mqttbytes import QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+PUBLISH = v4.Publish("a/b", QoS.AtLeastOnce, b"payload", pkid=7)
+
+
+class StreamDecoderTest(unittest.TestCase):
+    def test_packet_fed_byte_by_byte(self):
+        decoder = v4.StreamDecoder(1024)
+        data = PUBLISH.write()
+        for index in range(len(data) - 1):
+            decoder.feed(data[index : index + 1])
+            self.assertEqual(list(decoder), [])
+        self.assertEqual(len(decoder), len(data) - 1)
+        decoder.feed(bytearray(data[-1:]))
+        self.assertEqual(list(decoder), [PUBLISH])
+        self.assertEqual(len(decoder), 0)
+
+    def test_memoryview_slice(self):
+        decoder = v4.StreamDecoder(1024)
+        # As filled by `socket.recv_into`, with unused bytes after the packet.
+        buffer = bytearray(64)
+        data = PUBLISH.write()
+        buffer[: len(data)] = data
+        decoder.feed(memoryview(buffer)[: len(data)])
+        buffer[:] = bytes(64)
+        self.assertEqual(list(decoder), [PUBLISH])
+
+    def test_non_contiguous_buffer(self):
+        decoder = v4.StreamDecoder(1024)
+        with self.assertRaises(BufferError):
+            decoder.feed(memoryview(PUBLISH.write())[::2])
+
+
+if __name__ == "__main__":
+    unittest.main()
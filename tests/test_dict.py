@@ -0,0 +1,47 @@
+"""Tests of `to_dict` and `from_dict` on `v4` packets."""
+
+import json
+import unittest
+
+try:
+    from mqttbytes import QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+class DictTest(unittest.TestCase):
+    def test_round_trip_through_json(self):
+        for packet in (v4.Subscribe([("a/#", QoS.ExactlyOnce)], pkid=2), v4.SubAck(2, [None])):
+            with self.subTest(packet=packet):
+                data = json.loads(json.dumps(packet.to_dict()))
+                self.assertEqual(v4.packet_from_dict(data), packet)
+        # Only packets with binary fields take an encoding.
+        binary = [
+            v4.Publish("t", QoS.AtLeastOnce, b"\x00\xff", pkid=1),
+            v4.Connect("c", last_will=v4.LastWill("w", b"\x01", QoS.AtMostOnce)),
+        ]
+        for encoding in ("base64", "hex"):
+            for packet in binary:
+                with self.subTest(packet=packet, encoding=encoding):
+                    data = json.loads(json.dumps(packet.to_dict(encoding)))
+                    self.assertEqual(v4.packet_from_dict(data, encoding), packet)
+
+    def test_unknown_encoding(self):
+        connect = v4.Connect("c")
+        calls = {
+            "Connect.to_dict": lambda: connect.to_dict("bogus"),
+            "Connect.from_dict": lambda: v4.Connect.from_dict(connect.to_dict(), "bogus"),
+            "Publish.to_dict": lambda: v4.Publish("t", QoS.AtMostOnce, b"").to_dict("bogus"),
+        }
+        for name, call in calls.items():
+            with self.subTest(name):
+                with self.assertRaisesRegex(ValueError, "unknown encoding"):
+                    call()
+
+    def test_type_mismatch(self):
+        with self.assertRaises(ValueError):
+            v4.Publish.from_dict(v4.PubAck(1).to_dict())
+
+
+if __name__ == "__main__":
+    unittest.main()
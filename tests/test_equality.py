@@ -0,0 +1,82 @@
+"""Tests of equality, hashing and reprs of packets and helper types."""
+
+import unittest
+
+try:
+    from mqttbytes import FixedHeader, PacketType, QoS, check, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+def packets():
+    """One packet of each v4 type, built anew on each call."""
+    return [
+        v4.Connect("client", login=v4.Login("user", "secret")),
+        v4.ConnAck(v4.ConnectReturnCode.Success, session_present=True),
+        v4.Publish("a/b", QoS.AtLeastOnce, b"payload", pkid=1),
+        v4.PubAck(1),
+        v4.PubRec(1),
+        v4.PubRel(1),
+        v4.PubComp(1),
+        v4.Subscribe("a/#", QoS.AtMostOnce, pkid=2),
+        v4.SubAck(2, [QoS.AtMostOnce]),
+        v4.Unsubscribe("a/#", pkid=3),
+        v4.UnsubAck(3),
+        v4.PingReq(),
+        v4.PingResp(),
+        v4.Disconnect(),
+    ]
+
+
+class EqualityTest(unittest.TestCase):
+    def test_equal_packets(self):
+        for packet, same in zip(packets(), packets()):
+            with self.subTest(type(packet).__name__):
+                self.assertIsNot(packet, same)
+                self.assertEqual(packet, same)
+                self.assertFalse(packet != same)
+                self.assertEqual(packet, v4.read(packet.write(), 1024))
+
+    def test_different_types_are_not_equal(self):
+        for packet in packets():
+            with self.subTest(type(packet).__name__):
+                self.assertIs(packet.__eq__("packet"), NotImplemented)
+                self.assertNotEqual(packet, "packet")
+                self.assertNotEqual(packet, None)
+        # Acknowledgements with the same packet id are still different packets.
+        self.assertNotEqual(v4.PubAck(1), v4.PubRec(1))
+        self.assertIs(FixedHeader(0x30, 1, 5).__eq__(5), NotImplemented)
+
+    def test_immutable_values_hash_equal(self):
+        header = FixedHeader(0x30, 1, 5)
+        checked = check(v4.Publish("abc", QoS.AtMostOnce, b"").write(), 1024)
+        self.assertEqual(header, checked)
+        self.assertEqual(hash(header), hash(checked))
+        self.assertNotEqual(hash(header), hash(FixedHeader(0x32, 1, 5)))
+        # The remaining length takes two bytes.
+        data = v4.Publish("a", QoS.AtMostOnce, bytes(200)).write()
+        self.assertEqual(hash(check(data, 1024)), hash(FixedHeader(0x30, 2, len(data) - 3)))
+        for make in (v4.PingReq, v4.PingResp, v4.Disconnect):
+            self.assertEqual(hash(make()), hash(make()))
+        self.assertEqual(len({QoS.AtLeastOnce, QoS.AtLeastOnce, PacketType.Publish}), 2)
+
+    def test_mutable_packets_are_unhashable(self):
+        for packet in packets()[:-3]:
+            with self.subTest(type(packet).__name__), self.assertRaises(TypeError):
+                hash(packet)
+
+    def test_repr_masks_the_password(self):
+        login = v4.Login("user", "secret")
+        self.assertEqual(repr(login), "Login(username='user', password='***')")
+        self.assertNotIn("secret", repr(packets()[0]))
+
+    def test_repr_truncates_payloads(self):
+        publish = v4.Publish("a", QoS.AtMostOnce, bytes(200))
+        self.assertIn("...(200 bytes)", repr(publish))
+        self.assertLess(len(repr(publish)), 300)
+        self.assertEqual(repr(v4.PubAck(7)), "PubAck(pkid=7)")
+        self.assertEqual(repr(FixedHeader(0x30, 1, 5)), "FixedHeader(packet_type=PacketType.Publish, frame_length=7)")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,62 @@
+"""Tests of the `MqttBytesError` subclass raised for each upstream error."""
+
+import unittest
+
+try:
+    import mqttbytes
+    from mqttbytes import MqttBytesError, QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+# A Connect of protocol level 4 with an empty client id, and its protocol level.
+CONNECT = bytes([0x10, 12, 0, 4]) + b"MQTT" + bytes([4, 2, 0, 10, 0, 0])
+LEVEL = 8
+
+
+class ErrorTest(unittest.TestCase):
+    def assertError(self, data, error, max_size=1024, **attributes):
+        """Asserts that reading `data` raises `error` with `attributes`."""
+        with self.assertRaises(error) as context:
+            v4.read(data, max_size)
+        self.assertIsInstance(context.exception, MqttBytesError)
+        for name, value in attributes.items():
+            self.assertEqual(getattr(context.exception, name), value)
+        return context.exception
+
+    def test_insufficient_bytes(self):
+        self.assertError(bytes([0x30, 5, 0]), mqttbytes.InsufficientBytes, required=4)
+
+    def test_invalid_qos(self):
+        # QoS 3 in the flags of a publish to "a".
+        self.assertError(bytes([0x36, 5, 0, 1, 0x61, 0, 1]), mqttbytes.InvalidQoS, qos=3)
+
+    def test_payload_size_limit_exceeded(self):
+        data = v4.Publish("a", QoS.AtMostOnce, bytes(100)).write()
+        self.assertError(data, mqttbytes.PayloadSizeLimitExceeded, max_size=10, size=len(data) - 2)
+        with self.assertRaises(mqttbytes.PayloadSizeLimitExceeded) as context:
+            mqttbytes.check(data, 10)
+        self.assertEqual(context.exception.size, len(data) - 2)
+
+    def test_malformed_remaining_length(self):
+        # Every byte of the remaining length has the continuation bit set.
+        error = self.assertError(bytes([0x30, 0xFF, 0xFF, 0xFF, 0xFF, 1]), mqttbytes.MalformedRemainingLength)
+        self.assertIn("MalformedRemainingLength", str(error))
+
+    def test_invalid_packet_type(self):
+        self.assertError(bytes([0xF0, 0]), mqttbytes.InvalidPacketType, packet_type=15)
+
+    def test_invalid_connect_return_code(self):
+        self.assertError(bytes([0x20, 2, 0, 9]), mqttbytes.InvalidConnectReturnCode, code=9)
+
+    def test_invalid_protocol(self):
+        self.assertError(CONNECT.replace(b"MQTT", b"MQTX"), mqttbytes.InvalidProtocol)
+        data = CONNECT[:8] + bytes([LEVEL]) + CONNECT[9:]
+        self.assertError(data, mqttbytes.InvalidProtocolLevel, level=LEVEL)
+
+    def test_subclasses(self):
+        self.assertTrue(issubclass(mqttbytes.ValidationError, MqttBytesError))
+        self.assertFalse(issubclass(mqttbytes.ProtocolError, MqttBytesError))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,81 @@
+"""Tests of `v4.PacketIdAllocator`."""
+
+import unittest
+
+try:
+    from mqttbytes import ProtocolError, QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+def allocate_until(allocator, last):
+    """Allocates and releases ids until `last` was handed out."""
+    while (pkid := allocator.allocate()) != last:
+        allocator.release(pkid)
+
+
+class PacketIdAllocatorTest(unittest.TestCase):
+    def test_ids_increase_from_one(self):
+        allocator = v4.PacketIdAllocator()
+        self.assertEqual([allocator.allocate() for _ in range(3)], [1, 2, 3])
+        self.assertEqual(allocator.in_flight, [1, 2, 3])
+        self.assertEqual(len(allocator), 3)
+
+    def test_wrap_skips_zero(self):
+        allocator = v4.PacketIdAllocator()
+        allocate_until(allocator, 65535)
+        self.assertEqual(allocator.allocate(), 1)
+        self.assertEqual(allocator.in_flight, [1, 65535])
+        self.assertNotIn(0, allocator)
+
+    def test_ids_in_flight_are_skipped(self):
+        allocator = v4.PacketIdAllocator()
+        kept = [allocator.allocate() for _ in range(3)]
+        allocate_until(allocator, 65535)
+        # 1 to 3 are still in flight after wrapping.
+        self.assertEqual(allocator.allocate(), 4)
+        self.assertEqual(allocator.in_flight, kept + [4, 65535])
+
+    def test_exhaustion(self):
+        allocator = v4.PacketIdAllocator()
+        for _ in range(65535):
+            allocator.allocate()
+        with self.assertRaisesRegex(ProtocolError, "all packet ids are in flight"):
+            allocator.allocate()
+        self.assertTrue(allocator.acknowledge(v4.PubAck(500)))
+        self.assertEqual(allocator.allocate(), 500)
+
+    def test_acknowledge_releases_ids(self):
+        allocator = v4.PacketIdAllocator()
+        for _ in range(4):
+            allocator.allocate()
+        acknowledgements = [v4.PubAck(1), v4.PubComp(2), v4.SubAck(3, [QoS.AtMostOnce]), v4.UnsubAck(4)]
+        for packet in acknowledgements:
+            with self.subTest(type(packet).__name__):
+                self.assertIn(packet.pkid, allocator)
+                self.assertTrue(allocator.acknowledge(packet))
+                self.assertNotIn(packet.pkid, allocator)
+                # An id is only released once.
+                self.assertFalse(allocator.acknowledge(packet))
+        self.assertEqual(len(allocator), 0)
+
+    def test_released_ids_are_reused(self):
+        allocator = v4.PacketIdAllocator()
+        allocate_until(allocator, 65535)
+        allocator.release(65535)
+        self.assertEqual(allocator.allocate(), 1)
+        allocator.acknowledge(v4.PubAck(1))
+        self.assertEqual(allocator.allocate(), 2)
+        allocate_until(allocator, 65535)
+        self.assertEqual(allocator.allocate(), 1)
+
+    def test_pubrec_keeps_the_id(self):
+        allocator = v4.PacketIdAllocator()
+        allocator.allocate()
+        with self.assertRaisesRegex(TypeError, "expected PubAck, PubComp, SubAck or UnsubAck, got PubRec"):
+            allocator.acknowledge(v4.PubRec(1))
+        self.assertIn(1, allocator)
+
+
+if __name__ == "__main__":
+    unittest.main()
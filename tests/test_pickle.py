@@ -0,0 +1,60 @@
+"""Round trips of `v4` packets through pickle and copy."""
+
+import copy
+import pickle
+import unittest
+
+try:
+    from mqttbytes import QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+PACKETS = [
+    # A packet id is only assigned once a session sends the publish.
+    v4.Publish("a", QoS.AtLeastOnce, b"", pkid=0),
+    # The packet id of a QoS0 publish isn't encoded.
+    v4.Publish("a", QoS.AtMostOnce, b"payload", pkid=5, retain=True, dup=True),
+    v4.SubAck(1, []),
+    v4.SubAck(2, [QoS.AtLeastOnce, None]),
+    v4.Subscribe(),
+    v4.Subscribe([("a/+", QoS.ExactlyOnce), ("b", QoS.AtMostOnce)], pkid=3),
+    v4.Unsubscribe(),
+    v4.Unsubscribe(["a", "b"], pkid=4),
+    v4.PubAck(0),
+    v4.PubRec(6),
+    v4.PubRel(7),
+    v4.PubComp(8),
+    v4.UnsubAck(9),
+    v4.Connect(""),
+    v4.Connect(
+        "client",
+        keep_alive=0,
+        clean_session=False,
+        last_will=v4.LastWill("will", b"\x00\xff", QoS.ExactlyOnce, True),
+        login=v4.Login("user", "secret"),
+    ),
+    v4.ConnAck(v4.ConnectReturnCode.NotAuthorized, True),
+    v4.PingReq(),
+    v4.PingResp(),
+    v4.Disconnect(),
+]
+
+
+class PickleTest(unittest.TestCase):
+    def test_pickle(self):
+        for packet in PACKETS:
+            for protocol in range(pickle.HIGHEST_PROTOCOL + 1):
+                with self.subTest(packet=packet, protocol=protocol):
+                    unpickled = pickle.loads(pickle.dumps(packet, protocol))
+                    self.assertIs(type(unpickled), type(packet))
+                    self.assertEqual(unpickled, packet)
+
+    def test_copy(self):
+        for packet in PACKETS:
+            with self.subTest(packet=packet):
+                self.assertEqual(copy.copy(packet), packet)
+                self.assertEqual(copy.deepcopy(packet), packet)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,46 @@
+"""Tests of `v4.RetainedStore`."""
+
+import unittest
+
+try:
+    from mqttbytes import QoS, SubscriptionTree, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+TOPICS = ["a", "a/b", "a/b/c", "b", "$SYS/x", "$SYS/x/y", "/a", "a/"]
+FILTERS = ["#", "+", "a/#", "a/+", "+/b", "+/+/c", "$SYS/x", "$SYS/#", "$SYS/+", "+/x", "/+", "a/+/#"]
+
+
+def retained_topics(store, path):
+    return sorted(p.topic for p in store.retained(v4.SubscribeFilter(path, QoS.AtMostOnce)))
+
+
+class RetainedStoreTest(unittest.TestCase):
+    def setUp(self):
+        self.store = v4.RetainedStore()
+        for topic in TOPICS:
+            self.store.store(v4.Publish(topic, QoS.AtLeastOnce, b"x", retain=True))
+
+    def test_dollar_topics(self):
+        self.assertEqual(retained_topics(self.store, "$SYS/x"), ["$SYS/x"])
+        self.assertEqual(retained_topics(self.store, "$SYS/#"), ["$SYS/x", "$SYS/x/y"])
+        self.assertNotIn("$SYS/x", retained_topics(self.store, "#"))
+        self.assertNotIn("$SYS/x", retained_topics(self.store, "+/x"))
+
+    def test_matches_like_subscription_tree(self):
+        for path in FILTERS:
+            with self.subTest(filter=path):
+                tree = SubscriptionTree()
+                tree.insert(path, None)
+                expected = sorted(topic for topic in TOPICS if tree.matches(topic))
+                self.assertEqual(retained_topics(self.store, path), expected)
+
+    def test_downgrades_qos(self):
+        store = v4.RetainedStore()
+        store.store(v4.Publish("t", QoS.ExactlyOnce, b"x", retain=True, pkid=4))
+        (publish,) = store.retained(v4.SubscribeFilter("t", QoS.AtLeastOnce))
+        self.assertEqual((publish.qos, publish.pkid, publish.retain), (QoS.AtLeastOnce, 0, True))
+
+
+if __name__ == "__main__":
+    unittest.main()
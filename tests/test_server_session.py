@@ -0,0 +1,61 @@
+"""Tests of `v4.ServerSession`."""
+
+import unittest
+
+try:
+    from mqttbytes import ProtocolError, QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+def connected_session():
+    session = v4.ServerSession(1024)
+    session.receive_data(v4.Connect("client").write())
+    session.data_to_send()
+    return session
+
+
+class ServerSessionTest(unittest.TestCase):
+    def test_error_after_events_is_raised_by_next_call(self):
+        session = connected_session()
+        publish = v4.Publish("t", QoS.AtMostOnce, b"x")
+        data = publish.write() + v4.PubAck(55).write() + v4.PingReq().write()
+        self.assertEqual(session.receive_data(data), [publish])
+        with self.assertRaisesRegex(ProtocolError, "PubAck"):
+            session.receive_data(b"")
+        self.assertEqual(session.receive_data(b""), [])
+        self.assertEqual(session.data_to_send(), v4.PingResp().write())
+
+    def test_receive_data_accepts_buffers(self):
+        session = v4.ServerSession(1024)
+        data = bytearray(v4.Connect("client").write() + v4.PingReq().write())
+        self.assertEqual(session.receive_data(data), [v4.Connect("client")])
+        self.assertEqual(session.data_to_send(), v4.ConnAck(v4.ConnectReturnCode.Success).write() + v4.PingResp().write())
+        self.assertEqual(session.receive_data(memoryview(v4.PingReq().write())), [])
+        self.assertEqual(session.data_to_send(), v4.PingResp().write())
+
+    def test_first_packet_must_be_connect(self):
+        session = v4.ServerSession(1024)
+        with self.assertRaisesRegex(ProtocolError, "expected Connect"):
+            session.receive_data(v4.PingReq().write())
+        self.assertTrue(session.closed)
+
+    def test_publish_to_invalid_topic_closes(self):
+        session = connected_session()
+        with self.assertRaisesRegex(ProtocolError, "invalid topic"):
+            session.receive_data(v4.Publish("a/#", QoS.AtMostOnce, b"").write())
+        self.assertTrue(session.closed)
+
+    def test_subscriptions_are_granted_at_most_max_qos(self):
+        session = v4.ServerSession(1024, max_qos=QoS.AtLeastOnce)
+        session.receive_data(v4.Connect("client").write())
+        session.data_to_send()
+        subscribe = v4.Subscribe([("a", QoS.ExactlyOnce), ("b/#/c", QoS.AtMostOnce)], pkid=1)
+        self.assertEqual(session.receive_data(subscribe.write()), [subscribe])
+        self.assertEqual(
+            session.data_to_send(), v4.SubAck(1, [QoS.AtLeastOnce, None]).write()
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,50 @@
+"""Tests of the `strict` flag of the `v4` readers."""
+
+import unittest
+
+try:
+    from mqttbytes import QoS, ValidationError, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+VALID = v4.Publish("a/b", QoS.AtMostOnce, b"x")
+# Wildcards aren't allowed in the topic of a publish.
+INVALID = v4.Publish("a/+", QoS.AtMostOnce, b"y")
+
+
+class StrictTest(unittest.TestCase):
+    def test_read(self):
+        self.assertEqual(v4.read(INVALID.write(), 1024), INVALID)
+        with self.assertRaisesRegex(ValidationError, "wildcard"):
+            v4.read(INVALID.write(), 1024, strict=True)
+
+    def test_read_from(self):
+        data = VALID.write() + INVALID.write()
+        offset = len(VALID.write())
+        self.assertEqual(v4.read_from(data, offset, 1024), (INVALID, len(data) - offset))
+        self.assertEqual(v4.read_from(data, 0, 1024, strict=True), (VALID, offset))
+        with self.assertRaisesRegex(ValidationError, "wildcard"):
+            v4.read_from(data, offset, 1024, strict=True)
+
+    def test_read_all_stops_before_an_invalid_packet(self):
+        data = VALID.write() + INVALID.write() + VALID.write()
+        self.assertEqual(v4.read_all(data, 1024), ([VALID, INVALID, VALID], len(data)))
+        packets, consumed = v4.read_all(data, 1024, strict=True)
+        self.assertEqual((packets, consumed), ([VALID], len(VALID.write())))
+        with self.assertRaisesRegex(ValidationError, "wildcard"):
+            v4.read_all(data[consumed:], 1024, strict=True)
+
+    def test_stream_decoder_drops_an_invalid_packet(self):
+        decoder = v4.StreamDecoder(1024, strict=True)
+        self.assertTrue(decoder.strict)
+        decoder.feed(INVALID.write() + VALID.write())
+        with self.assertRaisesRegex(ValidationError, "wildcard"):
+            next(decoder)
+        self.assertEqual(list(decoder), [VALID])
+        lenient = v4.StreamDecoder(1024)
+        lenient.feed(INVALID.write())
+        self.assertEqual(list(lenient), [INVALID])
+
+
+if __name__ == "__main__":
+    unittest.main()
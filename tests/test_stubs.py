@@ -0,0 +1,99 @@
+"""Fails when the `.pyi` stubs drift from the bindings.
+
+Run with `python -m unittest discover tests` after building the extension,
+e.g. with `maturin develop`. Comparing with the built extension is skipped
+when it can't be imported.
+"""
+
+import ast
+import difflib
+import importlib
+import sys
+import unittest
+from pathlib import Path
+
+sys.path.insert(0, str(Path(__file__).resolve().parent.parent / "tools"))
+
+import generate_stubs  # noqa: E402
+
+
+def stub_names(body):
+    """Returns the names declared in the body of a stub module or class."""
+    names = set()
+    for node in body:
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef, ast.ClassDef)):
+            names.add(node.name)
+        elif isinstance(node, ast.AnnAssign):
+            names.add(node.target.id)
+        elif isinstance(node, ast.ImportFrom):
+            names.update(alias.asname for alias in node.names if alias.asname)
+    return names
+
+
+def public(names):
+    return {name for name in names if not name.startswith("_")}
+
+
+def defined(module):
+    """Returns the public names defined by a module, rather than imported."""
+    names = set()
+    for name, value in vars(module).items():
+        # The extension the package re-exports isn't part of its API.
+        if name == "mqttbytes" and module.__name__ == "mqttbytes":
+            continue
+        if getattr(value, "__module__", None) == module.__name__:
+            names.add(name)
+        elif getattr(value, "__name__", "").startswith(module.__name__ + "."):
+            names.add(name)
+    return public(names)
+
+
+class StubsTest(unittest.TestCase):
+    def test_stubs_are_up_to_date(self):
+        for name, text in generate_stubs.generate().items():
+            with self.subTest(stub=name):
+                path = generate_stubs.STUBS / name
+                self.assertTrue(path.exists(), f"{name} is missing, run tools/generate_stubs.py")
+                diff = difflib.unified_diff(
+                    path.read_text().splitlines(), text.splitlines(), "committed", "generated", lineterm=""
+                )
+                diff = "\n".join(diff)
+                if diff:
+                    self.fail(f"{name} is outdated, run tools/generate_stubs.py\n{diff}")
+
+    def test_stubs_match_extension(self):
+        try:
+            importlib.import_module("mqttbytes")
+        except ImportError:
+            self.skipTest("mqttbytes is not built")
+        for path in sorted(generate_stubs.STUBS.glob("*.pyi")):
+            name = "mqttbytes" if path.stem == "__init__" else f"mqttbytes.{path.stem}"
+            module = importlib.import_module(name)
+            tree = ast.parse(path.read_text())
+            aliases = {
+                node.target.id
+                for node in tree.body
+                if isinstance(node, ast.AnnAssign) and "TypeAlias" in ast.unparse(node.annotation)
+            }
+            with self.subTest(module=name):
+                declared = public(stub_names(tree.body)) - aliases
+                self.assertEqual(declared - public(vars(module)), set(), "missing from the module")
+                self.assertEqual(defined(module) - declared, set(), "missing from the stub")
+            for node in tree.body:
+                if not isinstance(node, ast.ClassDef):
+                    continue
+                with self.subTest(module=name, cls=node.name):
+                    runtime = getattr(module, node.name)
+                    names = stub_names(node.body)
+                    if issubclass(runtime, BaseException):
+                        # Exception attributes are set on instances when raised.
+                        names -= {n.target.id for n in node.body if isinstance(n, ast.AnnAssign)}
+                        self.assertEqual(public(dir(runtime)) - public(dir(Exception)), set())
+                    else:
+                        self.assertEqual(public(names), public(dir(runtime)))
+                    for member in names - public(names) - {"__init__"}:
+                        self.assertTrue(hasattr(runtime, member), member)
+
+
+if __name__ == "__main__":
+    unittest.main()
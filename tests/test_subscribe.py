@@ -0,0 +1,53 @@
+"""Tests of `v4.Subscribe` and `v4.Unsubscribe` as lists."""
+
+import unittest
+
+try:
+    from mqttbytes import QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+class SubscribeTest(unittest.TestCase):
+    def test_len_is_encoded_length(self):
+        subscribe = v4.Subscribe([("a", QoS.AtMostOnce), ("b/#", QoS.AtLeastOnce)], pkid=1)
+        # The remaining length, without the 2 bytes of fixed header.
+        self.assertEqual(len(subscribe), len(subscribe.write()) - 2)
+        self.assertEqual(len(subscribe.filters), 2)
+
+    def test_single_tuple_is_one_filter(self):
+        subscribe = v4.Subscribe(("a", 1))
+        self.assertEqual([(f.path, f.qos) for f in subscribe], [("a", QoS.AtLeastOnce)])
+        # A tuple of paths is still an iterable of filters.
+        self.assertEqual(len(v4.Subscribe(("a", "b"), QoS.AtMostOnce).filters), 2)
+
+    def test_contains_other_types(self):
+        subscribe = v4.Subscribe("a", QoS.AtMostOnce)
+        self.assertIn("a", subscribe)
+        self.assertIn(("a", QoS.AtMostOnce), subscribe)
+        for item in (1, None, b"a", ("a", "b")):
+            with self.subTest(item=item):
+                self.assertNotIn(item, subscribe)
+
+    def test_reversed(self):
+        subscribe = v4.Subscribe(["a", "b", "c"], QoS.AtMostOnce)
+        self.assertEqual([f.path for f in reversed(subscribe)], ["c", "b", "a"])
+
+
+class UnsubscribeTest(unittest.TestCase):
+    def test_topics_count(self):
+        self.assertEqual(len(v4.Unsubscribe(["a", "b", "c"]).topics), 3)
+
+    def test_contains_other_types(self):
+        unsubscribe = v4.Unsubscribe("a")
+        self.assertIn("a", unsubscribe)
+        for item in (1, None, b"a"):
+            with self.subTest(item=item):
+                self.assertNotIn(item, unsubscribe)
+
+    def test_reversed(self):
+        self.assertEqual(list(reversed(v4.Unsubscribe(["a", "b"]))), ["b", "a"])
+
+
+if __name__ == "__main__":
+    unittest.main()
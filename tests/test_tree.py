@@ -0,0 +1,77 @@
+"""Tests of `SubscriptionTree`."""
+
+import unittest
+
+try:
+    from mqttbytes import SubscriptionTree
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+def matching(tree, topic):
+    return sorted(filter for filter, _ in tree.matches(topic))
+
+
+class SubscriptionTreeTest(unittest.TestCase):
+    def test_insert_get_and_remove(self):
+        tree = SubscriptionTree()
+        self.assertIsNone(tree.insert("a/b", 1))
+        self.assertEqual(tree.insert("a/b", 2), 1)
+        self.assertEqual(tree.get("a/b"), 2)
+        self.assertIn("a/b", tree)
+        self.assertNotIn("a", tree)
+        self.assertEqual(tree.matches("a/b"), [("a/b", 2)])
+        self.assertEqual(tree.remove("a/b"), 2)
+        self.assertNotIn("a/b", tree)
+        self.assertEqual(tree.matches("a/b"), [])
+
+    def test_remove_missing_filter(self):
+        tree = SubscriptionTree()
+        tree.insert("a/b", 1)
+        for filter in ("a/b/c", "a", "x"):
+            with self.subTest(filter), self.assertRaises(KeyError):
+                tree.remove(filter)
+        self.assertEqual(len(tree), 1)
+        self.assertEqual(tree.get("a/b"), 1)
+
+    def test_wildcards_at_root_and_leaf(self):
+        tree = SubscriptionTree()
+        for filter in ("#", "+", "+/b", "a/+", "a/#", "a/b/#", "+/+/c"):
+            tree.insert(filter, None)
+        self.assertEqual(matching(tree, "a"), ["#", "+", "a/#"])
+        self.assertEqual(matching(tree, "a/b"), ["#", "+/b", "a/#", "a/+", "a/b/#"])
+        self.assertEqual(matching(tree, "x/y/c"), ["#", "+/+/c"])
+        self.assertEqual(matching(tree, "a/b/c/d"), ["#", "a/#", "a/b/#"])
+
+    def test_dollar_topics(self):
+        tree = SubscriptionTree()
+        for filter in ("#", "+/broker", "$SYS/#", "$SYS/+"):
+            tree.insert(filter, None)
+        self.assertEqual(matching(tree, "$SYS/broker"), ["$SYS/#", "$SYS/+"])
+        self.assertEqual(matching(tree, "SYS/broker"), ["#", "+/broker"])
+
+    def test_invalid_filters(self):
+        tree = SubscriptionTree()
+        for filter in ("", "a/#/b", "a#", "a+", "a/b+/c"):
+            with self.subTest(filter), self.assertRaisesRegex(ValueError, "invalid filter"):
+                tree.insert(filter, None)
+        self.assertEqual(len(tree), 0)
+
+    def test_iteration_and_len_after_removals(self):
+        tree = SubscriptionTree()
+        for value, filter in enumerate(("a", "a/b", "a/b/c", "+/b", "#")):
+            tree.insert(filter, value)
+        tree.insert("a/b", 10)
+        self.assertEqual(len(tree), 5)
+        tree.remove("a/b")
+        tree.remove("#")
+        self.assertEqual(len(tree), 3)
+        self.assertEqual(sorted(tree), [("+/b", 3), ("a", 0), ("a/b/c", 2)])
+        # The node of "a/b" is kept for "a/b/c", without an entry of its own.
+        self.assertNotIn("a/b", tree)
+        self.assertEqual(matching(tree, "a/b"), ["+/b"])
+        self.assertEqual(repr(tree), "SubscriptionTree(entries=3)")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,132 @@
+"""Round trips of `mqttbytes.v5` packets through `write()` and `v5.read`."""
+
+import unittest
+
+try:
+    from mqttbytes import Protocol, QoS, v5
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+
+def round_trip(packet):
+    data = packet.write()
+    return v5.read(data, len(data))
+
+
+class V5Test(unittest.TestCase):
+    def test_connect(self):
+        connect = v5.Connect("client")
+        connect.keep_alive = 30
+        connect.clean_session = False
+        connect.login = v5.Login("user", "secret")
+        connect.last_will = v5.LastWill("will", b"gone", QoS.AtLeastOnce, True)
+        # Getters return copies, so properties are set before being assigned.
+        properties = v5.ConnectProperties()
+        properties.session_expiry_interval = 60
+        properties.user_properties = [("key", "value")]
+        connect.properties = properties
+        read = round_trip(connect)
+        self.assertIsInstance(read, v5.Connect)
+        self.assertEqual(read.protocol, Protocol.V5)
+        self.assertEqual(read.client_id, "client")
+        self.assertEqual(read.keep_alive, 30)
+        self.assertFalse(read.clean_session)
+        self.assertEqual((read.login.username, read.login.password), ("user", "secret"))
+        self.assertEqual(read.last_will.topic, "will")
+        self.assertEqual(bytes(read.last_will.message), b"gone")
+        self.assertEqual(read.last_will.qos, QoS.AtLeastOnce)
+        self.assertTrue(read.last_will.retain)
+        self.assertEqual(read.properties.session_expiry_interval, 60)
+        self.assertEqual(read.properties.user_properties, [("key", "value")])
+
+    def test_connack(self):
+        connack = v5.ConnAck(v5.ConnectReturnCode.NotAuthorized, False)
+        properties = v5.ConnAckProperties()
+        properties.reason_string = "no"
+        connack.properties = properties
+        read = round_trip(connack)
+        self.assertIsInstance(read, v5.ConnAck)
+        self.assertEqual(read.code, v5.ConnectReturnCode.NotAuthorized)
+        self.assertFalse(read.session_present)
+        self.assertEqual(read.properties.reason_string, "no")
+
+    def test_publish(self):
+        publish = v5.Publish("a/b", QoS.ExactlyOnce, b"payload")
+        publish.pkid = 7
+        publish.retain = True
+        properties = v5.PublishProperties()
+        properties.content_type = "text/plain"
+        properties.message_expiry_interval = 10
+        publish.properties = properties
+        read = round_trip(publish)
+        self.assertIsInstance(read, v5.Publish)
+        self.assertEqual((read.topic, read.qos, read.pkid), ("a/b", QoS.ExactlyOnce, 7))
+        self.assertEqual(bytes(read.payload), b"payload")
+        self.assertTrue(read.retain)
+        self.assertFalse(read.dup)
+        self.assertEqual(read.properties.content_type, "text/plain")
+        self.assertEqual(read.properties.message_expiry_interval, 10)
+
+    def test_acks(self):
+        for cls in (v5.PubAck, v5.PubRec, v5.PubRel, v5.PubComp):
+            with self.subTest(cls=cls.__name__):
+                read = round_trip(cls(42))
+                self.assertIsInstance(read, cls)
+                self.assertEqual(read.pkid, 42)
+
+    def test_unsuback(self):
+        unsuback = v5.UnsubAck(42)
+        unsuback.reasons = [v5.UnsubAckReason.Success, v5.UnsubAckReason.NotAuthorized]
+        read = round_trip(unsuback)
+        self.assertIsInstance(read, v5.UnsubAck)
+        self.assertEqual(read.pkid, 42)
+        self.assertEqual(read.reasons, unsuback.reasons)
+
+    def test_puback_reason(self):
+        puback = v5.PubAck(3)
+        puback.reason = v5.PubAckReason.NoMatchingSubscribers
+        self.assertEqual(round_trip(puback).reason, v5.PubAckReason.NoMatchingSubscribers)
+
+    def test_subscribe(self):
+        subscribe = v5.Subscribe("a/+", QoS.AtLeastOnce)
+        subscribe.add("b/#", QoS.ExactlyOnce)
+        subscribe.pkid = 5
+        read = round_trip(subscribe)
+        self.assertIsInstance(read, v5.Subscribe)
+        self.assertEqual(read.pkid, 5)
+        self.assertEqual(
+            [(f.path, f.qos) for f in read.filters],
+            [("a/+", QoS.AtLeastOnce), ("b/#", QoS.ExactlyOnce)],
+        )
+
+    def test_suback(self):
+        codes = [v5.SubscribeReasonCode.QoS1, v5.SubscribeReasonCode.NotAuthorized]
+        read = round_trip(v5.SubAck(9, codes))
+        self.assertIsInstance(read, v5.SubAck)
+        self.assertEqual(read.pkid, 9)
+        self.assertEqual(read.return_codes, codes)
+
+    def test_unsubscribe(self):
+        unsubscribe = v5.Unsubscribe("a/b")
+        unsubscribe.pkid = 4
+        read = round_trip(unsubscribe)
+        self.assertIsInstance(read, v5.Unsubscribe)
+        self.assertEqual((read.pkid, read.filters), (4, ["a/b"]))
+
+    def test_ping_and_disconnect(self):
+        self.assertIsInstance(round_trip(v5.PingReq()), v5.PingReq)
+        self.assertIsInstance(round_trip(v5.PingResp()), v5.PingResp)
+        disconnect = v5.Disconnect()
+        disconnect.reason_code = v5.DisconnectReasonCode.ServerBusy
+        # mqttbytes only writes the properties length when there are properties.
+        properties = v5.DisconnectProperties()
+        properties.reason_string = "busy"
+        disconnect.properties = properties
+        read = round_trip(disconnect)
+        self.assertIsInstance(read, v5.Disconnect)
+        self.assertEqual(read.reason_code, v5.DisconnectReasonCode.ServerBusy)
+        self.assertEqual(read.properties.reason_string, "busy")
+
+
+if __name__ == "__main__":
+    unittest.main()
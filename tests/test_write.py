@@ -0,0 +1,74 @@
+"""Tests of `v4.write_many` and the `write_into` methods of v4 packets."""
+
+import unittest
+
+try:
+    from mqttbytes import QoS, v4
+except ImportError:
+    raise unittest.SkipTest("mqttbytes is not built")
+
+PACKETS = [
+    v4.Connect("client"),
+    v4.ConnAck(v4.ConnectReturnCode.Success),
+    v4.Publish("a/b", QoS.ExactlyOnce, b"payload", pkid=1),
+    v4.PubAck(1),
+    v4.PubRec(1),
+    v4.PubRel(1),
+    v4.PubComp(1),
+    v4.Subscribe([("a/#", QoS.AtLeastOnce), ("b", QoS.AtMostOnce)], pkid=2),
+    v4.SubAck(2, [QoS.AtLeastOnce, None]),
+    v4.Unsubscribe("a/#", pkid=3),
+    v4.UnsubAck(3),
+    v4.PingReq(),
+    v4.PingResp(),
+    v4.Disconnect(),
+]
+
+
+class WriteManyTest(unittest.TestCase):
+    def test_round_trip_through_read_all(self):
+        # Any iterable of packets is accepted.
+        data = v4.write_many(packet for packet in PACKETS)
+        self.assertEqual(data, b"".join(packet.write() for packet in PACKETS))
+        self.assertEqual(v4.read_all(data, 1024), (PACKETS, len(data)))
+
+    def test_empty(self):
+        self.assertEqual(v4.write_many([]), b"")
+
+    def test_not_a_packet(self):
+        with self.assertRaisesRegex(TypeError, "expected a v4 packet, got bytes"):
+            v4.write_many([v4.PingReq(), b"\xc0\x00"])
+
+
+class WriteIntoTest(unittest.TestCase):
+    def test_offset(self):
+        buffer = bytearray(b"\xff" * 64)
+        offset = 0
+        for packet in PACKETS[:4]:
+            offset += packet.write_into(buffer, offset)
+        self.assertEqual(bytes(buffer[:offset]), v4.write_many(PACKETS[:4]))
+        self.assertEqual(bytes(buffer[offset:]), b"\xff" * (64 - offset))
+
+    def test_buffer_too_small(self):
+        publish = PACKETS[2]
+        size = len(publish.write())
+        buffer = bytearray(b"\xff" * size)
+        with self.assertRaisesRegex(ValueError, f"buffer too small: {size} bytes needed at offset 1, {size - 1} available"):
+            publish.write_into(buffer, 1)
+        with self.assertRaisesRegex(ValueError, "buffer too small"):
+            publish.write_into(buffer, size + 10)
+        # Nothing is written when the packet doesn't fit.
+        self.assertEqual(buffer, b"\xff" * size)
+
+    def test_read_only_buffer(self):
+        for buffer in (bytes(64), memoryview(bytearray(64)).toreadonly()):
+            with self.subTest(type(buffer).__name__), self.assertRaisesRegex(BufferError, "read-only"):
+                v4.PingReq().write_into(buffer)
+
+    def test_non_contiguous_buffer(self):
+        with self.assertRaisesRegex(BufferError, "not C-contiguous"):
+            v4.PingReq().write_into(memoryview(bytearray(64))[::2])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,632 @@
+"""Generates the `.pyi` stubs of the extension from its Rust sources.
+
+The pyo3 bindings in `src` are scanned for `#[pyclass]` types, their
+`#[pymethods]`, `#[pyfunction]`s, exceptions and the classes and functions
+each `#[pymodule]` registers, along with the docstring it sets from a `DOC`
+constant, and Rust types are mapped to Python ones. Values that are typed
+`PyObject` or `&PyAny` in Rust get their Python type from `OVERRIDES`. The
+modules written in Python, `aio` and `client`, need no stubs.
+
+Run `python tools/generate_stubs.py` after changing the bindings, and
+`python tools/generate_stubs.py --check` to fail if the stubs are outdated.
+"""
+
+import argparse
+import ast
+import difflib
+import fnmatch
+import re
+import sys
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+SRC = ROOT / "src"
+STUBS = ROOT / "python" / "mqttbytes"
+
+# Module name, Rust sources and `#[pymodule]` function of each extension module.
+MODULES = {
+    "mqttbytes": (["lib.rs", "error.rs", "tree.rs", "payload.rs"], "mqttbytes"),
+    "mqttbytes.v4": (["v4"], "v4"),
+    "mqttbytes.v5": (["v5"], "v5"),
+    "mqttbytes.testing": (["testing.rs"], "testing"),
+    "mqttbytes.capture": (["capture"], "capture"),
+}
+
+# Python types of values typed `PyObject` or `&PyAny` in Rust, keyed by
+# `module:qualified name:parameter`, or `return` for the returned value.
+# Keys can be glob patterns.
+OVERRIDES = {
+    "mqttbytes:SubscriptionTree.get:return": "Any | None",
+    "mqttbytes:SubscriptionTree.insert:value": "Any",
+    "mqttbytes:SubscriptionTree.__iter__:return": "Iterator[tuple[str, Any]]",
+    "mqttbytes:SubscriptionTree.matches:return": "list[tuple[str, Any]]",
+    "mqttbytes:SubscriptionTree.remove:return": "Any | None",
+    "mqttbytes.v4:read:return": "Packet",
+    "mqttbytes.v4:read_from:return": "tuple[Packet, int]",
+    "mqttbytes.v4:read_all:return": "tuple[list[Packet], int]",
+    "mqttbytes.v4:packet_from_dict:return": "Packet",
+    "mqttbytes.v4:write_many:packets": "Iterable[Packet]",
+    "mqttbytes.v4:StreamDecoder.__next__:return": "Packet",
+    "mqttbytes.v4:*Session.receive_data:return": "list[Packet]",
+    "mqttbytes.v4:PacketIdAllocator.acknowledge:packet": "PubAck | PubComp | SubAck | UnsubAck",
+    "mqttbytes.v4:Subscribe.__init__:path": (
+        "str | SubscribeFilter | tuple[str, QoS | int] | Iterable[SubscribeFilter | tuple[str, QoS | int] | str] | None"
+    ),
+    "mqttbytes.v4:Subscribe.append:filter": "SubscribeFilter | tuple[str, QoS | int]",
+    "mqttbytes.v4:Subscribe.remove:filter": "str | SubscribeFilter | tuple[str, QoS | int]",
+    "mqttbytes.v4:Subscribe.__contains__:filter": "object",
+    "mqttbytes.v4:Subscribe.__iter__:return": "Iterator[SubscribeFilter]",
+    "mqttbytes.v4:Subscribe.__reversed__:return": "Iterator[SubscribeFilter]",
+    "mqttbytes.v4:Unsubscribe.__init__:topic": "str | Iterable[str] | None",
+    "mqttbytes.v4:Unsubscribe.__contains__:topic": "object",
+    "mqttbytes.v4:Unsubscribe.__iter__:return": "Iterator[str]",
+    "mqttbytes.v4:Unsubscribe.__reversed__:return": "Iterator[str]",
+    "mqttbytes.v5:read:return": "Packet",
+    "mqttbytes.capture:CapturedPacket.get_packet:return": "Packet",
+    "mqttbytes.v5:Subscribe.__init__:path": "str | Sequence[SubscribeFilter] | None",
+    "*:*.get_payload:return": "memoryview",
+    "*:*.get_message:return": "memoryview",
+    "*:*.write_into:buffer": "WriteableBuffer",
+    "*:*.__deepcopy__:_memo": "Any",
+    "*:*.__exit__:_*": "object",
+    "*:*.from_dict:data": "dict[str, Any]",
+}
+
+USED_OVERRIDES = set()
+
+PRIMITIVES = {
+    "bool": "bool",
+    "f32": "float",
+    "f64": "float",
+    "String": "str",
+    "str": "str",
+    "PyBytes": "bytes",
+    "PyDict": "dict[str, Any]",
+    "PyObject": "Any",
+    "PyAny": "Any",
+}
+for bits in ("8", "16", "32", "64", "128", "size"):
+    PRIMITIVES["u" + bits] = PRIMITIVES["i" + bits] = "int"
+
+# Dunder methods that are implementation details of the bindings.
+SKIPPED_METHODS = {"__getbuffer__", "__releasebuffer__", "__traverse__", "__clear__"}
+
+HEADER = "# Generated by tools/generate_stubs.py, do not edit.\n"
+
+
+class Item:
+    """A Rust item with its doc comment and attributes."""
+
+    def __init__(self, docs, attrs, header, body):
+        self.docs = docs
+        self.attrs = attrs
+        self.header = header
+        self.body = body
+
+    def attr(self, name):
+        for attr in self.attrs:
+            if attr == name or attr.startswith(name + "("):
+                return attr
+        return None
+
+
+def skip_literal(src, i):
+    """Returns the index after the string, char literal or lifetime at `i`."""
+    if src[i] == "r" and re.match(r'r#*"', src[i:]):
+        hashes = re.match(r"r(#*)", src[i:]).group(1)
+        end = src.index('"' + hashes, i + len(hashes) + 2)
+        return end + 1 + len(hashes)
+    if src[i] == '"':
+        i += 1
+        while src[i] != '"':
+            i += 2 if src[i] == "\\" else 1
+        return i + 1
+    # A char literal, or a lifetime which has no closing quote.
+    match = re.match(r"'(\\.[^']*|[^\\'])'", src[i:])
+    return i + (match.end() if match else 1)
+
+
+def matching(src, i):
+    """Returns the index after the bracket closing the one at `i`."""
+    opening = src[i]
+    closing = {"{": "}", "(": ")", "[": "]"}[opening]
+    depth = 0
+    while i < len(src):
+        c = src[i]
+        if src.startswith("//", i):
+            i = src.index("\n", i)
+        elif src.startswith("/*", i):
+            i = src.index("*/", i) + 2
+        elif c in "\"'" or (c == "r" and re.match(r'r#*"', src[i:]) and not src[i - 1].isalnum()):
+            i = skip_literal(src, i)
+        else:
+            depth += c == opening
+            depth -= c == closing
+            i += 1
+            if depth == 0:
+                return i
+    raise ValueError("unbalanced " + opening)
+
+
+def items(src):
+    """Yields the items of a module or `impl` body."""
+    docs, attrs, i = [], [], 0
+    while True:
+        while i < len(src) and src[i].isspace():
+            i += 1
+        if i >= len(src):
+            return
+        if src.startswith("///", i) or src.startswith("//!", i):
+            end = src.find("\n", i)
+            end = len(src) if end < 0 else end
+            if src.startswith("///", i):
+                docs.append(src[i + 3 : end].removeprefix(" "))
+            i = end
+        elif src.startswith("//", i):
+            i = src.find("\n", i)
+        elif src.startswith("/*", i):
+            i = src.index("*/", i) + 2
+        elif src.startswith("#![", i):
+            i = matching(src, i + 2)
+        elif src.startswith("#[", i):
+            end = matching(src, i + 1)
+            attrs.append(" ".join(src[i + 2 : end - 1].split()))
+            i = end
+        else:
+            start = i
+            if src.startswith("use ", i):
+                i = src.index(";", i) + 1
+                docs, attrs = [], []
+                continue
+            while src[i] not in "{;":
+                if src[i] in "([":
+                    i = matching(src, i)
+                elif src[i] in "\"'":
+                    i = skip_literal(src, i)
+                else:
+                    i += 1
+            header = " ".join(src[start:i].split())
+            body = None
+            if src[i] == "{":
+                end = matching(src, i)
+                body = src[i + 1 : end - 1]
+                i = end
+            else:
+                i += 1
+            yield Item(docs, attrs, header, body)
+            docs, attrs = [], []
+
+
+def split_top_level(text, separator=","):
+    """Splits `text` on `separator` outside of brackets."""
+    parts, depth, start = [], 0, 0
+    for i, c in enumerate(text):
+        if c in "([<":
+            depth += 1
+        elif c in ")]>" and not (c == ">" and text[i - 1] == "-"):
+            depth -= 1
+        elif c == separator and depth == 0:
+            parts.append(text[start:i])
+            start = i + 1
+    parts.append(text[start:])
+    return [part.strip() for part in parts if part.strip()]
+
+
+def parse_fn(header):
+    """Returns the name, parameters and return type of a function header."""
+    match = re.match(r"(?:pub(?:\([\w:]+\))? )?(?:async )?fn (\w+)(?:<[^(]*>)?\s*\(", header)
+    if match is None:
+        return None
+    params_end = matching(header, match.end() - 1)
+    params = []
+    for param in split_top_level(header[match.end() : params_end - 1]):
+        if param.endswith("self") or param.startswith("slf:"):
+            continue
+        name, rust_type = (part.strip() for part in param.split(":", 1))
+        params.append((name.removeprefix("mut "), rust_type))
+    rest = header[params_end:].strip()
+    returns = rest[2:].split(" where ")[0].strip() if rest.startswith("->") else "()"
+    return match.group(1), params, returns
+
+
+def parse_defaults(attr):
+    """Returns the defaults of `#[args(...)]` or `#[pyfunction(...)]`."""
+    if attr is None or "(" not in attr:
+        return {}
+    defaults = {}
+    for arg in split_top_level(attr[attr.index("(") + 1 : -1]):
+        if arg.strip() == '"*"':
+            # Keyword-only from the next argument on, kept in order.
+            defaults["*"] = None
+            continue
+        name, value = (part.strip() for part in arg.split("=", 1))
+        defaults[name] = python_default(ast.literal_eval(value))
+    return defaults
+
+
+def python_default(rust):
+    """Renders a default value given as Rust code the way stubs do."""
+    if rust in ("true", "false"):
+        return rust.capitalize()
+    if rust == "None" or re.fullmatch(r"-?\d+(\.\d+)?", rust):
+        return rust
+    if re.fullmatch(r'"[^"\\]*"', rust):
+        return rust
+    if rust == "String::new()":
+        return '""'
+    return "..."
+
+
+class Stub:
+    """Python declarations of one module, along with the names they use."""
+
+    def __init__(self, module, classes):
+        self.module = module
+        self.classes = classes
+        self.imports = {}
+        self.typing = set()
+
+    def override(self, qualname, param):
+        key = f"{self.module}:{qualname}:{param}"
+        for pattern, python_type in OVERRIDES.items():
+            if fnmatch.fnmatchcase(key, pattern):
+                USED_OVERRIDES.add(pattern)
+                return python_type
+        return None
+
+    def python_type(self, rust, owner, param=True):
+        """Maps a Rust type of a parameter or returned value to Python."""
+        rust = re.sub(r"'\w+\s*,?\s*", "", rust)
+        rust = re.sub(r"^&(mut )?", "", rust.strip()).strip()
+        if rust.startswith("(") and rust.endswith(")"):
+            inner = split_top_level(rust[1:-1])
+            if not inner:
+                return "None"
+            return "tuple[" + ", ".join(self.python_type(t, owner, param) for t in inner) + "]"
+        if rust == "[u8]":
+            return "bytes"
+        match = re.fullmatch(r"(?:::)?([\w:]+?)(?:<(.*)>)?", rust)
+        if match is None:
+            raise ValueError(f"unsupported type {rust}")
+        name = match.group(1).split("::")[-1]
+        args = split_top_level(match.group(2)) if match.group(2) else []
+        if name in ("PyResult", "Result"):
+            return self.python_type(args[0], owner, param)
+        if name in ("Py", "PyRef", "PyRefMut", "PyCell", "Box", "Arc"):
+            return self.python_type(args[0], owner, param)
+        if name == "Option":
+            return self.python_type(args[0], owner, param) + " | None"
+        if name == "Vec":
+            if args[0] == "u8":
+                return "bytes" if param else "list[int]"
+            item = self.python_type(args[0], owner, param)
+            if param:
+                self.typing.add("Sequence")
+                return f"Sequence[{item}]"
+            return f"list[{item}]"
+        if name in ("HashMap", "BTreeMap"):
+            key, value = (self.python_type(arg, owner, param) for arg in args)
+            return f"dict[{key}, {value}]"
+        if name == "PathBuf":
+            self.imports["os"] = self.imports.get("os", set()) | {"PathLike"}
+            return "str | PathLike[str]"
+        if name == "PyBuffer":
+            self.imports["_typeshed"] = self.imports.get("_typeshed", set()) | {"ReadableBuffer"}
+            return "ReadableBuffer"
+        if name == "Self":
+            return owner
+        if name == "QoSArg":
+            return self.python_type("QoS", owner, param) + " | int"
+        if name in PRIMITIVES:
+            if PRIMITIVES[name].startswith(("Any", "dict")):
+                self.typing.add("Any")
+            return PRIMITIVES[name]
+        if name in self.classes.get(self.module, {}):
+            return name
+        for module, classes in self.classes.items():
+            if name in classes:
+                self.imports.setdefault(module, set()).add(name)
+                return name
+        raise ValueError(f"unsupported type {rust} in {self.module}:{owner}")
+
+    def use(self, python_type):
+        """Records the imports needed by a type taken from `OVERRIDES`."""
+        for name in re.findall(r"[A-Za-z_]\w*", python_type):
+            if name in ("Any", "Iterable", "Iterator", "Sequence", "Callable"):
+                self.typing.add(name)
+            elif name in ("ReadableBuffer", "WriteableBuffer"):
+                self.imports.setdefault("_typeshed", set()).add(name)
+            elif name == "Packet" and self.module not in ("mqttbytes.v4", "mqttbytes.v5"):
+                # Other modules only decode `v4` packets.
+                self.imports.setdefault("mqttbytes.v4", set()).add(name)
+
+    def signature(self, qualname, owner, params, defaults, setter=False):
+        """Renders the parameters of a function, without `self`."""
+        rendered = []
+        names = list(defaults)
+        keyword_only = names[names.index("*") + 1] if "*" in defaults else None
+        for i, (name, rust_type) in enumerate(params):
+            if rust_type.strip().lstrip("&") in ("Python", "CompareOp") or rust_type.startswith(
+                "Python<"
+            ):
+                continue
+            python_type = self.override(qualname, name)
+            if python_type is None:
+                python_type = self.python_type(rust_type, owner)
+            else:
+                self.use(python_type)
+            default = defaults.get(name)
+            trailing = all(
+                p_name in defaults or p_type.startswith("Option<") for p_name, p_type in params[i:]
+            )
+            if default is None and rust_type.startswith("Option<") and trailing and not setter:
+                default = "None"
+            if "*" in defaults and name == keyword_only:
+                rendered.append("*")
+            rendered.append(f"{name}: {python_type}" + (f" = {default}" if default else ""))
+        return rendered
+
+    def returns(self, qualname, owner, rust_type):
+        python_type = self.override(qualname, "return")
+        if python_type is not None:
+            self.use(python_type)
+            return python_type
+        return self.python_type(rust_type, owner, param=False)
+
+
+def docstring(docs, indent):
+    text = re.sub(r"```\w+", "```", "\n".join(docs).strip())
+    if not text:
+        return []
+    text = text.replace("\\", "\\\\").replace('"""', '\\"\\"\\"')
+    lines = text.split("\n")
+    if len(lines) == 1:
+        return [f'{indent}"""{lines[0]}"""']
+    return [f'{indent}"""{lines[0]}'] + [
+        f"{indent}{line}" if line else "" for line in lines[1:]
+    ] + [f'{indent}"""']
+
+
+def rust_sources(paths):
+    for path in paths:
+        path = SRC / path
+        yield from sorted(path.glob("*.rs")) if path.is_dir() else [path]
+
+
+def collect():
+    """Parses every module and returns its items by kind."""
+    modules = {}
+    for module, (paths, init) in MODULES.items():
+        classes, methods, functions, exceptions, registered = {}, {}, {}, {}, []
+        docs = []
+        for path in rust_sources(paths):
+            source = path.read_text()
+            for item in items(source):
+                pyclass = item.attr("pyclass")
+                if pyclass is not None:
+                    kind, name = re.search(r"\b(struct|enum) (\w+)", item.header).groups()
+                    classes[name] = (item, kind)
+                elif item.attr("pymethods") is not None:
+                    name = re.match(r"impl (\w+)", item.header).group(1)
+                    methods.setdefault(name, []).extend(items(item.body))
+                elif item.attr("pyfunction") is not None:
+                    functions[parse_fn(item.header)[0]] = item
+                elif parse_fn(item.header) and parse_fn(item.header)[0] == init:
+                    registered = re.findall(
+                        r"add_class::<(?:\w+::)*(\w+)>|wrap_pyfunction!\((\w+)", item.body
+                    )
+                    registered += [
+                        (name, "") for name in re.findall(r'm\.add\(\s*"(\w+)",\s*_py\.get_type', item.body)
+                    ]
+                elif parse_fn(item.header) and parse_fn(item.header)[0] == "register":
+                    registered += [
+                        (name, "")
+                        for name in re.findall(r'm\.add\(\s*"(\w+)",\s*_py\.get_type', item.body)
+                    ]
+            # The docstring set by the module with `m.add("__doc__", DOC)`.
+            match = re.search(r'\nconst DOC: &str = "((?:[^"\\]|\\.)*)";', source)
+            if match:
+                docs = match.group(1).strip("\n").split("\n")
+            for name, base, doc in re.findall(
+                r'create_exception!\(\s*\w+,\s*(\w+),\s*(\w+),\s*"((?:[^"\\]|\\.)*)"\s*\)', source
+            ):
+                exceptions[name] = (base, doc)
+            for name, attribute, value in re.findall(
+                r'(\w+)::new_err\(message\),\s*Some\(\("(\w+)",\s*(.*?)\.into_py', source
+            ):
+                exceptions.setdefault("attributes", {})[name] = (
+                    attribute,
+                    "PacketType" if value.startswith("PacketType") else "int",
+                )
+        modules[module] = {
+            "classes": {
+                name: classes[name] for name, function in registered if function == "" and name in classes
+            },
+            "methods": methods,
+            "functions": [functions[name] for _, name in registered if name],
+            "exceptions": [(name, exceptions[name]) for name, _ in registered if name in exceptions],
+            "attributes": exceptions.get("attributes", {}),
+            "docs": docs,
+        }
+        # `add_class` and `m.add` both yield unnamed functions; keep the order of registration.
+    return modules
+
+
+def render_function(stub, item, qualname, owner=None, indent=""):
+    name, params, returns = parse_fn(item.header)
+    attr = item.attr("args") or item.attr("pyfunction")
+    lines = docstring(item.docs, indent + "    ")
+    body = " ..." if not lines else ""
+    decorators = []
+    if item.attr("staticmethod") is not None:
+        decorators.append("@staticmethod")
+    if item.attr("getter") is not None:
+        decorators.append("@property")
+        name = name.removeprefix("get_")
+        qualname = qualname.rsplit(".", 1)[0] + "." + "get_" + name
+    if item.attr("setter") is not None:
+        name = name.removeprefix("set_")
+        decorators.append(f"@{name}.setter")
+    if item.attr("new") is not None:
+        name = "__init__"
+        qualname = qualname.rsplit(".", 1)[0] + ".__init__"
+    rendered = stub.signature(
+        qualname, owner, params, parse_defaults(attr), item.attr("setter") is not None
+    )
+    if owner is not None and item.attr("staticmethod") is None:
+        rendered.insert(0, "self")
+    if name == "__init__":
+        python_return = "None"
+    elif name == "__next__" and returns.startswith(("Option<", "Result<Option<", "PyResult<Option<")):
+        python_return = stub.returns(qualname, owner, returns).removesuffix(" | None")
+    elif item.attr("setter") is not None:
+        python_return = "None"
+    else:
+        python_return = stub.returns(qualname, owner, returns)
+    out = [f"{indent}{decorator}" for decorator in decorators]
+    out.append(f"{indent}def {name}({', '.join(rendered)}) -> {python_return}:{body}")
+    return out + lines
+
+
+def render_class(stub, name, item, kind, methods):
+    out = [f"class {name}:"]
+    out += docstring(item.docs, "    ")
+    if kind == "enum":
+        stub.typing.add("ClassVar")
+        for variant in items(item.body.replace(",", ";")):
+            variant_name = variant.header.split("=")[0].strip()
+            out.append(f"    {variant_name}: ClassVar[{name}]")
+            out += docstring(variant.docs, "    ")
+        out.append("    def __int__(self) -> int: ...")
+        out.append("    def __eq__(self, other: object) -> bool: ...")
+        out.append("    def __ne__(self, other: object) -> bool: ...")
+    getters = {}
+    for method in methods:
+        parsed = parse_fn(method.header)
+        if parsed is None or parsed[0] in SKIPPED_METHODS:
+            continue
+        method_name = parsed[0]
+        if method_name == "__richcmp__":
+            out.append("    def __eq__(self, other: object) -> bool: ...")
+            out.append("    def __ne__(self, other: object) -> bool: ...")
+            continue
+        if method.attr("setter") is not None and method_name.removeprefix("set_") not in getters:
+            raise ValueError(f"setter {name}.{method_name} has no getter before it")
+        if method.attr("getter") is not None:
+            getters[method_name.removeprefix("get_")] = True
+        out += render_function(stub, method, f"{name}.{method_name}", name, "    ")
+    if len(out) == 1:
+        out.append("    ...")
+    return out
+
+
+def render_module(module, parsed, modules):
+    classes = {m: set(p["classes"]) for m, p in modules.items()}
+    classes[module] = set(parsed["classes"])
+    stub = Stub(module, classes)
+    body = []
+    attributes = parsed["attributes"]
+    for name, (base, doc) in parsed["exceptions"]:
+        base = {"PyException": "Exception"}.get(base, base)
+        body.append(f"class {name}({base}):")
+        body += docstring([doc], "    ")
+        if name in attributes:
+            attribute, python_type = attributes[name]
+            body.append(f"    {attribute}: {python_type}")
+        elif not doc:
+            body.append("    ...")
+        body.append("")
+    for name, (item, kind) in parsed["classes"].items():
+        body += render_class(stub, name, item, kind, parsed["methods"].get(name, []))
+        body.append("")
+    for item in parsed["functions"]:
+        body += render_function(stub, item, parse_fn(item.header)[0])
+        body.append("")
+    packet = packet_alias(module)
+    if packet:
+        # `typing.TypeAlias` is missing before Python 3.10.
+        stub.imports.setdefault("typing_extensions", set()).add("TypeAlias")
+        body.insert(0, "")
+        body.insert(0, f"Packet: TypeAlias = {packet}")
+    return finish(stub, body, parsed["docs"])
+
+
+def packet_alias(module):
+    """Returns the union of the classes that `read` can return."""
+    if module not in ("mqttbytes.v4", "mqttbytes.v5"):
+        return None
+    source = (SRC / module.split(".")[1] / "mod.rs").read_text()
+    name = "packet_into_py" if "fn packet_into_py" in source else "read"
+    match = re.search(rf"fn {name}\b.*?\n}}\n", source, re.S)
+    names = re.findall(r"=> (?:Ok\()?(\w+)(?:::from|\()", match.group(0))
+    return " | ".join(dict.fromkeys(names))
+
+
+def finish(stub, body, docs):
+    header = [HEADER.rstrip("\n")] + docstring(docs, "")
+    if stub.typing:
+        header.append(f"from typing import {', '.join(sorted(stub.typing))}")
+    for module in sorted(stub.imports):
+        header.append(f"from {module} import {', '.join(sorted(stub.imports[module]))}")
+    while body and body[-1] == "":
+        body.pop()
+    return "\n".join(header + [""] + body) + "\n"
+
+
+def render_init(modules):
+    """Adds the submodules and re-exports to the stub of `mqttbytes`."""
+    lines = [
+        "from . import aio as aio, capture as capture, client as client, testing as testing",
+        "from . import v4 as v4, v5 as v5",
+        "from .client import AsyncClient as AsyncClient, Client as Client",
+        "",
+        "__version__: str",
+        "",
+    ]
+    return lines
+
+
+def generate():
+    """Returns the content of every stub file by file name."""
+    modules = collect()
+    stubs = {}
+    for module, parsed in modules.items():
+        text = render_module(module, parsed, modules)
+        if module == "mqttbytes":
+            head, rest = text.split("\n\n", 1)
+            text = head + "\n" + "\n".join(render_init(modules)) + "\n" + rest
+            stubs["__init__.pyi"] = text
+        else:
+            stubs[module.split(".")[1] + ".pyi"] = text
+    unused = set(OVERRIDES) - USED_OVERRIDES
+    if unused:
+        raise ValueError(f"overrides matching nothing: {', '.join(sorted(unused))}")
+    return stubs
+
+
+def main():
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--check", action="store_true", help="fail if the stubs are outdated")
+    args = parser.parse_args()
+    stale = []
+    for name, text in generate().items():
+        path = STUBS / name
+        current = path.read_text() if path.exists() else ""
+        if current == text:
+            continue
+        if args.check:
+            stale.append(name)
+            sys.stdout.writelines(
+                difflib.unified_diff(
+                    current.splitlines(True), text.splitlines(True), f"a/{name}", f"b/{name}"
+                )
+            )
+        else:
+            path.write_text(text)
+            print(f"wrote {path.relative_to(ROOT)}")
+    if stale:
+        sys.exit(f"outdated stubs: {', '.join(stale)}; run tools/generate_stubs.py")
+
+
+if __name__ == "__main__":
+    main()